@@ -0,0 +1,407 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use crate::utils::{FileOrUrl, parse_string_into_scale_value, validate_url_security};
+use clap::{Parser, Subcommand};
+use codec::Decode;
+use color_eyre::eyre::{Context, eyre};
+use scale_value::{Composite, ValueDef};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use subxt::client::{OfflineClientT, RuntimeVersion};
+use subxt::config::DefaultExtrinsicParamsBuilder as Params;
+use subxt::tx::{
+    PartialTransaction, SubmittableTransaction, TransactionDescription, UnsignedTransactionEnvelope,
+};
+use subxt::utils::{AccountId32, MultiSignature};
+use subxt::{Metadata, OfflineClient, OnlineClient, PolkadotConfig};
+use subxt_signer::{SecretUri, sr25519};
+use subxt_utils_fetchmetadata::Url;
+
+/// Prepare, sign and submit transactions in separate steps, so that signing can happen on
+/// another (for instance, air-gapped) machine.
+///
+/// # Example
+///
+/// Prepare a transaction to be signed by some account, using a connected node to obtain the
+/// account nonce and other details:
+///
+/// ```text
+/// subxt tx prepare --account 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY System remark '(0x68656c6c6f,)' -o unsigned.json
+/// ```
+///
+/// Sign the prepared transaction. This does not need a connection to a node, but does need
+/// the metadata that the transaction was prepared with, in order to decode and show what is
+/// being signed:
+///
+/// ```text
+/// subxt tx sign unsigned.json --file metadata.scale --suri //Alice -o signed.json
+/// ```
+///
+/// Submit the signed transaction and wait for it to be finalized:
+///
+/// ```text
+/// subxt tx submit signed.json
+/// ```
+#[derive(Debug, Parser)]
+pub struct Opts {
+    #[command(subcommand)]
+    subcommand: TxSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum TxSubcommand {
+    Prepare(PrepareOpts),
+    Sign(SignOpts),
+    Submit(SubmitOpts),
+}
+
+/// Prepare an unsigned transaction, writing it out in a portable JSON format.
+#[derive(Debug, Parser)]
+pub struct PrepareOpts {
+    /// The url of the substrate node to connect to.
+    #[clap(long)]
+    url: Option<Url>,
+    /// Allow insecure URLs e.g. URLs starting with ws:// or http:// without SSL encryption
+    #[clap(long, short)]
+    allow_insecure: bool,
+    /// The SS58 or hex encoded account that will sign the transaction.
+    #[clap(long)]
+    account: AccountId32,
+    /// The account nonce to use. Defaults to the next nonce of the account.
+    #[clap(long)]
+    nonce: Option<u64>,
+    /// The tip to give to the block author.
+    #[clap(long, default_value_t = 0)]
+    tip: u128,
+    /// The number of blocks that the transaction will be valid for.
+    #[clap(long)]
+    mortality: Option<u64>,
+    /// Write the unsigned transaction to the provided file path.
+    #[clap(long, short, value_parser)]
+    output_file: Option<PathBuf>,
+    /// The name of the pallet containing the call.
+    pallet: String,
+    /// The name of the call.
+    call: String,
+    /// The call arguments, as a SCALE value.
+    #[clap(required = false)]
+    trailing_args: Vec<String>,
+}
+
+/// Sign a prepared transaction with an sr25519 key.
+///
+/// The call and transaction extensions are decoded from the transaction bytes using the given
+/// metadata, which must be the metadata that the transaction was prepared with, and are shown
+/// so that they can be checked.
+#[derive(Debug, Parser)]
+pub struct SignOpts {
+    #[command(flatten)]
+    file_or_url: FileOrUrl,
+    /// Allow insecure URLs e.g. URLs starting with ws:// or http:// without SSL encryption
+    #[clap(long, short)]
+    allow_insecure: bool,
+    /// The path to the unsigned transaction created with `subxt tx prepare`.
+    envelope: PathBuf,
+    /// The secret URI of the sr25519 key to sign with, for instance a mnemonic phrase
+    /// or a development account such as `//Alice`.
+    #[clap(long)]
+    suri: String,
+    /// Write the signed transaction to the provided file path.
+    #[clap(long, short, value_parser)]
+    output_file: Option<PathBuf>,
+}
+
+/// Submit a signed transaction and wait for it to be finalized.
+#[derive(Debug, Parser)]
+pub struct SubmitOpts {
+    /// The url of the substrate node to connect to.
+    #[clap(long)]
+    url: Option<Url>,
+    /// Allow insecure URLs e.g. URLs starting with ws:// or http:// without SSL encryption
+    #[clap(long, short)]
+    allow_insecure: bool,
+    /// The path to the signed transaction created with `subxt tx sign`.
+    signed: PathBuf,
+}
+
+/// An unsigned transaction along with an sr25519 signature for it.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SignedEnvelope {
+    envelope: UnsignedTransactionEnvelope,
+    account: AccountId32,
+    signature: String,
+}
+
+pub async fn run(opts: Opts, output: &mut impl std::io::Write) -> color_eyre::Result<()> {
+    match opts.subcommand {
+        TxSubcommand::Prepare(opts) => prepare(opts, output).await,
+        TxSubcommand::Sign(opts) => sign(opts, output).await,
+        TxSubcommand::Submit(opts) => submit(opts, output).await,
+    }
+}
+
+async fn prepare(opts: PrepareOpts, output: &mut impl std::io::Write) -> color_eyre::Result<()> {
+    validate_url_security(opts.url.as_ref(), opts.allow_insecure)?;
+    let client = create_client(opts.url.as_ref()).await?;
+
+    let args = parse_string_into_scale_value(&opts.trailing_args.join(" "))?;
+    let args = match args.value {
+        ValueDef::Composite(composite) => composite,
+        _ => Composite::Unnamed(vec![args]),
+    };
+    let payload = subxt::dynamic::tx(opts.pallet, opts.call, args);
+
+    let mut params = Params::new().tip(opts.tip);
+    if let Some(nonce) = opts.nonce {
+        params = params.nonce(nonce);
+    }
+    if let Some(mortality) = opts.mortality {
+        params = params.mortal(mortality);
+    }
+
+    let envelope = client
+        .tx()
+        .create_v4_partial(&payload, &opts.account, params.build())
+        .await?
+        .to_envelope()?;
+
+    write_json(&envelope, opts.output_file, output)
+}
+
+async fn sign(opts: SignOpts, output: &mut impl std::io::Write) -> color_eyre::Result<()> {
+    validate_url_security(opts.file_or_url.url.as_ref(), opts.allow_insecure)?;
+    let bytes = opts.file_or_url.fetch().await?;
+    let metadata = Metadata::decode(&mut &bytes[..])?;
+
+    let envelope: UnsignedTransactionEnvelope = read_json(&opts.envelope)?;
+    let description = describe(&envelope, metadata)?;
+    let suri: SecretUri = opts.suri.parse()?;
+    let keypair = sr25519::Keypair::from_uri(&suri)?;
+
+    // Show what is being signed, without getting mixed up with the output.
+    eprintln!("Signing:\n{description}");
+
+    let signature = keypair.sign(&envelope.signer_payload());
+    let signed = SignedEnvelope {
+        envelope,
+        account: keypair.public_key().to_account_id(),
+        signature: format!("0x{}", hex::encode(signature.0)),
+    };
+
+    write_json(&signed, opts.output_file, output)
+}
+
+async fn submit(opts: SubmitOpts, output: &mut impl std::io::Write) -> color_eyre::Result<()> {
+    validate_url_security(opts.url.as_ref(), opts.allow_insecure)?;
+    let client = create_client(opts.url.as_ref()).await?;
+
+    let signed: SignedEnvelope = read_json(&opts.signed)?;
+    let tx = signed_transaction(client, signed)?;
+
+    let tx_in_block = tx.submit_and_watch().await?.wait_for_finalized().await?;
+    tx_in_block.wait_for_success().await?;
+
+    writeln!(
+        output,
+        "Transaction {:?} finalized in block {:?}",
+        tx_in_block.extrinsic_hash(),
+        tx_in_block.block_hash()
+    )?;
+    Ok(())
+}
+
+/// Decode the call and transaction extensions from the bytes in the envelope, rather than
+/// trusting the description that it contains.
+fn describe(
+    envelope: &UnsignedTransactionEnvelope,
+    metadata: Metadata,
+) -> color_eyre::Result<TransactionDescription> {
+    // Only the metadata is needed in order to decode the transaction.
+    let runtime_version = RuntimeVersion {
+        spec_version: 0,
+        transaction_version: 0,
+    };
+    let client =
+        OfflineClient::<PolkadotConfig>::new(Default::default(), runtime_version, metadata);
+    let description = PartialTransaction::from_envelope(client, envelope.clone())?.describe()?;
+    Ok(description)
+}
+
+/// Attach the signature in a signed envelope to the transaction that it signs.
+fn signed_transaction<C: OfflineClientT<PolkadotConfig>>(
+    client: C,
+    signed: SignedEnvelope,
+) -> color_eyre::Result<SubmittableTransaction<PolkadotConfig, C>> {
+    let signature: [u8; 64] = hex::decode(signed.signature.trim_start_matches("0x"))?
+        .try_into()
+        .map_err(|_| eyre!("An sr25519 signature is expected to be 64 bytes long"))?;
+
+    let tx = PartialTransaction::from_envelope(client, signed.envelope)?
+        .sign_with_account_and_signature(&signed.account, &MultiSignature::Sr25519(signature));
+    Ok(tx)
+}
+
+async fn create_client(url: Option<&Url>) -> color_eyre::Result<OnlineClient<PolkadotConfig>> {
+    let client = match url {
+        Some(url) => OnlineClient::<PolkadotConfig>::from_url(url).await?,
+        None => OnlineClient::<PolkadotConfig>::new().await?,
+    };
+    Ok(client)
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &PathBuf) -> color_eyre::Result<T> {
+    let file =
+        std::fs::File::open(path).with_context(|| format!("Cannot open {}", path.display()))?;
+    serde_json::from_reader(std::io::BufReader::new(file))
+        .with_context(|| format!("Cannot parse {}", path.display()))
+}
+
+fn write_json<T: Serialize>(
+    value: &T,
+    output_file: Option<PathBuf>,
+    output: &mut impl std::io::Write,
+) -> color_eyre::Result<()> {
+    let json = serde_json::to_string_pretty(value)?;
+    match output_file {
+        Some(path) => std::fs::write(path, json)?,
+        None => writeln!(output, "{json}")?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use codec::Encode;
+    use subxt::utils::MultiAddress;
+    use subxt_signer::sr25519::dev;
+
+    const METADATA_PATH: &str = "../artifacts/polkadot_metadata_full.scale";
+
+    async fn run(args: &[&str]) -> color_eyre::Result<String> {
+        let args = std::iter::once(&"tx").chain(args);
+        let opts: Opts = clap::Parser::try_parse_from(args)?;
+        let mut output: Vec<u8> = Vec::new();
+        super::run(opts, &mut output).await?;
+        Ok(String::from_utf8(output).unwrap())
+    }
+
+    fn offline_client() -> OfflineClient<PolkadotConfig> {
+        let bytes = std::fs::read(METADATA_PATH).unwrap();
+        let metadata = Metadata::decode(&mut &bytes[..]).unwrap();
+        let runtime_version = RuntimeVersion {
+            spec_version: 1,
+            transaction_version: 1,
+        };
+        OfflineClient::new(Default::default(), runtime_version, metadata)
+    }
+
+    fn unsigned_envelope() -> UnsignedTransactionEnvelope {
+        let call = subxt::dynamic::tx(
+            "System",
+            "remark",
+            vec![scale_value::Value::from_bytes("hello")],
+        );
+        offline_client()
+            .tx()
+            .create_v4_partial_offline(&call, Params::new().nonce(1).build())
+            .unwrap()
+            .to_envelope()
+            .unwrap()
+    }
+
+    // Write the envelope to a file that's unique to the calling test.
+    fn write_envelope(name: &str, envelope: &UnsignedTransactionEnvelope) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("subxt-cli-tx-{name}-{}.json", std::process::id()));
+        std::fs::write(&path, serde_json::to_string(envelope).unwrap()).unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn sign_and_attach_signature() {
+        let envelope = unsigned_envelope();
+        let path = write_envelope("sign", &envelope);
+
+        let output = run(&[
+            "sign",
+            path.to_str().unwrap(),
+            "--file",
+            METADATA_PATH,
+            "--suri",
+            "//Alice",
+        ])
+        .await
+        .unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        let signed: SignedEnvelope = serde_json::from_str(&output).unwrap();
+        assert_eq!(signed.envelope, envelope);
+        assert_eq!(signed.account, dev::alice().public_key().to_account_id());
+
+        let signature: [u8; 64] = hex::decode(signed.signature.trim_start_matches("0x"))
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert!(sr25519::verify(
+            &sr25519::Signature(signature),
+            envelope.signer_payload(),
+            &dev::alice().public_key()
+        ));
+
+        // This is what `subxt tx submit` will submit:
+        let tx = signed_transaction(offline_client(), signed).unwrap();
+        let extrinsics = subxt::ext::subxt_core::blocks::decode_from::<PolkadotConfig>(
+            vec![tx.encoded().to_vec()],
+            offline_client().metadata(),
+        )
+        .unwrap();
+        let ext = extrinsics.iter().next().unwrap();
+        assert_eq!(ext.call_bytes(), envelope.call_data);
+        let address: MultiAddress<AccountId32, ()> = dev::alice().public_key().into();
+        assert_eq!(ext.address_bytes().unwrap(), address.encode());
+    }
+
+    #[tokio::test]
+    async fn sign_rejects_envelopes_for_other_metadata() {
+        let path = write_envelope("other-metadata", &unsigned_envelope());
+
+        let result = run(&[
+            "sign",
+            path.to_str().unwrap(),
+            "--file",
+            "../artifacts/polkadot_metadata_small.scale",
+            "--suri",
+            "//Alice",
+        ])
+        .await;
+        std::fs::remove_file(path).unwrap();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn descriptions_are_decoded_from_the_transaction() {
+        let mut envelope = unsigned_envelope();
+        envelope.call = "Balances(transfer_all { .. })".into();
+
+        let description = describe(&envelope, offline_client().metadata())
+            .unwrap()
+            .to_string();
+        assert!(description.starts_with("Call: System(remark { remark: 0x68656c6c6f })"));
+        assert!(!description.contains("Balances"));
+    }
+
+    #[test]
+    fn signatures_must_be_sr25519() {
+        let signed = SignedEnvelope {
+            envelope: unsigned_envelope(),
+            account: dev::alice().public_key().to_account_id(),
+            signature: "0x0102".into(),
+        };
+        assert!(signed_transaction(offline_client(), signed).is_err());
+    }
+}
@@ -25,6 +25,10 @@ pub struct FileOrUrl {
     /// The path to the encoded metadata file.
     #[clap(long, value_parser)]
     pub file: Option<PathOrStdIn>,
+    /// The path to a (possibly compressed) Wasm runtime file. The runtime is executed
+    /// locally in order to obtain the metadata.
+    #[clap(long, value_parser)]
+    pub wasm: Option<PathBuf>,
     /// Specify the metadata version.
     ///
     ///  - "latest": Use the latest stable version available.
@@ -44,6 +48,7 @@ impl FromStr for FileOrUrl {
             Ok(FileOrUrl {
                 url: None,
                 file: Some(path),
+                wasm: None,
                 version: None,
             })
         } else {
@@ -52,6 +57,7 @@ impl FromStr for FileOrUrl {
                 .map(|uri| FileOrUrl {
                     url: Some(uri),
                     file: None,
+                    wasm: None,
                     version: None,
                 })
         }
@@ -87,6 +93,15 @@ impl FromStr for PathOrStdIn {
 impl FileOrUrl {
     /// Fetch the metadata bytes.
     pub async fn fetch(&self) -> color_eyre::Result<Vec<u8>> {
+        if let Some(wasm) = &self.wasm {
+            // Can't provide --wasm alongside --file or --url
+            if self.file.is_some() || self.url.is_some() {
+                bail!("specify one of `--url`, `--file` or `--wasm` but not more than one")
+            }
+            let version = self.version.unwrap_or_default();
+            return Ok(fetch_metadata::from_wasm_file_blocking(wasm, version)?);
+        }
+
         match (&self.file, &self.url, self.version) {
             // Can't provide both --file and --url
             (Some(_), Some(_), _) => {
@@ -327,7 +342,43 @@ pub fn validate_url_security(url: Option<&Url>, allow_insecure: bool) -> color_e
 #[cfg(test)]
 mod tests {
     use crate::utils::{FileOrUrl, PathOrStdIn};
+    use std::path::PathBuf;
     use std::str::FromStr;
+    use subxt_utils_fetchmetadata::MetadataVersion;
+
+    const WESTEND_RUNTIME: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../artifacts/westend_runtime.compact.compressed.wasm"
+    );
+
+    fn wasm_source(version: Option<MetadataVersion>) -> FileOrUrl {
+        FileOrUrl {
+            url: None,
+            file: None,
+            wasm: Some(PathBuf::from(WESTEND_RUNTIME)),
+            version,
+        }
+    }
+
+    #[tokio::test]
+    async fn fetch_from_wasm() {
+        let bytes = wasm_source(None).fetch().await.unwrap();
+        assert_eq!(&bytes[..4], b"meta");
+    }
+
+    #[tokio::test]
+    async fn fetch_from_wasm_respects_version() {
+        // Version 1 doesn't exist, so we should fail rather than fall back to another version.
+        let source = wasm_source(Some(MetadataVersion::Version(1)));
+        assert!(source.fetch().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn wasm_cannot_be_combined_with_other_sources() {
+        let mut source = wasm_source(None);
+        source.file = Some(PathOrStdIn::StdIn);
+        assert!(source.fetch().await.is_err());
+    }
 
     #[test]
     fn parsing() {
@@ -336,6 +387,7 @@ mod tests {
             Ok(FileOrUrl {
                 url: None,
                 file: Some(PathOrStdIn::StdIn),
+                wasm: None,
                 version: None
             })
         ),);
@@ -345,6 +397,7 @@ mod tests {
             Ok(FileOrUrl {
                 url: None,
                 file: Some(PathOrStdIn::StdIn),
+                wasm: None,
                 version: None
             })
         ),);
@@ -354,6 +407,7 @@ mod tests {
             Ok(FileOrUrl {
                 url: None,
                 file: Some(PathOrStdIn::Path(_)),
+                wasm: None,
                 version: None
             })
         ),);
@@ -365,6 +419,7 @@ mod tests {
             Ok(FileOrUrl {
                 url: Some(_),
                 file: None,
+                wasm: None,
                 version: None
             })
         ));
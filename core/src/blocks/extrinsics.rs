@@ -6,12 +6,16 @@ use super::BlockError;
 use crate::blocks::extrinsic_transaction_extensions::ExtrinsicTransactionExtensions;
 use crate::{
     Metadata,
-    config::{Config, HashFor, Hasher},
+    config::{Config, HashFor, Hasher, transaction_extensions::CheckMetadataHash},
     error::{Error, MetadataError},
+    utils::Era,
 };
+use alloc::format;
 use alloc::sync::Arc;
 use alloc::vec::Vec;
+use codec::{Decode, Encode};
 use core::ops::Deref;
+use derive_where::derive_where;
 use frame_decode::extrinsics::Extrinsic;
 use scale_decode::DecodeAsType;
 use subxt_metadata::PalletMetadata;
@@ -338,11 +342,124 @@ where
         Ok(decoded)
     }
 
+    /// Rebuild the payload that was signed to produce the signature of this extrinsic, given
+    /// the implicit data in the [`SigningContext`]. This is the payload that a signature can be
+    /// verified against.
+    ///
+    /// Only V4 signed extrinsics are supported.
+    pub fn signer_payload(&self, context: &SigningContext<T>) -> Result<Vec<u8>, Error> {
+        let info = self.decoded_info();
+        if !info.is_signed() || info.version() != 4 {
+            return Err(BlockError::SignerPayload(
+                "only V4 signed extrinsics have a signer payload".into(),
+            )
+            .into());
+        }
+        let extensions = self
+            .transaction_extensions()
+            .expect("signed extrinsics have transaction extensions; qed");
+
+        let mut implicit = Vec::new();
+        for ext in extensions.iter() {
+            match ext.name() {
+                "CheckSpecVersion" => context.spec_version.encode_to(&mut implicit),
+                "CheckTxVersion" => context.transaction_version.encode_to(&mut implicit),
+                "CheckGenesis" => context.genesis_hash.encode_to(&mut implicit),
+                "CheckMortality" => match Era::decode(&mut ext.bytes())? {
+                    Era::Immortal => context.genesis_hash.encode_to(&mut implicit),
+                    Era::Mortal { .. } => context
+                        .mortality_checkpoint
+                        .ok_or_else(|| {
+                            BlockError::SignerPayload(
+                                "the extrinsic is mortal but no checkpoint hash was given".into(),
+                            )
+                        })?
+                        .encode_to(&mut implicit),
+                },
+                "CheckMetadataHash" => {
+                    let mode = ext.as_signed_extension::<CheckMetadataHash>()?;
+                    let hash = match mode {
+                        Some(mode) if mode.is_enabled() => {
+                            Some(context.metadata_hash.ok_or_else(|| {
+                                BlockError::SignerPayload(
+                                    "metadata hash checking is enabled but no metadata hash was given"
+                                        .into(),
+                                )
+                            })?)
+                        }
+                        _ => None,
+                    };
+                    hash.encode_to(&mut implicit);
+                }
+                name => {
+                    // Any other extension can only be handled if it has no implicit data.
+                    let additional_ty = self
+                        .metadata
+                        .extrinsic()
+                        .transaction_extensions_by_version(0)
+                        .into_iter()
+                        .flatten()
+                        .find(|e| e.identifier() == name)
+                        .map(|e| e.additional_ty())
+                        .ok_or_else(|| {
+                            BlockError::SignerPayload(format!(
+                                "the '{name}' transaction extension is not in the metadata"
+                            ))
+                        })?;
+                    let is_empty = scale_decode::visitor::decode_with_visitor(
+                        &mut &[][..],
+                        additional_ty,
+                        self.metadata.types(),
+                        scale_decode::visitor::IgnoreVisitor::<scale_info::PortableRegistry>::new(),
+                    )
+                    .is_ok();
+                    if !is_empty {
+                        return Err(BlockError::SignerPayload(format!(
+                            "the implicit data of the '{name}' transaction extension is not known"
+                        ))
+                        .into());
+                    }
+                }
+            }
+        }
+
+        let mut payload = self.call_bytes().to_vec();
+        payload.extend(
+            self.transaction_extensions_bytes()
+                .expect("signed extrinsics have transaction extensions; qed"),
+        );
+        payload.extend(implicit);
+
+        // Payloads longer than 256 bytes are hashed before being signed.
+        if payload.len() > 256 {
+            Ok(sp_crypto_hashing::blake2_256(&payload).to_vec())
+        } else {
+            Ok(payload)
+        }
+    }
+
     fn decoded_info(&self) -> &Extrinsic<'static, u32> {
         &self.ext.0
     }
 }
 
+/// The data, not included in an extrinsic itself, that is needed to rebuild the payload that
+/// it was signed with. See [`ExtrinsicDetails::signer_payload()`].
+#[derive_where(Debug, Clone, Copy)]
+pub struct SigningContext<T: Config> {
+    /// The genesis hash of the chain.
+    pub genesis_hash: HashFor<T>,
+    /// The spec version of the runtime that the extrinsic was signed for.
+    pub spec_version: u32,
+    /// The transaction version of the runtime that the extrinsic was signed for.
+    pub transaction_version: u32,
+    /// The hash of the block that the mortality period of the extrinsic starts from. This
+    /// is only needed if the extrinsic is mortal.
+    pub mortality_checkpoint: Option<HashFor<T>>,
+    /// The hash of the metadata, if the extrinsic was signed with metadata hash checking enabled.
+    pub metadata_hash: Option<[u8; 32]>,
+}
+
 /// A Static Extrinsic found in a block coupled with it's details.
 pub struct FoundExtrinsic<T: Config, E> {
     /// Details for the extrinsic.
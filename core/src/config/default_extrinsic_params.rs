@@ -3,12 +3,16 @@
 // see LICENSE for license details.
 
 use crate::config::transaction_extensions::CheckMortalityParams;
+use alloc::string::String;
+use scale_value::Value;
 
 use super::{Config, HashFor};
 use super::{ExtrinsicParams, transaction_extensions};
 
 /// The default [`super::ExtrinsicParams`] implementation understands common signed extensions
-/// and how to apply them to a given chain.
+/// and how to apply them to a given chain. Values for any other transaction extensions can be
+/// provided via [`DefaultExtrinsicParamsBuilder::transaction_extension()`], which populates the
+/// final [`transaction_extensions::DynamicTransactionExtensions`] member.
 pub type DefaultExtrinsicParams<T> = transaction_extensions::AnyOf<
     T,
     (
@@ -21,6 +25,7 @@ pub type DefaultExtrinsicParams<T> = transaction_extensions::AnyOf<
         transaction_extensions::ChargeAssetTxPayment<T>,
         transaction_extensions::ChargeTransactionPayment,
         transaction_extensions::CheckMetadataHash,
+        transaction_extensions::DynamicTransactionExtensions,
     ),
 >;
 
@@ -36,6 +41,10 @@ pub struct DefaultExtrinsicParamsBuilder<T: Config> {
     tip_of_asset_id: Option<T::AssetId>,
     tip: u128,
     tip_of: u128,
+    /// Whether to provide a metadata hash, and how to obtain it.
+    metadata_hash: transaction_extensions::CheckMetadataHashParams,
+    /// Values to use for any transaction extensions, by identifier.
+    dynamic_extensions: transaction_extensions::DynamicTransactionExtensionsParams,
 }
 
 impl<T: Config> Default for DefaultExtrinsicParamsBuilder<T> {
@@ -46,6 +55,8 @@ impl<T: Config> Default for DefaultExtrinsicParamsBuilder<T> {
             tip_of: 0,
             tip_of_asset_id: None,
             nonce: None,
+            metadata_hash: transaction_extensions::CheckMetadataHashParams::disabled(),
+            dynamic_extensions: transaction_extensions::DynamicTransactionExtensionsParams::new(),
         }
     }
 }
@@ -121,6 +132,40 @@ impl<T: Config> DefaultExtrinsicParamsBuilder<T> {
         self
     }
 
+    /// Enable the `CheckMetadataHash` transaction extension, computing the RFC-78 metadata hash
+    /// from the chain metadata along with the given number of `decimals` and `token_symbol` of the
+    /// chain's native token. This is required by some offline signers, and is ignored on chains
+    /// which don't use the `CheckMetadataHash` transaction extension.
+    #[cfg(feature = "merkleized-metadata")]
+    pub fn metadata_hash(mut self, decimals: u8, token_symbol: impl Into<String>) -> Self {
+        self.metadata_hash =
+            transaction_extensions::CheckMetadataHashParams::enabled(decimals, token_symbol);
+        self
+    }
+
+    /// Enable the `CheckMetadataHash` transaction extension, using an RFC-78 metadata hash that has
+    /// already been computed. This is ignored on chains which don't use the `CheckMetadataHash`
+    /// transaction extension.
+    pub fn precomputed_metadata_hash(mut self, hash: [u8; 32]) -> Self {
+        self.metadata_hash = transaction_extensions::CheckMetadataHashParams::with_hash(hash);
+        self
+    }
+
+    /// Provide the `value` and `implicit` data to use for the transaction extension with the
+    /// given `identifier`. These are encoded according to the types in the metadata, and take
+    /// precedence over any other configuration for that transaction extension. This allows
+    /// transactions to be constructed for chains with transaction extensions that aren't otherwise
+    /// supported. An empty composite value can be given if either type is empty.
+    pub fn transaction_extension(
+        mut self,
+        identifier: impl Into<String>,
+        value: Value,
+        implicit: Value,
+    ) -> Self {
+        self.dynamic_extensions = self.dynamic_extensions.set(identifier, value, implicit);
+        self
+    }
+
     /// Build the extrinsic parameters.
     pub fn build(self) -> <DefaultExtrinsicParams<T> as ExtrinsicParams<T>>::Params {
         let check_mortality_params = self.mortality;
@@ -149,7 +194,8 @@ impl<T: Config> DefaultExtrinsicParamsBuilder<T> {
             check_mortality_params,
             charge_asset_tx_params,
             charge_transaction_params,
-            (),
+            self.metadata_hash,
+            self.dynamic_extensions,
         )
     }
 }
@@ -157,6 +203,11 @@ impl<T: Config> DefaultExtrinsicParamsBuilder<T> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::client::{ClientState, RuntimeVersion};
+    use crate::config::PolkadotConfig;
+    use crate::error::ExtrinsicParamsError;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
 
     fn assert_default<T: Default>(_t: T) {}
 
@@ -165,4 +216,127 @@ mod test {
         let params = DefaultExtrinsicParamsBuilder::<crate::config::PolkadotConfig>::new().build();
         assert_default(params)
     }
+
+    fn client_state() -> ClientState<PolkadotConfig> {
+        let metadata_bytes = include_bytes!("../../../artifacts/polkadot_metadata_full.scale");
+        ClientState {
+            metadata: crate::metadata::decode_from(&metadata_bytes[..]).unwrap(),
+            genesis_hash: Default::default(),
+            runtime_version: RuntimeVersion {
+                spec_version: 1,
+                transaction_version: 1,
+            },
+        }
+    }
+
+    // Return the encoded transaction extension values and implicit data.
+    fn encode<E: ExtrinsicParams<PolkadotConfig>>(
+        params: E::Params,
+    ) -> Result<(Vec<u8>, Vec<u8>), ExtrinsicParamsError> {
+        let ext_params = E::new(&client_state(), params)?;
+        let mut value = Vec::new();
+        ext_params.encode_value_to(&mut value);
+        let mut implicit = Vec::new();
+        ext_params.encode_implicit_to(&mut implicit);
+        Ok((value, implicit))
+    }
+
+    #[test]
+    fn dynamic_transaction_extensions_override_others() {
+        let expected = encode::<DefaultExtrinsicParams<PolkadotConfig>>(
+            DefaultExtrinsicParamsBuilder::new().nonce(5).build(),
+        )
+        .unwrap();
+        let actual = encode::<DefaultExtrinsicParams<PolkadotConfig>>(
+            DefaultExtrinsicParamsBuilder::new()
+                .nonce(1)
+                .transaction_extension(
+                    "CheckNonce",
+                    Value::unnamed_composite([Value::u128(5)]),
+                    Value::unnamed_composite([]),
+                )
+                .build(),
+        )
+        .unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn dynamic_transaction_extensions_provide_implicit_data() {
+        let (default_value, default_implicit) = encode::<DefaultExtrinsicParams<PolkadotConfig>>(
+            DefaultExtrinsicParamsBuilder::new().build(),
+        )
+        .unwrap();
+        let (value, implicit) = encode::<DefaultExtrinsicParams<PolkadotConfig>>(
+            DefaultExtrinsicParamsBuilder::new()
+                .transaction_extension(
+                    "CheckGenesis",
+                    Value::unnamed_composite([]),
+                    Value::from_bytes([1u8; 32]),
+                )
+                .build(),
+        )
+        .unwrap();
+
+        // CheckGenesis has no value, so only the implicit data changes.
+        assert_eq!(value, default_value);
+        assert_ne!(implicit, default_implicit);
+        assert!(implicit.windows(32).any(|w| w == [1u8; 32]));
+        assert!(!default_implicit.windows(32).any(|w| w == [1u8; 32]));
+    }
+
+    #[test]
+    fn dynamic_transaction_extensions_support_unknown_extensions() {
+        // These params don't know about `CheckNonce`, which has a non-empty type.
+        type ParamsWithoutNonce = transaction_extensions::AnyOf<
+            PolkadotConfig,
+            (
+                transaction_extensions::VerifySignature<PolkadotConfig>,
+                transaction_extensions::CheckSpecVersion,
+                transaction_extensions::CheckTxVersion,
+                transaction_extensions::CheckGenesis<PolkadotConfig>,
+                transaction_extensions::CheckMortality<PolkadotConfig>,
+                transaction_extensions::ChargeTransactionPayment,
+                transaction_extensions::CheckMetadataHash,
+                transaction_extensions::DynamicTransactionExtensions,
+            ),
+        >;
+
+        let err = encode::<ParamsWithoutNonce>(Default::default()).unwrap_err();
+        assert!(
+            matches!(err, ExtrinsicParamsError::UnknownTransactionExtension(ref id) if id == "CheckNonce")
+        );
+
+        let mut params: <ParamsWithoutNonce as ExtrinsicParams<PolkadotConfig>>::Params =
+            Default::default();
+        params.7 = params.7.set(
+            "CheckNonce",
+            Value::unnamed_composite([Value::u128(5)]),
+            Value::unnamed_composite([]),
+        );
+        let actual = encode::<ParamsWithoutNonce>(params).unwrap();
+        let expected = encode::<DefaultExtrinsicParams<PolkadotConfig>>(
+            DefaultExtrinsicParamsBuilder::new().nonce(5).build(),
+        )
+        .unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn dynamic_transaction_extensions_reject_invalid_values() {
+        let err = encode::<DefaultExtrinsicParams<PolkadotConfig>>(
+            DefaultExtrinsicParamsBuilder::new()
+                .transaction_extension(
+                    "CheckNonce",
+                    Value::string("not a nonce"),
+                    Value::unnamed_composite([]),
+                )
+                .build(),
+        )
+        .unwrap_err();
+
+        assert!(err.to_string().contains("CheckNonce"));
+    }
 }
@@ -11,9 +11,11 @@ use crate::{
     client::ClientState,
     config::{Config, HashFor},
     error::ExtrinsicParamsError,
+    utils::Era,
 };
 use alloc::vec::Vec;
 use core::any::Any;
+use scale_value::Value;
 
 /// This trait allows you to configure the "signed extra" and
 /// "additional" parameters that are a part of the transaction payload
@@ -79,6 +81,18 @@ pub trait Params<T: Config> {
     fn inject_account_nonce(&mut self, _nonce: u64) {}
     /// Set the current block.
     fn inject_block(&mut self, _number: u64, _hash: HashFor<T>) {}
+    /// Return the value and implicit data to encode for the transaction extension with the given
+    /// identifier, if any have been provided. When these are returned, they are encoded according
+    /// to the types in the metadata and used by [`super::transaction_extensions::AnyOf`] in place
+    /// of any matching transaction extension.
+    fn transaction_extension_values(&self, _identifier: &str) -> Option<(&Value, &Value)> {
+        None
+    }
+    /// Return the mortality that the transaction will be given, if these parameters
+    /// determine it.
+    fn mortality(&self) -> Option<Era> {
+        None
+    }
 }
 
 impl<T: Config> Params<T> for () {}
@@ -93,6 +107,14 @@ macro_rules! impl_tuples {
             fn inject_block(&mut self, number: u64, hash: HashFor<Conf>) {
                 $(self.$index.inject_block(number, hash);)+
             }
+
+            fn transaction_extension_values(&self, identifier: &str) -> Option<(&Value, &Value)> {
+                None$(.or_else(|| self.$index.transaction_extension_values(identifier)))+
+            }
+
+            fn mortality(&self) -> Option<Era> {
+                None$(.or_else(|| self.$index.mortality()))+
+            }
         }
     }
 }
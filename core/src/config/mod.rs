@@ -123,6 +123,12 @@ pub trait Header: Sized + Encode + Decode {
     /// Return the block number of this header.
     fn number(&self) -> Self::Number;
 
+    /// Return the hash of the parent block, if this header exposes it. This is used to
+    /// walk back through blocks, and returns `None` by default.
+    fn parent_hash(&self) -> Option<<Self::Hasher as Hasher>::Output> {
+        None
+    }
+
     /// Hash this header.
     fn hash_with(&self, hasher: Self::Hasher) -> <Self::Hasher as Hasher>::Output {
         hasher.hash_of(self)
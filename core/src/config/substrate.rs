@@ -140,6 +140,10 @@ where
     fn number(&self) -> Self::Number {
         self.number
     }
+
+    fn parent_hash(&self) -> Option<H::Output> {
+        Some(self.parent_hash)
+    }
 }
 
 /// Generic header digest. From `sp_runtime::generic::digest`.
@@ -15,6 +15,9 @@ use crate::error::ExtrinsicParamsError;
 use crate::utils::{Era, Static};
 use alloc::borrow::ToOwned;
 use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
 use alloc::vec::Vec;
 use codec::{Compact, Encode};
 use core::any::Any;
@@ -22,7 +25,9 @@ use core::fmt::Debug;
 use derive_where::derive_where;
 use hashbrown::HashMap;
 use scale_decode::DecodeAsType;
+use scale_encode::EncodeAsType;
 use scale_info::PortableRegistry;
+use scale_value::Value;
 
 // Re-export this here; it's a bit generically named to be re-exported from ::config.
 pub use super::extrinsic_params::Params;
@@ -113,27 +118,46 @@ pub enum VerifySignatureDetails<T: Config> {
 
 /// The [`CheckMetadataHash`] transaction extension.
 pub struct CheckMetadataHash {
-    // Eventually we might provide or calculate the metadata hash here,
-    // but for now we never provide a hash and so this is empty.
+    // The RFC-78 metadata hash, if metadata checking is enabled.
+    hash: Option<[u8; 32]>,
 }
 
 impl<T: Config> ExtrinsicParams<T> for CheckMetadataHash {
-    type Params = ();
+    type Params = CheckMetadataHashParams;
 
-    fn new(_client: &ClientState<T>, _params: Self::Params) -> Result<Self, ExtrinsicParamsError> {
-        Ok(CheckMetadataHash {})
+    // The client state is only needed to compute the metadata hash.
+    #[cfg_attr(not(feature = "merkleized-metadata"), allow(unused_variables))]
+    fn new(client: &ClientState<T>, params: Self::Params) -> Result<Self, ExtrinsicParamsError> {
+        let hash = match params.0 {
+            CheckMetadataHashParamsInner::Disabled => None,
+            CheckMetadataHashParamsInner::Hash(hash) => Some(hash),
+            #[cfg(feature = "merkleized-metadata")]
+            CheckMetadataHashParamsInner::Compute {
+                decimals,
+                token_symbol,
+            } => {
+                let hash = client
+                    .metadata
+                    .merkleized_metadata_hash(decimals, &token_symbol)
+                    .map_err(|e| ExtrinsicParamsError::Custom(Box::new(e)))?;
+                Some(hash)
+            }
+        };
+        Ok(CheckMetadataHash { hash })
     }
 }
 
 impl ExtrinsicParamsEncoder for CheckMetadataHash {
     fn encode_value_to(&self, v: &mut Vec<u8>) {
         // A single 0 byte in the TX payload indicates that the chain should
-        // _not_ expect any metadata hash to exist in the signer payload.
-        0u8.encode_to(v);
+        // _not_ expect any metadata hash to exist in the signer payload, and
+        // a 1 byte indicates that it should.
+        let mode = if self.hash.is_some() { 1u8 } else { 0u8 };
+        mode.encode_to(v);
     }
     fn encode_implicit_to(&self, v: &mut Vec<u8>) {
-        // We provide no metadata hash in the signer payload to align with the above.
-        None::<()>.encode_to(v);
+        // Provide the metadata hash (if any) in the signer payload to align with the above.
+        self.hash.encode_to(v);
     }
 }
 
@@ -144,6 +168,60 @@ impl<T: Config> TransactionExtension<T> for CheckMetadataHash {
     }
 }
 
+/// Parameters to configure the [`CheckMetadataHash`] transaction extension. By default,
+/// metadata checking is disabled.
+///
+/// These parameters used to be `()`. Code which builds a params tuple by hand can keep the
+/// old behaviour by passing `().into()` or [`CheckMetadataHashParams::default()`] instead.
+#[derive(Debug, Clone, Default)]
+pub struct CheckMetadataHashParams(CheckMetadataHashParamsInner);
+
+#[derive(Debug, Clone, Default)]
+enum CheckMetadataHashParamsInner {
+    #[default]
+    Disabled,
+    Hash([u8; 32]),
+    #[cfg(feature = "merkleized-metadata")]
+    Compute {
+        decimals: u8,
+        token_symbol: String,
+    },
+}
+
+impl CheckMetadataHashParams {
+    /// Do not provide a metadata hash, and so disable metadata checking.
+    pub fn disabled() -> Self {
+        Self(CheckMetadataHashParamsInner::Disabled)
+    }
+
+    /// Enable metadata checking, computing the RFC-78 metadata hash from the current metadata
+    /// along with the number of `decimals` and the `token_symbol` of the chain's native token.
+    ///
+    /// Computing the hash is fairly expensive, so prefer [`CheckMetadataHashParams::with_hash()`]
+    /// if submitting many transactions. The hash can be computed once using
+    /// [`subxt_metadata::Metadata::merkleized_metadata_hash()`].
+    #[cfg(feature = "merkleized-metadata")]
+    pub fn enabled(decimals: u8, token_symbol: impl Into<String>) -> Self {
+        Self(CheckMetadataHashParamsInner::Compute {
+            decimals,
+            token_symbol: token_symbol.into(),
+        })
+    }
+
+    /// Enable metadata checking, using the given precomputed RFC-78 metadata hash.
+    pub fn with_hash(hash: [u8; 32]) -> Self {
+        Self(CheckMetadataHashParamsInner::Hash(hash))
+    }
+}
+
+impl From<()> for CheckMetadataHashParams {
+    fn from(_: ()) -> Self {
+        Self::disabled()
+    }
+}
+
+impl<T: Config> Params<T> for CheckMetadataHashParams {}
+
 /// Is metadata checking enabled or disabled?
 // Dev note: The "Disabled" and "Enabled" variant names match those that the
 // transaction extension will be encoded with, in order that DecodeAsType will work
@@ -421,6 +499,23 @@ impl<T: Config> Params<T> for CheckMortalityParams<T> {
             }
         }
     }
+
+    fn mortality(&self) -> Option<Era> {
+        // This lines up with how the era is encoded in `CheckMortality`.
+        match &self.0 {
+            CheckMortalityParamsInner::MortalFromBlock {
+                for_n_blocks,
+                from_block_n,
+                ..
+            } => Some(Era::mortal(*for_n_blocks, *from_block_n)),
+            CheckMortalityParamsInner::Immortal
+            | CheckMortalityParamsInner::MortalForBlocksOrImmortalIfNotPossible(_) => {
+                Some(Era::Immortal)
+            }
+            // This can't be used to construct a transaction.
+            CheckMortalityParamsInner::MortalForBlocks(_) => None,
+        }
+    }
 }
 
 /// The [`ChargeAssetTxPayment`] transaction extension.
@@ -564,9 +659,116 @@ impl ChargeTransactionPaymentParams {
 
 impl<T: Config> Params<T> for ChargeTransactionPaymentParams {}
 
+/// This can be added to the tuple of transaction extensions given to [`AnyOf`] in order to
+/// support providing [`scale_value::Value`]s for any transaction extension by its identifier,
+/// via [`DynamicTransactionExtensionsParams`]. These values are encoded using the types given
+/// in the metadata, and are used in place of any other matching transaction extension. This
+/// allows signing transactions for chains which have transaction extensions that Subxt doesn't
+/// know about, without needing to write a [`TransactionExtension`] implementation for them.
+pub struct DynamicTransactionExtensions;
+
+impl<T: Config> ExtrinsicParams<T> for DynamicTransactionExtensions {
+    type Params = DynamicTransactionExtensionsParams;
+
+    fn new(_client: &ClientState<T>, _params: Self::Params) -> Result<Self, ExtrinsicParamsError> {
+        Ok(DynamicTransactionExtensions)
+    }
+}
+
+// The values themselves are encoded by `AnyOf`, which knows where in the
+// transaction each of them belongs.
+impl ExtrinsicParamsEncoder for DynamicTransactionExtensions {}
+
+impl<T: Config> TransactionExtension<T> for DynamicTransactionExtensions {
+    type Decoded = ();
+    fn matches(_identifier: &str, _type_id: u32, _types: &PortableRegistry) -> bool {
+        false
+    }
+}
+
+/// Parameters to configure [`DynamicTransactionExtensions`]. This holds the values to use for
+/// any transaction extensions, keyed by their identifier.
+#[derive(Debug, Clone, Default)]
+pub struct DynamicTransactionExtensionsParams {
+    values: BTreeMap<String, (Value, Value)>,
+}
+
+impl DynamicTransactionExtensionsParams {
+    /// Provide no values for any transaction extensions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Provide the `value` (which is included in the transaction) and `implicit` data (which is
+    /// only included in the signer payload) for the transaction extension with the given
+    /// `identifier`. Each will be encoded according to the corresponding type in the metadata;
+    /// an empty composite can be given if the type is empty.
+    pub fn set(mut self, identifier: impl Into<String>, value: Value, implicit: Value) -> Self {
+        self.values.insert(identifier.into(), (value, implicit));
+        self
+    }
+
+    /// Return the value and implicit data for the transaction extension with the given identifier.
+    pub fn get(&self, identifier: &str) -> Option<(&Value, &Value)> {
+        self.values.get(identifier).map(|(v, i)| (v, i))
+    }
+
+    /// Are there no values provided?
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl<T: Config> Params<T> for DynamicTransactionExtensionsParams {
+    fn transaction_extension_values(&self, identifier: &str) -> Option<(&Value, &Value)> {
+        self.get(identifier)
+    }
+}
+
+// A single transaction extension whose value and implicit data have been
+// encoded from the values given in `DynamicTransactionExtensionsParams`.
+struct DynamicTransactionExtension {
+    value: Vec<u8>,
+    implicit: Vec<u8>,
+}
+
+impl DynamicTransactionExtension {
+    fn new(
+        identifier: &str,
+        (value, implicit): (&Value, &Value),
+        (value_ty, implicit_ty): (u32, u32),
+        types: &PortableRegistry,
+    ) -> Result<Self, ExtrinsicParamsError> {
+        let encode = |v: &Value, ty: u32| {
+            v.encode_as_type(ty, types).map_err(|e| {
+                ExtrinsicParamsError::custom(format!(
+                    "Cannot encode the value given for transaction extension {identifier}: {e}"
+                ))
+            })
+        };
+        Ok(DynamicTransactionExtension {
+            value: encode(value, value_ty)?,
+            implicit: encode(implicit, implicit_ty)?,
+        })
+    }
+}
+
+impl ExtrinsicParamsEncoder for DynamicTransactionExtension {
+    fn encode_value_to(&self, v: &mut Vec<u8>) {
+        v.extend_from_slice(&self.value);
+    }
+    fn encode_implicit_to(&self, v: &mut Vec<u8>) {
+        v.extend_from_slice(&self.implicit);
+    }
+}
+
 /// This accepts a tuple of [`TransactionExtension`]s, and will dynamically make use of whichever
 /// ones are actually required for the chain in the correct order, ignoring the rest. This
 /// is a sensible default, and allows for a single configuration to work across multiple chains.
+///
+/// Any values returned from [`Params::transaction_extension_values()`] (for instance those given
+/// to [`DynamicTransactionExtensions`] via its params) are used in place of the other matching
+/// transaction extensions.
 pub struct AnyOf<T, Params> {
     params: Vec<Box<dyn ExtrinsicParamsEncoder + Send + 'static>>,
     _marker: core::marker::PhantomData<(T, Params)>,
@@ -591,9 +793,24 @@ macro_rules! impl_tuples {
                 let metadata = &client.metadata;
                 let types = metadata.types();
 
+                // Any dynamic values that we've been given take precedence over the
+                // matching transaction extensions, so encode and store them first.
+                let mut exts_by_index = HashMap::new();
+                for (idx, e) in metadata.extrinsic().transaction_extensions_to_use_for_encoding().enumerate() {
+                    if let Some(values) = <Self::Params as Params<T>>::transaction_extension_values(&params, e.identifier()) {
+                        let ext = DynamicTransactionExtension::new(
+                            e.identifier(),
+                            values,
+                            (e.extra_ty(), e.additional_ty()),
+                            types,
+                        )?;
+                        let boxed_ext: Box<dyn ExtrinsicParamsEncoder + Send + 'static> = Box::new(ext);
+                        exts_by_index.insert(idx, boxed_ext);
+                    }
+                }
+
                 // For each transaction extension in the tuple, find the matching index in the metadata, if
                 // there is one, and add it to a map with that index as the key.
-                let mut exts_by_index = HashMap::new();
                 $({
                     for (idx, e) in metadata.extrinsic().transaction_extensions_to_use_for_encoding().enumerate() {
                         // Skip over any exts that have a match already:
@@ -70,6 +70,9 @@ pub enum BlockError {
         /// The decode error.
         error: ExtrinsicDecodeError,
     },
+    /// The payload that the extrinsic signature was created from could not be rebuilt.
+    #[error("Cannot rebuild the signer payload of the extrinsic: {0}")]
+    SignerPayload(String),
 }
 
 /// An alias for [`frame_decode::extrinsics::ExtrinsicDecodeError`].
@@ -181,6 +184,10 @@ pub enum ExtrinsicError {
     /// Issue encoding transaction extensions.
     #[error("Cannot construct the required transaction extensions: {0}")]
     Params(#[from] ExtrinsicParamsError),
+    /// Issue generating a merkleized metadata proof for the transaction.
+    #[cfg(feature = "merkleized-metadata")]
+    #[error("Cannot generate a metadata proof for the transaction: {0}")]
+    MetadataProof(#[from] subxt_metadata::MerkleizedMetadataError),
 }
 
 impl From<ExtrinsicParamsError> for Error {
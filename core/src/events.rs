@@ -141,6 +141,11 @@ impl<T: Config> Events<T> {
         &self.event_bytes
     }
 
+    /// Return the metadata used to decode these events.
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
     /// Iterate over all of the events, using metadata to dynamically
     /// decode them as we go, and returning the raw bytes and other associated
     /// details. If an error occurs, all subsequent iterations return `None`.
@@ -188,10 +188,21 @@ pub fn create_v4_signed<T: Config, Call: Payload>(
     let additional_and_extra_params =
         <T::ExtrinsicParams as ExtrinsicParams<T>>::new(client_state, params)?;
 
+    // 4. Encode them, since the V4 format needs nothing more from them.
+    let mut extension_values = Vec::new();
+    additional_and_extra_params.encode_value_to(&mut extension_values);
+    let mut signer_payload_values = Vec::new();
+    additional_and_extra_params.encode_signer_payload_value_to(&mut signer_payload_values);
+    let mut implicit = Vec::new();
+    additional_and_extra_params.encode_implicit_to(&mut implicit);
+
     // Return these details, ready to construct a signed extrinsic from.
     Ok(PartialTransactionV4 {
         call_data,
-        additional_and_extra_params,
+        extension_values,
+        signer_payload_values,
+        implicit,
+        _marker: core::marker::PhantomData,
     })
 }
 
@@ -229,16 +240,58 @@ pub fn create_v5_general<T: Config, Call: Payload>(
 /// A partially constructed V4 extrinsic, ready to be signed.
 pub struct PartialTransactionV4<T: Config> {
     call_data: Vec<u8>,
-    additional_and_extra_params: T::ExtrinsicParams,
+    extension_values: Vec<u8>,
+    signer_payload_values: Vec<u8>,
+    implicit: Vec<u8>,
+    _marker: core::marker::PhantomData<T>,
 }
 
 impl<T: Config> PartialTransactionV4<T> {
+    /// Construct a [`PartialTransactionV4`] from its already encoded parts: the call data,
+    /// the transaction extension values which are included in the extrinsic, the values which
+    /// are signed in their place (which can differ, see
+    /// [`crate::config::ExtrinsicParamsEncoder::encode_signer_payload_value_to()`]), and the
+    /// implicit data which is only included in the signer payload. This is useful for re-signing
+    /// an existing transaction whose parts have been modified.
+    pub fn from_parts(
+        call_data: Vec<u8>,
+        extension_values: Vec<u8>,
+        signer_payload_values: Vec<u8>,
+        implicit: Vec<u8>,
+    ) -> Self {
+        PartialTransactionV4 {
+            call_data,
+            extension_values,
+            signer_payload_values,
+            implicit,
+            _marker: core::marker::PhantomData,
+        }
+    }
+
     /// Return the bytes representing the call data for this partially constructed
     /// extrinsic.
     pub fn call_data(&self) -> &[u8] {
         &self.call_data
     }
 
+    /// Return the SCALE encoded transaction extension values which will be included in the extrinsic.
+    pub fn transaction_extension_values(&self) -> &[u8] {
+        &self.extension_values
+    }
+
+    /// Return the SCALE encoded transaction extension values which are included in the signer
+    /// payload. These are usually the same as [`Self::transaction_extension_values()`], but
+    /// extensions can sign something different from what they include in the extrinsic.
+    pub fn transaction_extension_signer_payload_values(&self) -> &[u8] {
+        &self.signer_payload_values
+    }
+
+    /// Return the SCALE encoded implicit transaction extension data, which is included in the
+    /// signer payload but not in the extrinsic.
+    pub fn transaction_extension_implicit(&self) -> &[u8] {
+        &self.implicit
+    }
+
     // Obtain bytes representing the signer payload and run call some function
     // with them. This can avoid an allocation in some cases.
     fn with_signer_payload<F, R>(&self, f: F) -> R
@@ -246,10 +299,8 @@ impl<T: Config> PartialTransactionV4<T> {
         F: for<'a> FnOnce(Cow<'a, [u8]>) -> R,
     {
         let mut bytes = self.call_data.clone();
-        self.additional_and_extra_params
-            .encode_signer_payload_value_to(&mut bytes);
-        self.additional_and_extra_params
-            .encode_implicit_to(&mut bytes);
+        bytes.extend(&self.signer_payload_values);
+        bytes.extend(&self.implicit);
 
         if bytes.len() > 256 {
             f(Cow::Borrowed(&blake2_256(&bytes)))
@@ -264,6 +315,20 @@ impl<T: Config> PartialTransactionV4<T> {
         self.with_signer_payload(|bytes| bytes.to_vec())
     }
 
+    /// Return the SCALE encoded RFC-78 metadata proof for this extrinsic. This contains the
+    /// type information needed to decode the extrinsic, and is what offline signers which
+    /// check the metadata hash (for instance hardware wallets) need alongside the signer payload.
+    #[cfg(feature = "merkleized-metadata")]
+    pub fn metadata_proof(&self, metadata: &Metadata) -> Result<Vec<u8>, Error> {
+        let proof = metadata
+            .merkleized_metadata_proof_for_parts(
+                &self.call_data,
+                Some((&self.extension_values, &self.implicit)),
+            )
+            .map_err(ExtrinsicError::MetadataProof)?;
+        Ok(proof)
+    }
+
     /// Convert this [`PartialTransactionV4`] into a V4 signed [`Transaction`], ready to submit.
     /// The provided `signer` is responsible for providing the "from" address for the transaction,
     /// as well as providing a signature to attach to it.
@@ -294,8 +359,7 @@ impl<T: Config> PartialTransactionV4<T> {
             // the signature
             signature.encode_to(&mut encoded_inner);
             // attach custom extra params
-            self.additional_and_extra_params
-                .encode_value_to(&mut encoded_inner);
+            encoded_inner.extend(&self.extension_values);
             // and now, call data (remembering that it's been encoded already and just needs appending)
             encoded_inner.extend(&self.call_data);
             // now, prefix byte length:
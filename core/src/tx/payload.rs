@@ -10,11 +10,12 @@ use crate::error::MetadataError;
 use crate::metadata::Metadata;
 use alloc::borrow::{Cow, ToOwned};
 use alloc::boxed::Box;
+use alloc::format;
 use alloc::string::String;
 
 use alloc::vec::Vec;
-use codec::Encode;
-use scale_encode::EncodeAsFields;
+use codec::{Compact, Encode};
+use scale_encode::{EncodeAsFields, EncodeAsType};
 use scale_value::{Composite, Value, ValueDef, Variant};
 
 /// This represents a transaction payload that can be submitted
@@ -205,3 +206,338 @@ pub fn dynamic(
 ) -> DynamicPayload {
     DefaultPayload::new(pallet_name, call_name, call_data.into())
 }
+
+/// Which call in the `Utility` pallet a [`Batch`] is submitted as. The modes differ
+/// in what happens when one of the batched calls fails.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BatchMode {
+    /// Submit as `Utility::batch`. Calls are dispatched in order until one fails, at which
+    /// point the remaining calls are skipped. Calls which succeeded are not reverted.
+    #[default]
+    Batch,
+    /// Submit as `Utility::batch_all`. Calls are dispatched in order, and if any of them
+    /// fails then the whole batch is reverted.
+    BatchAll,
+    /// Submit as `Utility::force_batch`. Every call is dispatched, even if some of them fail.
+    /// Calls which succeeded are not reverted.
+    ForceBatch,
+}
+
+impl BatchMode {
+    /// The name of the `Utility` pallet call that this mode corresponds to.
+    pub fn call_name(&self) -> &'static str {
+        match self {
+            BatchMode::Batch => "batch",
+            BatchMode::BatchAll => "batch_all",
+            BatchMode::ForceBatch => "force_batch",
+        }
+    }
+}
+
+/// A transaction payload which batches together any number of other payloads
+/// (static or dynamic) into a single `Utility` pallet call.
+///
+/// In `subxt`, the outcome of each call in a submitted batch can be obtained from the
+/// extrinsic events via `ExtrinsicEvents::batch_results()`.
+///
+/// # Example
+///
+/// ```rust
+/// use subxt_core::dynamic::Value;
+/// use subxt_core::tx::payload::{dynamic, Batch, BatchMode};
+///
+/// let batch = Batch::new(BatchMode::BatchAll)
+///     .call(dynamic("System", "remark", vec![Value::from_bytes("hello")]))
+///     .call(dynamic("System", "remark", vec![Value::from_bytes("world")]));
+///
+/// assert_eq!(batch.len(), 2);
+/// ```
+pub struct Batch {
+    mode: BatchMode,
+    calls: Vec<Box<dyn Payload + Send + Sync>>,
+}
+
+impl Batch {
+    /// Create a new, empty [`Batch`] which will be submitted using the given [`BatchMode`].
+    pub fn new(mode: BatchMode) -> Self {
+        Batch {
+            mode,
+            calls: Vec::new(),
+        }
+    }
+
+    /// Add a call to the batch.
+    pub fn call(mut self, call: impl Payload + Send + Sync + 'static) -> Self {
+        self.push(call);
+        self
+    }
+
+    /// Add a call to the batch, mutating it in place.
+    pub fn push(&mut self, call: impl Payload + Send + Sync + 'static) {
+        self.calls.push(Box::new(call));
+    }
+
+    /// The [`BatchMode`] that this batch will be submitted with.
+    pub fn mode(&self) -> BatchMode {
+        self.mode
+    }
+
+    /// The number of calls in the batch.
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Is the batch empty?
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+}
+
+impl core::fmt::Debug for Batch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Batch")
+            .field("mode", &self.mode)
+            .field("calls", &self.calls.len())
+            .finish()
+    }
+}
+
+impl Payload for Batch {
+    fn encode_call_data_to(&self, metadata: &Metadata, out: &mut Vec<u8>) -> Result<(), Error> {
+        let call_name = self.mode.call_name();
+        let pallet = metadata.pallet_by_name_err("Utility")?;
+        let call = pallet
+            .call_variant_by_name(call_name)
+            .ok_or_else(|| MetadataError::CallNameNotFound(call_name.to_owned()))?;
+
+        // Each of the batch calls takes a single `calls: Vec<RuntimeCall>` argument. The
+        // call data of each payload is a SCALE encoded `RuntimeCall`, so we can encode this
+        // vector by hand once we've checked that the call looks like we expect.
+        let is_vec_of_calls = match &call.fields[..] {
+            [field] => {
+                let ty = metadata
+                    .types()
+                    .resolve(field.ty.id)
+                    .ok_or(MetadataError::TypeNotFound(field.ty.id))?;
+                matches!(ty.type_def, scale_info::TypeDef::Sequence(_))
+            }
+            _ => false,
+        };
+        if !is_vec_of_calls {
+            return Err(scale_encode::Error::custom_string(format!(
+                "Utility::{call_name} is expected to take a single vector of calls"
+            ))
+            .into());
+        }
+
+        pallet.index().encode_to(out);
+        call.index.encode_to(out);
+        Compact(self.calls.len() as u32).encode_to(out);
+        for call in &self.calls {
+            super::validate(call, metadata)?;
+            call.encode_call_data_to(metadata, out)?;
+        }
+        Ok(())
+    }
+}
+
+/// A transaction payload for a call which takes another call as one of its arguments, for
+/// instance `Proxy::proxy` or `Multisig::as_multi`. Arguments are provided by name, and can be
+/// given as dynamic values, as pre-encoded bytes, or as another (static or dynamic) payload.
+///
+/// # Example
+///
+/// ```rust
+/// use subxt_core::dynamic::Value;
+/// use subxt_core::tx::payload::{dynamic, NestedCallPayload};
+///
+/// let remark = dynamic("System", "remark", vec![Value::from_bytes("hello")]);
+/// let call = NestedCallPayload::new("Utility", "dispatch_as")
+///     .arg("as_origin", Value::unnamed_variant("system", [Value::unnamed_variant("Root", [])]))
+///     .call_arg("call", remark);
+/// ```
+pub struct NestedCallPayload {
+    pallet_name: Cow<'static, str>,
+    call_name: Cow<'static, str>,
+    args: Vec<(Cow<'static, str>, NestedCallArg)>,
+}
+
+enum NestedCallArg {
+    Value(Value),
+    Encoded(Vec<u8>),
+    Call(Box<dyn Payload + Send + Sync>),
+}
+
+impl NestedCallPayload {
+    /// Create a new [`NestedCallPayload`] for the given pallet and call, with no arguments yet.
+    pub fn new(
+        pallet_name: impl Into<Cow<'static, str>>,
+        call_name: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        NestedCallPayload {
+            pallet_name: pallet_name.into(),
+            call_name: call_name.into(),
+            args: Vec::new(),
+        }
+    }
+
+    /// Provide the argument with the given name as a dynamic value, which will be
+    /// encoded according to the type of the argument in the metadata.
+    pub fn arg(self, name: impl Into<Cow<'static, str>>, value: Value) -> Self {
+        self.with_arg(name, NestedCallArg::Value(value))
+    }
+
+    /// Provide the argument with the given name as SCALE encoded bytes. These are used
+    /// as-is, and so must line up with the type of the argument in the metadata.
+    pub fn encoded_arg(self, name: impl Into<Cow<'static, str>>, bytes: Vec<u8>) -> Self {
+        self.with_arg(name, NestedCallArg::Encoded(bytes))
+    }
+
+    /// Provide the argument with the given name as a call, which will be validated and
+    /// encoded as a runtime call.
+    pub fn call_arg(
+        self,
+        name: impl Into<Cow<'static, str>>,
+        call: impl Payload + Send + Sync + 'static,
+    ) -> Self {
+        self.with_arg(name, NestedCallArg::Call(Box::new(call)))
+    }
+
+    /// Returns the pallet name.
+    pub fn pallet_name(&self) -> &str {
+        &self.pallet_name
+    }
+
+    /// Returns the call name.
+    pub fn call_name(&self) -> &str {
+        &self.call_name
+    }
+
+    fn with_arg(mut self, name: impl Into<Cow<'static, str>>, arg: NestedCallArg) -> Self {
+        let name = name.into();
+        self.args.retain(|(n, _)| *n != name);
+        self.args.push((name, arg));
+        self
+    }
+}
+
+impl core::fmt::Debug for NestedCallPayload {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let arg_names: Vec<&str> = self.args.iter().map(|(name, _)| &**name).collect();
+        f.debug_struct("NestedCallPayload")
+            .field("pallet_name", &self.pallet_name)
+            .field("call_name", &self.call_name)
+            .field("args", &arg_names)
+            .finish()
+    }
+}
+
+impl Payload for NestedCallPayload {
+    fn encode_call_data_to(&self, metadata: &Metadata, out: &mut Vec<u8>) -> Result<(), Error> {
+        let pallet = metadata.pallet_by_name_err(&self.pallet_name)?;
+        let call = pallet
+            .call_variant_by_name(&self.call_name)
+            .ok_or_else(|| MetadataError::CallNameNotFound((*self.call_name).to_owned()))?;
+
+        pallet.index().encode_to(out);
+        call.index.encode_to(out);
+
+        for field in &call.fields {
+            let field_name = field.name.as_deref().unwrap_or_default();
+            let Some((_, arg)) = self.args.iter().find(|(name, _)| name == field_name) else {
+                return Err(scale_encode::Error::custom_string(format!(
+                    "No value was provided for the argument '{field_name}' of {}::{}",
+                    self.pallet_name, self.call_name
+                ))
+                .into());
+            };
+
+            match arg {
+                NestedCallArg::Value(value) => {
+                    value.encode_as_type_to(field.ty.id, metadata.types(), out)?
+                }
+                NestedCallArg::Encoded(bytes) => out.extend_from_slice(bytes),
+                NestedCallArg::Call(call) => {
+                    super::validate(call, metadata)?;
+                    call.encode_call_data_to(metadata, out)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn metadata() -> Metadata {
+        let bytes = include_bytes!("../../../artifacts/polkadot_metadata_full.scale");
+        crate::metadata::decode_from(&bytes[..]).expect("valid metadata")
+    }
+
+    fn remark(msg: &str) -> DynamicPayload {
+        dynamic("System", "remark", vec![Value::from_bytes(msg)])
+    }
+
+    #[test]
+    fn batch_encodes_like_dynamic_utility_call() {
+        let metadata = metadata();
+
+        for mode in [BatchMode::Batch, BatchMode::BatchAll, BatchMode::ForceBatch] {
+            let batch = Batch::new(mode).call(remark("hello")).call(remark("world"));
+            let expected = dynamic(
+                "Utility",
+                mode.call_name(),
+                vec![Value::unnamed_composite([
+                    remark("hello").into_value(),
+                    remark("world").into_value(),
+                ])],
+            );
+
+            assert_eq!(
+                batch.encode_call_data(&metadata).unwrap(),
+                expected.encode_call_data(&metadata).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn nested_call_encodes_like_dynamic_call() {
+        let metadata = metadata();
+        let origin = Value::unnamed_variant("system", [Value::unnamed_variant("Root", [])]);
+
+        let nested = NestedCallPayload::new("Utility", "dispatch_as")
+            .call_arg("call", remark("hello"))
+            .arg("as_origin", origin.clone());
+        let expected = dynamic(
+            "Utility",
+            "dispatch_as",
+            vec![origin, remark("hello").into_value()],
+        );
+
+        assert_eq!(
+            nested.encode_call_data(&metadata).unwrap(),
+            expected.encode_call_data(&metadata).unwrap()
+        );
+
+        // Missing arguments are an error:
+        let missing =
+            NestedCallPayload::new("Utility", "dispatch_as").call_arg("call", remark("hi"));
+        assert!(missing.encode_call_data(&metadata).is_err());
+    }
+
+    #[test]
+    fn batch_validates_inner_calls() {
+        let metadata = metadata();
+        let bad_call =
+            DefaultPayload::new_static("System", "remark", remark("hi").call_data, [0; 32]);
+        let batch = Batch::new(BatchMode::Batch).call(bad_call);
+
+        assert!(matches!(
+            batch.encode_call_data(&metadata),
+            Err(Error::Metadata(MetadataError::IncompatibleCodegen))
+        ));
+    }
+}
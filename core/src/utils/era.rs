@@ -74,6 +74,26 @@ impl Era {
             phase: quantized_phase,
         }
     }
+
+    /// Get the block number of the start of the era whose properties this object
+    /// describes that `current` belongs to.
+    pub fn birth(self, current: u64) -> u64 {
+        match self {
+            Self::Immortal => 0,
+            Self::Mortal { period, phase } => {
+                (current.max(phase) - phase) / period * period + phase
+            }
+        }
+    }
+
+    /// Get the block number of the first block at which the era has ended. A transaction
+    /// with this era can only be included in blocks before this one.
+    pub fn death(self, current: u64) -> u64 {
+        match self {
+            Self::Immortal => u64::MAX,
+            Self::Mortal { period, .. } => self.birth(current) + period,
+        }
+    }
 }
 
 // Both copied from `sp_runtime::generic::Era`; this is the wire interface and so
@@ -232,3 +252,27 @@ impl<R: TypeResolver> Visitor for EraVisitor<R> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mortal_era_birth_and_death() {
+        let era = Era::mortal(64, 1000);
+        // 1000 % 64 == 40, so the era started at block 1000:
+        assert_eq!(era.birth(1000), 1000);
+        assert_eq!(era.death(1000), 1064);
+        // Any block in the same period gives the same birth and death:
+        assert_eq!(era.birth(1063), 1000);
+        assert_eq!(era.death(1063), 1064);
+        // The next period starts once it has died:
+        assert_eq!(era.birth(1064), 1064);
+    }
+
+    #[test]
+    fn immortal_era_never_dies() {
+        assert_eq!(Era::Immortal.birth(1000), 0);
+        assert_eq!(Era::Immortal.death(1000), u64::MAX);
+    }
+}
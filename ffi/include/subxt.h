@@ -0,0 +1,111 @@
+/*
+ * Copyright 2019-2025 Parity Technologies (UK) Ltd.
+ * This file is dual-licensed as Apache-2.0 or GPL-3.0.
+ * see LICENSE for license details.
+ *
+ * C bindings for Subxt. See the crate docs of `subxt-ffi` for the conventions
+ * that these functions follow. Briefly:
+ *
+ * - Every function returns a SubxtErrorCode. If it isn't SUBXT_OK, then
+ *   subxt_last_error_message() describes what went wrong.
+ * - Results are written to out_* pointers, which are untouched on error.
+ * - Strings handed back must be freed with subxt_string_free(), and handles
+ *   must be freed with the matching *_free function.
+ * - Values are passed as JSON.
+ */
+
+#ifndef SUBXT_H
+#define SUBXT_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum SubxtErrorCode {
+    SUBXT_OK = 0,
+    SUBXT_INVALID_ARGUMENT = 1,
+    SUBXT_RPC = 2,
+    SUBXT_TIMEOUT = 3,
+    SUBXT_METADATA = 4,
+    SUBXT_ENCODE = 5,
+    SUBXT_DECODE = 6,
+    SUBXT_TRANSACTION = 7,
+    SUBXT_OTHER = 8,
+    SUBXT_PANIC = 9,
+} SubxtErrorCode;
+
+typedef enum SubxtKeyType {
+    SUBXT_KEY_SR25519 = 0,
+    SUBXT_KEY_ECDSA = 1,
+} SubxtKeyType;
+
+typedef struct SubxtClient SubxtClient;
+typedef struct SubxtStorageIter SubxtStorageIter;
+typedef struct SubxtSigner SubxtSigner;
+typedef struct SubxtSubscription SubxtSubscription;
+
+typedef void (*SubxtEventCallback)(void *user_data, SubxtErrorCode code, const char *json);
+
+/* Errors and strings */
+char *subxt_last_error_message(void);
+void subxt_string_free(char *s);
+
+/* Clients */
+SubxtErrorCode subxt_client_connect(const char *url, SubxtClient **out_client);
+void subxt_client_free(SubxtClient *client);
+SubxtErrorCode subxt_client_genesis_hash(const SubxtClient *client, char **out_hash);
+SubxtErrorCode subxt_client_runtime_version(const SubxtClient *client, char **out_json);
+
+/* Metadata and constants */
+SubxtErrorCode subxt_metadata_pallets(const SubxtClient *client, char **out_json);
+SubxtErrorCode subxt_metadata_pallet(const SubxtClient *client, const char *pallet_name,
+                                     char **out_json);
+SubxtErrorCode subxt_metadata_runtime_apis(const SubxtClient *client, char **out_json);
+SubxtErrorCode subxt_constant(const SubxtClient *client, const char *pallet_name,
+                              const char *constant_name, char **out_json);
+
+/* Storage. keys_json and block_hash may be NULL. */
+SubxtErrorCode subxt_storage_fetch(const SubxtClient *client, const char *pallet_name,
+                                   const char *entry_name, const char *keys_json,
+                                   const char *block_hash, char **out_json);
+SubxtErrorCode subxt_storage_iter(const SubxtClient *client, const char *pallet_name,
+                                  const char *entry_name, const char *keys_json,
+                                  const char *block_hash, SubxtStorageIter **out_iter);
+SubxtErrorCode subxt_storage_iter_next(const SubxtStorageIter *iter, char **out_json);
+void subxt_storage_iter_free(SubxtStorageIter *iter);
+
+/* Runtime APIs. args_json and block_hash may be NULL. */
+SubxtErrorCode subxt_runtime_api_call(const SubxtClient *client, const char *trait_name,
+                                      const char *method_name, const char *args_json,
+                                      const char *block_hash, char **out_json);
+
+/* Signers. password may be NULL. */
+SubxtErrorCode subxt_signer_from_uri(SubxtKeyType key_type, const char *uri,
+                                     SubxtSigner **out_signer);
+SubxtErrorCode subxt_signer_from_phrase(SubxtKeyType key_type, const char *phrase,
+                                        const char *password, SubxtSigner **out_signer);
+SubxtErrorCode subxt_signer_account_id(const SubxtSigner *signer, char **out_account_id);
+SubxtErrorCode subxt_signer_public_key(const SubxtSigner *signer, char **out_public_key);
+void subxt_signer_free(SubxtSigner *signer);
+
+/* Transactions. args_json may be NULL. */
+SubxtErrorCode subxt_tx_sign(const SubxtClient *client, const char *pallet_name,
+                             const char *call_name, const char *args_json,
+                             const SubxtSigner *signer, char **out_tx);
+SubxtErrorCode subxt_tx_submit(const SubxtClient *client, const char *tx, char **out_json);
+SubxtErrorCode subxt_tx_sign_and_submit(const SubxtClient *client, const char *pallet_name,
+                                        const char *call_name, const char *args_json,
+                                        const SubxtSigner *signer, char **out_json);
+
+/* Events. The callback runs on a thread owned by the library and may call other functions,
+ * including subxt_subscription_free(). Freeing a subscription waits for a running callback to
+ * return, unless it's freed from within that callback. */
+SubxtErrorCode subxt_events_subscribe(const SubxtClient *client, SubxtEventCallback callback,
+                                      void *user_data, SubxtSubscription **out_subscription);
+void subxt_subscription_free(SubxtSubscription *subscription);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* SUBXT_H */
@@ -0,0 +1,164 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Connect to a node.
+
+use crate::error::{SubxtErrorCode, ffi_call};
+use crate::runtime;
+use crate::utils::{handle_arg, into_c_string, out_arg, str_arg, to_hex, write_out};
+use std::os::raw::c_char;
+use subxt::{OnlineClient, PolkadotConfig};
+
+/// A handle to a client which is connected to a node. Free it with [`subxt_client_free()`].
+#[derive(Debug)]
+pub struct SubxtClient {
+    pub(crate) client: OnlineClient<PolkadotConfig>,
+}
+
+/// Connect to the node at the given URL (for instance `ws://127.0.0.1:9944`), and write
+/// a client handle to `out_client` on success.
+///
+/// # Safety
+///
+/// `url` must be a NUL terminated string and `out_client` must be valid to write to.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn subxt_client_connect(
+    url: *const c_char,
+    out_client: *mut *mut SubxtClient,
+) -> SubxtErrorCode {
+    ffi_call(|| {
+        out_arg(out_client, "out_client")?;
+        // SAFETY: The caller guarantees that `url` is a valid string.
+        let url = unsafe { str_arg(url, "url")? };
+
+        let client = runtime().block_on(OnlineClient::<PolkadotConfig>::from_url(url))?;
+
+        let client = Box::into_raw(Box::new(SubxtClient { client }));
+        // SAFETY: `out_client` was checked above and the caller guarantees it's valid.
+        unsafe { write_out(out_client, client) };
+        Ok(())
+    })
+}
+
+/// Disconnect and free a client. Does nothing if `client` is null. Anything obtained
+/// from the client, like storage iterators and subscriptions, continues to work.
+///
+/// # Safety
+///
+/// `client` must be null or a handle from [`subxt_client_connect()`] which has not yet
+/// been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn subxt_client_free(client: *mut SubxtClient) {
+    if !client.is_null() {
+        // SAFETY: The caller guarantees that this came from `Box::into_raw`.
+        drop(unsafe { Box::from_raw(client) });
+    }
+}
+
+/// Write the genesis hash of the chain, as a `0x` prefixed hex string, to `out_hash`.
+///
+/// # Safety
+///
+/// `client` must be a live client handle and `out_hash` must be valid to write to.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn subxt_client_genesis_hash(
+    client: *const SubxtClient,
+    out_hash: *mut *mut c_char,
+) -> SubxtErrorCode {
+    ffi_call(|| {
+        out_arg(out_hash, "out_hash")?;
+        // SAFETY: The caller guarantees that `client` is a live handle.
+        let client = unsafe { handle_arg(client, "client")? };
+
+        let hash = to_hex(client.client.genesis_hash());
+        // SAFETY: `out_hash` was checked above and the caller guarantees it's valid.
+        unsafe { write_out(out_hash, into_c_string(hash)) };
+        Ok(())
+    })
+}
+
+/// Write the runtime version that the client is currently using to `out_json`, as
+/// a JSON object like `{ "spec_version": 1, "transaction_version": 1 }`.
+///
+/// # Safety
+///
+/// `client` must be a live client handle and `out_json` must be valid to write to.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn subxt_client_runtime_version(
+    client: *const SubxtClient,
+    out_json: *mut *mut c_char,
+) -> SubxtErrorCode {
+    ffi_call(|| {
+        out_arg(out_json, "out_json")?;
+        // SAFETY: The caller guarantees that `client` is a live handle.
+        let client = unsafe { handle_arg(client, "client")? };
+
+        let version = client.client.runtime_version();
+        let json = serde_json::json!({
+            "spec_version": version.spec_version,
+            "transaction_version": version.transaction_version,
+        });
+        // SAFETY: `out_json` was checked above and the caller guarantees it's valid.
+        unsafe { write_out(out_json, into_c_string(json.to_string())) };
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::last_error_message;
+    use std::ffi::CString;
+
+    #[test]
+    fn connect_reports_invalid_arguments() {
+        let mut client = std::ptr::null_mut();
+        let code = unsafe { subxt_client_connect(std::ptr::null(), &mut client) };
+        assert_eq!(code, SubxtErrorCode::InvalidArgument);
+        assert_eq!(last_error_message().as_deref(), Some("url is null"));
+        assert!(client.is_null());
+
+        let url = CString::new("ws://127.0.0.1:9944").unwrap();
+        let code = unsafe { subxt_client_connect(url.as_ptr(), std::ptr::null_mut()) };
+        assert_eq!(code, SubxtErrorCode::InvalidArgument);
+        assert_eq!(last_error_message().as_deref(), Some("out_client is null"));
+    }
+
+    #[test]
+    fn connect_reports_invalid_urls() {
+        let url = CString::new("not a url").unwrap();
+        let mut client = std::ptr::null_mut();
+        let code = unsafe { subxt_client_connect(url.as_ptr(), &mut client) };
+        assert_ne!(code, SubxtErrorCode::Ok);
+        assert!(client.is_null());
+    }
+
+    #[test]
+    fn null_client_handles_are_reported() {
+        let mut out = std::ptr::null_mut();
+        let code = unsafe { subxt_client_genesis_hash(std::ptr::null(), &mut out) };
+        assert_eq!(code, SubxtErrorCode::InvalidArgument);
+        assert_eq!(last_error_message().as_deref(), Some("client is null"));
+        assert!(out.is_null());
+
+        let code = unsafe { subxt_client_runtime_version(std::ptr::null(), &mut out) };
+        assert_eq!(code, SubxtErrorCode::InvalidArgument);
+        assert_eq!(last_error_message().as_deref(), Some("client is null"));
+        assert!(out.is_null());
+
+        // Freeing a null client does nothing.
+        unsafe { subxt_client_free(std::ptr::null_mut()) };
+    }
+
+    #[test]
+    fn null_out_pointers_are_reported() {
+        let code = unsafe { subxt_client_genesis_hash(std::ptr::null(), std::ptr::null_mut()) };
+        assert_eq!(code, SubxtErrorCode::InvalidArgument);
+        assert_eq!(last_error_message().as_deref(), Some("out_hash is null"));
+
+        let code = unsafe { subxt_client_runtime_version(std::ptr::null(), std::ptr::null_mut()) };
+        assert_eq!(code, SubxtErrorCode::InvalidArgument);
+        assert_eq!(last_error_message().as_deref(), Some("out_json is null"));
+    }
+}
@@ -0,0 +1,188 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use crate::utils::into_c_string;
+use std::any::Any;
+use std::cell::RefCell;
+use std::os::raw::c_char;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use subxt::error::Error;
+
+/// The result of calling one of the functions exposed by this library.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubxtErrorCode {
+    /// The call succeeded.
+    Ok = 0,
+    /// An argument was invalid, for instance a null pointer, a string which isn't
+    /// valid UTF-8 or some JSON which doesn't describe a valid value.
+    InvalidArgument = 1,
+    /// Something went wrong talking to the node.
+    Rpc = 2,
+    /// A request to the node took too long to complete.
+    Timeout = 3,
+    /// The thing being asked for (for instance a pallet, storage entry or call)
+    /// could not be found in the metadata.
+    Metadata = 4,
+    /// A value could not be encoded into the shape that the node expects.
+    Encode = 5,
+    /// Some bytes handed back from the node could not be decoded.
+    Decode = 6,
+    /// A transaction was invalid, was dropped, or failed to execute.
+    Transaction = 7,
+    /// Some other error occurred.
+    Other = 8,
+    /// The call panicked. This is a bug.
+    Panic = 9,
+}
+
+/// An error which is reported to the caller as a [`SubxtErrorCode`] and message.
+#[derive(Debug)]
+pub(crate) struct FfiError {
+    code: SubxtErrorCode,
+    message: String,
+}
+
+impl FfiError {
+    pub(crate) fn new(code: SubxtErrorCode, message: impl Into<String>) -> Self {
+        FfiError {
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn invalid_argument(message: impl Into<String>) -> Self {
+        FfiError::new(SubxtErrorCode::InvalidArgument, message)
+    }
+
+    pub(crate) fn code(&self) -> SubxtErrorCode {
+        self.code
+    }
+
+    pub(crate) fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl From<Error> for FfiError {
+    fn from(err: Error) -> Self {
+        let code = if err.is_request_timeout() {
+            SubxtErrorCode::Timeout
+        } else {
+            match &err {
+                Error::Rpc(_) | Error::Io(_) => SubxtErrorCode::Rpc,
+                Error::Metadata(_) | Error::MetadataDecoding(_) => SubxtErrorCode::Metadata,
+                Error::Encode(_) | Error::StorageAddress(_) | Error::Extrinsic(_) => {
+                    SubxtErrorCode::Encode
+                }
+                Error::Decode(_) | Error::Codec(_) => SubxtErrorCode::Decode,
+                Error::Runtime(_) | Error::Transaction(_) => SubxtErrorCode::Transaction,
+                _ => SubxtErrorCode::Other,
+            }
+        };
+        FfiError::new(code, err.to_string())
+    }
+}
+
+impl From<serde_json::Error> for FfiError {
+    fn from(err: serde_json::Error) -> Self {
+        FfiError::invalid_argument(format!("invalid JSON: {err}"))
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Run the body of an exported function, converting any error or panic into
+/// a [`SubxtErrorCode`] and recording the message for [`subxt_last_error_message()`].
+pub(crate) fn ffi_call(f: impl FnOnce() -> Result<(), FfiError>) -> SubxtErrorCode {
+    let err = match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => None,
+        Ok(Err(err)) => Some(err),
+        Err(payload) => Some(FfiError::new(
+            SubxtErrorCode::Panic,
+            format!("panicked: {}", panic_message(&*payload)),
+        )),
+    };
+
+    let code = err.as_ref().map_or(SubxtErrorCode::Ok, |err| err.code);
+    LAST_ERROR.with(|last| *last.borrow_mut() = err.map(|err| err.message));
+    code
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "unknown panic"
+    }
+}
+
+/// Return a description of the error returned by the last call made on this thread,
+/// or null if the last call succeeded. The string must be freed with
+/// [`crate::subxt_string_free()`].
+#[unsafe(no_mangle)]
+pub extern "C" fn subxt_last_error_message() -> *mut c_char {
+    LAST_ERROR.with(|last| match &*last.borrow() {
+        Some(message) => into_c_string(message.clone()),
+        None => std::ptr::null_mut(),
+    })
+}
+
+/// The message recorded by the last call made on this thread, if it failed.
+#[cfg(test)]
+pub(crate) fn last_error_message() -> Option<String> {
+    let ptr = subxt_last_error_message();
+    if ptr.is_null() {
+        return None;
+    }
+    // SAFETY: The pointer is non-null, so it's a string from `into_c_string`.
+    let message = unsafe { std::ffi::CStr::from_ptr(ptr) }
+        .to_str()
+        .unwrap()
+        .to_owned();
+    // SAFETY: The string came from `subxt_last_error_message` and is freed once.
+    unsafe { crate::subxt_string_free(ptr) };
+    Some(message)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use subxt::error::RpcError;
+
+    #[test]
+    fn errors_are_recorded_until_the_next_call() {
+        let code = ffi_call(|| Err(FfiError::invalid_argument("bad things")));
+        assert_eq!(code, SubxtErrorCode::InvalidArgument);
+        assert_eq!(last_error_message().as_deref(), Some("bad things"));
+
+        let code = ffi_call(|| Ok(()));
+        assert_eq!(code, SubxtErrorCode::Ok);
+        assert_eq!(last_error_message(), None);
+    }
+
+    #[test]
+    fn panics_are_caught() {
+        let code = ffi_call(|| panic!("oh no"));
+        assert_eq!(code, SubxtErrorCode::Panic);
+        assert_eq!(last_error_message().as_deref(), Some("panicked: oh no"));
+    }
+
+    #[test]
+    fn subxt_errors_map_to_codes() {
+        let timeout: FfiError =
+            Error::from(RpcError::RequestTimeout(std::time::Duration::from_secs(1))).into();
+        assert_eq!(timeout.code, SubxtErrorCode::Timeout);
+
+        let rpc: FfiError = Error::from(RpcError::SubscriptionDropped).into();
+        assert_eq!(rpc.code, SubxtErrorCode::Rpc);
+
+        let other: FfiError = Error::Other("foo".into()).into();
+        assert_eq!(other.code, SubxtErrorCode::Other);
+    }
+}
@@ -0,0 +1,315 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Subscribe to the events in finalized blocks.
+
+use crate::client::SubxtClient;
+use crate::error::{FfiError, SubxtErrorCode, ffi_call};
+use crate::runtime;
+use crate::utils::{handle_arg, out_arg, to_hex, write_out};
+use crate::value::composite_to_json;
+use serde_json::{Value as Json, json};
+use std::ffi::{CString, c_void};
+use std::os::raw::c_char;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use subxt::blocks::Block;
+use subxt::error::Error;
+use subxt::events::{EventDetails, Phase};
+use subxt::{OnlineClient, PolkadotConfig};
+use tokio::task::JoinHandle;
+
+/// Called with the events in each new finalized block. `user_data` is the pointer given to
+/// [`subxt_events_subscribe()`].
+///
+/// If `code` is [`SubxtErrorCode::Ok`], then `json` is a JSON object like
+/// `{ "number": 1, "hash": "0x..", "events": [..] }`. Each event is an object like
+/// `{ "index": 0, "pallet": "Balances", "name": "Transfer", "extrinsic_index": 1, "fields": {..} }`,
+/// where `extrinsic_index` is null for events which weren't emitted by an extrinsic.
+///
+/// Otherwise, the subscription has failed, `json` is a description of the error and the
+/// callback won't be called again.
+///
+/// `json` is only valid until the callback returns. The callback is called from a thread
+/// owned by this library which isn't used for anything else, so it may call other functions
+/// in this library, including [`subxt_subscription_free()`] to stop the subscription. It
+/// must not unwind.
+pub type SubxtEventCallback =
+    unsafe extern "C" fn(user_data: *mut c_void, code: SubxtErrorCode, json: *const c_char);
+
+/// A handle to an event subscription. Freeing it with [`subxt_subscription_free()`]
+/// stops the subscription.
+#[derive(Debug)]
+pub struct SubxtSubscription {
+    /// Fetches the events and sends them to the callback thread.
+    task: JoinHandle<()>,
+    /// Used to wake the callback thread up when the subscription is stopped.
+    messages: mpsc::Sender<Option<Message>>,
+    /// Set when the subscription is stopped, so that queued messages are dropped.
+    stopped: Arc<AtomicBool>,
+    /// Calls the callback with each message.
+    callback_thread: Option<thread::JoinHandle<()>>,
+}
+
+/// The error code and JSON to call the callback with.
+type Message = (SubxtErrorCode, String);
+
+impl Drop for SubxtSubscription {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        self.task.abort();
+        let _ = self.messages.send(None);
+
+        let Some(callback_thread) = self.callback_thread.take() else {
+            return;
+        };
+        // The subscription can be freed from within the callback, in which case the
+        // thread finishes by itself once the callback returns.
+        if callback_thread.thread().id() != thread::current().id() {
+            let _ = callback_thread.join();
+        }
+    }
+}
+
+/// Subscribe to finalized blocks, calling `callback` with the events in each one, and
+/// write a handle to the subscription to `out_subscription`.
+///
+/// # Safety
+///
+/// `client` must be a live client handle and `out_subscription` must be valid to write to.
+/// `callback` is called from another thread, so `user_data` must be safe to use from other
+/// threads until the subscription is freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn subxt_events_subscribe(
+    client: *const SubxtClient,
+    callback: Option<SubxtEventCallback>,
+    user_data: *mut c_void,
+    out_subscription: *mut *mut SubxtSubscription,
+) -> SubxtErrorCode {
+    ffi_call(|| {
+        out_arg(out_subscription, "out_subscription")?;
+        // SAFETY: The caller guarantees that `client` is a live handle.
+        let client = unsafe { handle_arg(client, "client")? };
+        let callback = callback.ok_or_else(|| FfiError::invalid_argument("callback is null"))?;
+
+        let mut blocks = runtime().block_on(client.client.blocks().subscribe_finalized())?;
+
+        let stopped = Arc::new(AtomicBool::new(false));
+        let (messages, rx) = mpsc::channel::<Option<Message>>();
+
+        let user_data = UserData(user_data);
+        let thread_stopped = stopped.clone();
+        let callback_thread = thread::Builder::new()
+            .name("subxt-events".to_owned())
+            .spawn(move || {
+                while let Ok(Some((code, json))) = rx.recv() {
+                    if thread_stopped.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    user_data.call(callback, code, json);
+                }
+            })
+            .map_err(|e| FfiError::new(SubxtErrorCode::Other, e.to_string()))?;
+
+        let task_messages = messages.clone();
+        let task = runtime().spawn(async move {
+            loop {
+                let res = match blocks.next().await {
+                    Some(block) => block_to_json(block).await.map_err(FfiError::from),
+                    None => Err(FfiError::new(
+                        SubxtErrorCode::Rpc,
+                        "the block subscription ended",
+                    )),
+                };
+
+                let (message, failed) = match res {
+                    Ok(json) => ((SubxtErrorCode::Ok, json.to_string()), false),
+                    Err(e) => ((e.code(), e.message().to_owned()), true),
+                };
+                if task_messages.send(Some(message)).is_err() || failed {
+                    break;
+                }
+            }
+        });
+
+        let subscription = SubxtSubscription {
+            task,
+            messages,
+            stopped,
+            callback_thread: Some(callback_thread),
+        };
+        let subscription = Box::into_raw(Box::new(subscription));
+        // SAFETY: `out_subscription` was checked above and the caller guarantees it's valid.
+        unsafe { write_out(out_subscription, subscription) };
+        Ok(())
+    })
+}
+
+/// Stop and free an event subscription. Does nothing if `subscription` is null.
+///
+/// If the callback is running, this waits for it to return, so the callback won't be
+/// called again and `user_data` is no longer used once this returns. When it's called from
+/// within the callback, the subscription is stopped and the callback isn't called again
+/// after it returns.
+///
+/// # Safety
+///
+/// `subscription` must be null or a handle from [`subxt_events_subscribe()`] which has
+/// not yet been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn subxt_subscription_free(subscription: *mut SubxtSubscription) {
+    if !subscription.is_null() {
+        // SAFETY: The caller guarantees that this came from `Box::into_raw`.
+        drop(unsafe { Box::from_raw(subscription) });
+    }
+}
+
+/// The user data pointer handed to [`subxt_events_subscribe()`].
+struct UserData(*mut c_void);
+
+// SAFETY: The caller of `subxt_events_subscribe` guarantees that the pointer can be
+// used from other threads.
+unsafe impl Send for UserData {}
+
+impl UserData {
+    fn call(&self, callback: SubxtEventCallback, code: SubxtErrorCode, json: String) {
+        let json = CString::new(json.replace('\0', "")).expect("NUL bytes were removed; qed");
+        // SAFETY: The caller of `subxt_events_subscribe` guarantees that the callback
+        // and user data are valid until the subscription is freed.
+        unsafe { callback(self.0, code, json.as_ptr()) }
+    }
+}
+
+async fn block_to_json(
+    block: Result<Block<PolkadotConfig, OnlineClient<PolkadotConfig>>, Error>,
+) -> Result<Json, Error> {
+    let block = block?;
+    let events = block
+        .events()
+        .await?
+        .iter()
+        .map(|event| event_to_json(&event?))
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(json!({
+        "number": block.number(),
+        "hash": to_hex(block.hash()),
+        "events": events,
+    }))
+}
+
+/// Convert an event into JSON.
+pub(crate) fn event_to_json(event: &EventDetails<PolkadotConfig>) -> Result<Json, Error> {
+    let extrinsic_index = match event.phase() {
+        Phase::ApplyExtrinsic(index) => Some(index),
+        Phase::Finalization | Phase::Initialization => None,
+    };
+    Ok(json!({
+        "index": event.index(),
+        "pallet": event.pallet_name(),
+        "name": event.variant_name(),
+        "extrinsic_index": extrinsic_index,
+        "fields": composite_to_json(&event.field_values()?),
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::last_error_message;
+    use std::sync::atomic::AtomicUsize;
+
+    unsafe extern "C" fn count_calls(
+        user_data: *mut c_void,
+        _code: SubxtErrorCode,
+        _json: *const c_char,
+    ) {
+        // SAFETY: The tests below pass a pointer to an `AtomicUsize` which outlives the
+        // subscription.
+        let calls = unsafe { &*(user_data as *const AtomicUsize) };
+        calls.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[test]
+    fn null_client_handles_are_reported() {
+        let mut subscription = std::ptr::null_mut();
+        let code = unsafe {
+            subxt_events_subscribe(
+                std::ptr::null(),
+                Some(count_calls),
+                std::ptr::null_mut(),
+                &mut subscription,
+            )
+        };
+        assert_eq!(code, SubxtErrorCode::InvalidArgument);
+        assert_eq!(last_error_message().as_deref(), Some("client is null"));
+        assert!(subscription.is_null());
+    }
+
+    #[test]
+    fn null_out_pointers_are_reported() {
+        let code = unsafe {
+            subxt_events_subscribe(
+                std::ptr::null(),
+                None,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            )
+        };
+        assert_eq!(code, SubxtErrorCode::InvalidArgument);
+        assert_eq!(
+            last_error_message().as_deref(),
+            Some("out_subscription is null")
+        );
+    }
+
+    #[test]
+    fn freeing_null_subscriptions_does_nothing() {
+        unsafe { subxt_subscription_free(std::ptr::null_mut()) };
+    }
+
+    #[test]
+    fn freed_subscriptions_stop_calling_the_callback() {
+        let calls = AtomicUsize::new(0);
+        let user_data = UserData(&calls as *const AtomicUsize as *mut c_void);
+
+        let stopped = Arc::new(AtomicBool::new(false));
+        let (messages, rx) = mpsc::channel::<Option<Message>>();
+        let thread_stopped = stopped.clone();
+        let callback_thread = thread::spawn(move || {
+            while let Ok(Some((code, json))) = rx.recv() {
+                if thread_stopped.load(Ordering::SeqCst) {
+                    break;
+                }
+                user_data.call(count_calls, code, json);
+            }
+        });
+        let task = runtime().spawn(std::future::pending());
+
+        messages
+            .send(Some((SubxtErrorCode::Ok, "{}".to_owned())))
+            .unwrap();
+        while calls.load(Ordering::SeqCst) == 0 {
+            thread::yield_now();
+        }
+
+        let subscription = Box::into_raw(Box::new(SubxtSubscription {
+            task,
+            messages: messages.clone(),
+            stopped,
+            callback_thread: Some(callback_thread),
+        }));
+        unsafe { subxt_subscription_free(subscription) };
+
+        // The callback thread has been joined, so nothing sent now is delivered.
+        assert!(
+            messages
+                .send(Some((SubxtErrorCode::Ok, "{}".to_owned())))
+                .is_err()
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
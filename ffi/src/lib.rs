@@ -0,0 +1,80 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! # Subxt-ffi
+//!
+//! A C ABI on top of Subxt, so that it can be used from languages like C, Swift, Kotlin
+//! and Python. It exposes a stable subset of Subxt's dynamic APIs:
+//!
+//! - Connecting to a node with [`client::subxt_client_connect()`].
+//! - Querying metadata and constants, see the [`metadata`] module.
+//! - Fetching and iterating over storage entries, see the [`storage`] module.
+//! - Calling runtime APIs with [`runtime_api::subxt_runtime_api_call()`].
+//! - Creating `sr25519` and `ecdsa` signers, see the [`signer`] module.
+//! - Signing and submitting transactions, see the [`tx`] module.
+//! - Subscribing to the events in finalized blocks via a callback, see the [`events`] module.
+//!
+//! A C header describing these functions lives in `include/subxt.h`.
+//!
+//! # Conventions
+//!
+//! - Every function returns a [`SubxtErrorCode`]. If it isn't [`SubxtErrorCode::Ok`], then
+//!   [`subxt_last_error_message()`] returns a description of what went wrong. Panics are caught
+//!   and reported as [`SubxtErrorCode::Panic`] rather than unwinding into the caller.
+//! - Results are written to `out_*` pointers, which are left untouched if an error is returned.
+//! - Strings are NUL terminated and UTF-8 encoded. Strings handed back by this library must be
+//!   freed with [`subxt_string_free()`], and handles must be freed with the matching `*_free`
+//!   function.
+//! - Values are passed as JSON, as described below.
+//! - Calls which talk to the node block the current thread until they complete.
+//!
+//! All handles can be used from multiple threads at once.
+//!
+//! # Values
+//!
+//! Values (for instance call arguments, storage keys and decoded storage values) are JSON
+//! in the same shape as `scale_value`'s serde representation:
+//!
+//! - Structs with named fields are objects, and tuples, tuple structs and sequences are arrays.
+//! - Enum variants are objects of the form `{ "name": "Id", "values": [..] }`, where `values`
+//!   is an object or array holding the variant's fields.
+//! - Booleans and strings are booleans and strings. The unit value `()` is an empty array,
+//!   and `null` is accepted in its place.
+//! - Integers are numbers. Integers which don't fit into 64 bits are strings of decimal digits,
+//!   since many JSON implementations can't represent them accurately as numbers.
+//!
+//! When passing values in, they're converted using the types in the metadata, so a few
+//! shorthands are also accepted where the type allows it. Integers can be strings of decimal
+//! digits, sequences and arrays of bytes can be `0x` prefixed hex strings, types wrapping a single
+//! value (like account IDs) can be given as that value, and variants without fields can be
+//! given as just their name. This makes it easy to pass balances and account IDs, for example:
+//!
+//! ```json
+//! [{ "name": "Id", "values": ["0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"] }, "1000000000000000000000"]
+//! ```
+
+pub mod client;
+pub mod events;
+pub mod metadata;
+pub mod runtime_api;
+pub mod signer;
+pub mod storage;
+pub mod tx;
+
+mod error;
+mod utils;
+mod value;
+
+pub use error::{SubxtErrorCode, subxt_last_error_message};
+pub use utils::subxt_string_free;
+
+use std::sync::OnceLock;
+use tokio::runtime::Runtime;
+
+/// The runtime that all of the async work is done on. Calls block on this, and
+/// subscriptions are spawned onto it.
+fn runtime() -> &'static Runtime {
+    static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| Runtime::new().expect("failed to start the tokio runtime"))
+}
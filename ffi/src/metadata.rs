@@ -0,0 +1,178 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Query the metadata that a client is using, and look up constants.
+
+use crate::client::SubxtClient;
+use crate::error::{SubxtErrorCode, ffi_call};
+use crate::utils::{handle_arg, into_c_string, out_arg, str_arg, write_out};
+use crate::value::value_to_json;
+use serde_json::json;
+use std::os::raw::c_char;
+use subxt::error::{Error, MetadataError};
+
+/// Write the pallets in the metadata to `out_json`, as a JSON array of objects
+/// like `{ "name": "Balances", "index": 5 }`.
+///
+/// # Safety
+///
+/// `client` must be a live client handle and `out_json` must be valid to write to.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn subxt_metadata_pallets(
+    client: *const SubxtClient,
+    out_json: *mut *mut c_char,
+) -> SubxtErrorCode {
+    ffi_call(|| {
+        out_arg(out_json, "out_json")?;
+        // SAFETY: The caller guarantees that `client` is a live handle.
+        let client = unsafe { handle_arg(client, "client")? };
+
+        let metadata = client.client.metadata();
+        let pallets: Vec<_> = metadata
+            .pallets()
+            .map(|pallet| json!({ "name": pallet.name(), "index": pallet.index() }))
+            .collect();
+
+        // SAFETY: `out_json` was checked above and the caller guarantees it's valid.
+        unsafe { write_out(out_json, into_c_string(json!(pallets).to_string())) };
+        Ok(())
+    })
+}
+
+/// Write the details of a pallet to `out_json`, as a JSON object containing its `name`
+/// and `index`, and the names of its `calls`, `storage` entries, `events`, `errors`
+/// and `constants`.
+///
+/// # Safety
+///
+/// `client` must be a live client handle, `pallet_name` must be a NUL terminated string
+/// and `out_json` must be valid to write to.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn subxt_metadata_pallet(
+    client: *const SubxtClient,
+    pallet_name: *const c_char,
+    out_json: *mut *mut c_char,
+) -> SubxtErrorCode {
+    ffi_call(|| {
+        out_arg(out_json, "out_json")?;
+        // SAFETY: The caller guarantees that the arguments are valid.
+        let (client, pallet_name) = unsafe {
+            (
+                handle_arg(client, "client")?,
+                str_arg(pallet_name, "pallet_name")?,
+            )
+        };
+
+        let metadata = client.client.metadata();
+        let pallet = metadata
+            .pallet_by_name(pallet_name)
+            .ok_or_else(|| Error::from(MetadataError::PalletNameNotFound(pallet_name.into())))?;
+
+        let storage: Vec<_> = pallet
+            .storage()
+            .map(|storage| storage.entries().iter().map(|e| e.name()).collect())
+            .unwrap_or_default();
+        let constants: Vec<_> = pallet.constants().map(|c| c.name()).collect();
+        let calls: Vec<_> = pallet
+            .call_variants()
+            .unwrap_or_default()
+            .iter()
+            .map(|v| &v.name)
+            .collect();
+        let events: Vec<_> = pallet
+            .event_variants()
+            .unwrap_or_default()
+            .iter()
+            .map(|v| &v.name)
+            .collect();
+        let errors: Vec<_> = pallet
+            .error_variants()
+            .unwrap_or_default()
+            .iter()
+            .map(|v| &v.name)
+            .collect();
+        let json = json!({
+            "name": pallet.name(),
+            "index": pallet.index(),
+            "calls": calls,
+            "storage": storage,
+            "events": events,
+            "errors": errors,
+            "constants": constants,
+        });
+
+        // SAFETY: `out_json` was checked above and the caller guarantees it's valid.
+        unsafe { write_out(out_json, into_c_string(json.to_string())) };
+        Ok(())
+    })
+}
+
+/// Write the runtime APIs in the metadata to `out_json`, as a JSON array of objects
+/// like `{ "name": "Core", "methods": ["version", "execute_block", "initialize_block"] }`.
+///
+/// # Safety
+///
+/// `client` must be a live client handle and `out_json` must be valid to write to.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn subxt_metadata_runtime_apis(
+    client: *const SubxtClient,
+    out_json: *mut *mut c_char,
+) -> SubxtErrorCode {
+    ffi_call(|| {
+        out_arg(out_json, "out_json")?;
+        // SAFETY: The caller guarantees that `client` is a live handle.
+        let client = unsafe { handle_arg(client, "client")? };
+
+        let metadata = client.client.metadata();
+        let apis: Vec<_> = metadata
+            .runtime_api_traits()
+            .map(|api| {
+                let methods: Vec<_> = api.methods().map(|m| m.name()).collect();
+                json!({ "name": api.name(), "methods": methods })
+            })
+            .collect();
+
+        // SAFETY: `out_json` was checked above and the caller guarantees it's valid.
+        unsafe { write_out(out_json, into_c_string(json!(apis).to_string())) };
+        Ok(())
+    })
+}
+
+/// Look up a constant and write its value to `out_json`, as JSON.
+///
+/// # Safety
+///
+/// `client` must be a live client handle, `pallet_name` and `constant_name` must be
+/// NUL terminated strings and `out_json` must be valid to write to.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn subxt_constant(
+    client: *const SubxtClient,
+    pallet_name: *const c_char,
+    constant_name: *const c_char,
+    out_json: *mut *mut c_char,
+) -> SubxtErrorCode {
+    ffi_call(|| {
+        out_arg(out_json, "out_json")?;
+        // SAFETY: The caller guarantees that the arguments are valid.
+        let (client, pallet_name, constant_name) = unsafe {
+            (
+                handle_arg(client, "client")?,
+                str_arg(pallet_name, "pallet_name")?,
+                str_arg(constant_name, "constant_name")?,
+            )
+        };
+
+        let address = subxt::dynamic::constant(pallet_name, constant_name);
+        let value = client
+            .client
+            .constants()
+            .at(&address)?
+            .to_value()
+            .map_err(Error::from)?;
+
+        // SAFETY: `out_json` was checked above and the caller guarantees it's valid.
+        unsafe { write_out(out_json, into_c_string(value_to_json(&value).to_string())) };
+        Ok(())
+    })
+}
@@ -0,0 +1,79 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Call runtime APIs.
+
+use crate::client::SubxtClient;
+use crate::error::{SubxtErrorCode, ffi_call};
+use crate::runtime;
+use crate::utils::{
+    block_hash_arg, handle_arg, into_c_string, json_arg, out_arg, str_arg, write_out,
+};
+use crate::value::{json_to_fields, value_to_json};
+use std::os::raw::c_char;
+use subxt::error::{Error, MetadataError};
+
+/// Call a runtime API method and write the value it returns to `out_json`, as JSON.
+///
+/// `args_json` is a JSON array (or object, to name them) of the arguments to pass to
+/// the method, and can be null if it takes none. `block_hash` is the hex encoded hash of
+/// the block to make the call at, and if it's null, the call is made at the latest
+/// finalized block.
+///
+/// # Safety
+///
+/// `client` must be a live client handle, the strings must be NUL terminated (or null
+/// where that's allowed) and `out_json` must be valid to write to.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn subxt_runtime_api_call(
+    client: *const SubxtClient,
+    trait_name: *const c_char,
+    method_name: *const c_char,
+    args_json: *const c_char,
+    block_hash: *const c_char,
+    out_json: *mut *mut c_char,
+) -> SubxtErrorCode {
+    ffi_call(|| {
+        out_arg(out_json, "out_json")?;
+        // SAFETY: The caller guarantees that the arguments are valid.
+        let (client, trait_name, method_name, args, block_hash) = unsafe {
+            (
+                handle_arg(client, "client")?,
+                str_arg(trait_name, "trait_name")?,
+                str_arg(method_name, "method_name")?,
+                json_arg(args_json, "args_json")?,
+                block_hash_arg(block_hash, "block_hash")?,
+            )
+        };
+
+        let metadata = client.client.metadata();
+        let method = metadata
+            .runtime_api_trait_by_name(trait_name)
+            .ok_or_else(|| MetadataError::RuntimeTraitNotFound(trait_name.into()))
+            .and_then(|api| {
+                api.method_by_name(method_name)
+                    .ok_or_else(|| MetadataError::RuntimeMethodNotFound(method_name.into()))
+            })
+            .map_err(Error::from)?;
+        let inputs = method
+            .inputs()
+            .map(|input| (Some(input.name.as_str()), input.ty))
+            .collect();
+        let args = json_to_fields(args, inputs, metadata.types())?;
+
+        let payload = subxt::dynamic::runtime_api_call(trait_name, method_name, args);
+        let value = runtime().block_on(async {
+            let runtime_api = match block_hash {
+                Some(hash) => client.client.runtime_api().at(hash),
+                None => client.client.runtime_api().at_latest().await?,
+            };
+            runtime_api.call(payload).await
+        })?;
+
+        let value = value.to_value().map_err(Error::from)?;
+        // SAFETY: `out_json` was checked above and the caller guarantees it's valid.
+        unsafe { write_out(out_json, into_c_string(value_to_json(&value).to_string())) };
+        Ok(())
+    })
+}
@@ -0,0 +1,242 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Create signers which can be used to sign transactions.
+
+use crate::error::{FfiError, SubxtErrorCode, ffi_call};
+use crate::utils::{handle_arg, into_c_string, out_arg, str_arg, to_hex, write_out};
+use std::os::raw::c_char;
+use std::str::FromStr;
+use subxt::PolkadotConfig;
+use subxt::tx::Signer;
+use subxt::utils::{AccountId32, MultiSignature};
+use subxt_signer::{SecretUri, bip39::Mnemonic, ecdsa, sr25519};
+
+/// The type of key that a signer uses.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubxtKeyType {
+    /// An sr25519 key, which most Substrate based chains use by default.
+    Sr25519 = 0,
+    /// An ecdsa key.
+    Ecdsa = 1,
+}
+
+/// A handle to a signer. Free it with [`subxt_signer_free()`].
+#[derive(Debug, Clone)]
+pub struct SubxtSigner {
+    keypair: Keypair,
+}
+
+#[derive(Debug, Clone)]
+enum Keypair {
+    Sr25519(sr25519::Keypair),
+    Ecdsa(ecdsa::Keypair),
+}
+
+impl Signer<PolkadotConfig> for SubxtSigner {
+    fn account_id(&self) -> AccountId32 {
+        match &self.keypair {
+            Keypair::Sr25519(keypair) => keypair.public_key().into(),
+            Keypair::Ecdsa(keypair) => keypair.public_key().into(),
+        }
+    }
+
+    fn sign(&self, signer_payload: &[u8]) -> MultiSignature {
+        match &self.keypair {
+            Keypair::Sr25519(keypair) => keypair.sign(signer_payload).into(),
+            Keypair::Ecdsa(keypair) => keypair.sign(signer_payload).into(),
+        }
+    }
+}
+
+/// Create a signer from a secret URI, like `//Alice` or `<mnemonic phrase>//path///password`,
+/// and write a handle to it to `out_signer`.
+///
+/// # Safety
+///
+/// `uri` must be a NUL terminated string and `out_signer` must be valid to write to.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn subxt_signer_from_uri(
+    key_type: SubxtKeyType,
+    uri: *const c_char,
+    out_signer: *mut *mut SubxtSigner,
+) -> SubxtErrorCode {
+    ffi_call(|| {
+        out_arg(out_signer, "out_signer")?;
+        // SAFETY: The caller guarantees that `uri` is a valid string.
+        let uri = unsafe { str_arg(uri, "uri")? };
+
+        let uri = SecretUri::from_str(uri)
+            .map_err(|e| FfiError::invalid_argument(format!("invalid secret URI: {e}")))?;
+        let keypair = match key_type {
+            SubxtKeyType::Sr25519 => sr25519::Keypair::from_uri(&uri)
+                .map(Keypair::Sr25519)
+                .map_err(|e| FfiError::invalid_argument(e.to_string()))?,
+            SubxtKeyType::Ecdsa => ecdsa::Keypair::from_uri(&uri)
+                .map(Keypair::Ecdsa)
+                .map_err(|e| FfiError::invalid_argument(e.to_string()))?,
+        };
+
+        let signer = Box::into_raw(Box::new(SubxtSigner { keypair }));
+        // SAFETY: `out_signer` was checked above and the caller guarantees it's valid.
+        unsafe { write_out(out_signer, signer) };
+        Ok(())
+    })
+}
+
+/// Create a signer from a BIP-39 mnemonic phrase and an optional password, which can be
+/// null, and write a handle to it to `out_signer`.
+///
+/// # Safety
+///
+/// `phrase` must be a NUL terminated string, `password` must be null or a NUL terminated
+/// string and `out_signer` must be valid to write to.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn subxt_signer_from_phrase(
+    key_type: SubxtKeyType,
+    phrase: *const c_char,
+    password: *const c_char,
+    out_signer: *mut *mut SubxtSigner,
+) -> SubxtErrorCode {
+    ffi_call(|| {
+        out_arg(out_signer, "out_signer")?;
+        // SAFETY: The caller guarantees that the strings are valid.
+        let (phrase, password) = unsafe {
+            let password = if password.is_null() {
+                None
+            } else {
+                Some(str_arg(password, "password")?)
+            };
+            (str_arg(phrase, "phrase")?, password)
+        };
+
+        let phrase = Mnemonic::parse(phrase)
+            .map_err(|e| FfiError::invalid_argument(format!("invalid phrase: {e}")))?;
+        let keypair = match key_type {
+            SubxtKeyType::Sr25519 => sr25519::Keypair::from_phrase(&phrase, password)
+                .map(Keypair::Sr25519)
+                .map_err(|e| FfiError::invalid_argument(e.to_string()))?,
+            SubxtKeyType::Ecdsa => ecdsa::Keypair::from_phrase(&phrase, password)
+                .map(Keypair::Ecdsa)
+                .map_err(|e| FfiError::invalid_argument(e.to_string()))?,
+        };
+
+        let signer = Box::into_raw(Box::new(SubxtSigner { keypair }));
+        // SAFETY: `out_signer` was checked above and the caller guarantees it's valid.
+        unsafe { write_out(out_signer, signer) };
+        Ok(())
+    })
+}
+
+/// Write the SS58 encoded account ID of a signer to `out_account_id`.
+///
+/// # Safety
+///
+/// `signer` must be a live signer handle and `out_account_id` must be valid to write to.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn subxt_signer_account_id(
+    signer: *const SubxtSigner,
+    out_account_id: *mut *mut c_char,
+) -> SubxtErrorCode {
+    ffi_call(|| {
+        out_arg(out_account_id, "out_account_id")?;
+        // SAFETY: The caller guarantees that `signer` is a live handle.
+        let signer = unsafe { handle_arg(signer, "signer")? };
+
+        let account_id = Signer::<PolkadotConfig>::account_id(signer).to_string();
+        // SAFETY: `out_account_id` was checked above and the caller guarantees it's valid.
+        unsafe { write_out(out_account_id, into_c_string(account_id)) };
+        Ok(())
+    })
+}
+
+/// Write the public key of a signer, as a `0x` prefixed hex string, to `out_public_key`.
+///
+/// # Safety
+///
+/// `signer` must be a live signer handle and `out_public_key` must be valid to write to.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn subxt_signer_public_key(
+    signer: *const SubxtSigner,
+    out_public_key: *mut *mut c_char,
+) -> SubxtErrorCode {
+    ffi_call(|| {
+        out_arg(out_public_key, "out_public_key")?;
+        // SAFETY: The caller guarantees that `signer` is a live handle.
+        let signer = unsafe { handle_arg(signer, "signer")? };
+
+        let public_key = match &signer.keypair {
+            Keypair::Sr25519(keypair) => to_hex(keypair.public_key().0),
+            Keypair::Ecdsa(keypair) => to_hex(keypair.public_key().0),
+        };
+        // SAFETY: `out_public_key` was checked above and the caller guarantees it's valid.
+        unsafe { write_out(out_public_key, into_c_string(public_key)) };
+        Ok(())
+    })
+}
+
+/// Free a signer. Does nothing if `signer` is null.
+///
+/// # Safety
+///
+/// `signer` must be null or a handle from one of the `subxt_signer_from_*` functions
+/// which has not yet been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn subxt_signer_free(signer: *mut SubxtSigner) {
+    if !signer.is_null() {
+        // SAFETY: The caller guarantees that this came from `Box::into_raw`.
+        drop(unsafe { Box::from_raw(signer) });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::ffi::{CStr, CString};
+    use subxt_signer::sr25519::dev;
+
+    #[test]
+    fn signers_can_be_created_from_uris() {
+        let uri = CString::new("//Alice").unwrap();
+        let mut signer = std::ptr::null_mut();
+        let code =
+            unsafe { subxt_signer_from_uri(SubxtKeyType::Sr25519, uri.as_ptr(), &mut signer) };
+        assert_eq!(code, SubxtErrorCode::Ok);
+
+        let mut account_id = std::ptr::null_mut();
+        let code = unsafe { subxt_signer_account_id(signer, &mut account_id) };
+        assert_eq!(code, SubxtErrorCode::Ok);
+        assert_eq!(
+            unsafe { CStr::from_ptr(account_id) }.to_str().unwrap(),
+            dev::alice().public_key().to_account_id().to_string()
+        );
+
+        unsafe {
+            crate::subxt_string_free(account_id);
+            subxt_signer_free(signer);
+        }
+    }
+
+    #[test]
+    fn invalid_arguments_are_reported() {
+        let mut signer = std::ptr::null_mut();
+        let code =
+            unsafe { subxt_signer_from_uri(SubxtKeyType::Ecdsa, std::ptr::null(), &mut signer) };
+        assert_eq!(code, SubxtErrorCode::InvalidArgument);
+        assert!(signer.is_null());
+
+        let phrase = CString::new("not a valid phrase").unwrap();
+        let code = unsafe {
+            subxt_signer_from_phrase(
+                SubxtKeyType::Ecdsa,
+                phrase.as_ptr(),
+                std::ptr::null(),
+                &mut signer,
+            )
+        };
+        assert_eq!(code, SubxtErrorCode::InvalidArgument);
+        assert!(signer.is_null());
+    }
+}
@@ -0,0 +1,324 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Fetch and iterate over storage entries.
+
+use crate::client::SubxtClient;
+use crate::error::{FfiError, SubxtErrorCode, ffi_call};
+use crate::runtime;
+use crate::utils::{
+    block_hash_arg, handle_arg, into_c_string, json_arg, out_arg, str_arg, to_hex, write_out,
+};
+use crate::value::{json_to_values, value_to_json};
+use scale_info::TypeDef;
+use serde_json::{Value as Json, json};
+use std::os::raw::c_char;
+use std::sync::Mutex;
+use subxt::backend::StreamOfResults;
+use subxt::dynamic::Value;
+use subxt::error::{Error, MetadataError};
+use subxt::metadata::types::StorageEntryType;
+use subxt::storage::{DynamicAddress, Storage, StorageKeyValuePair};
+use subxt::utils::H256;
+use subxt::{OnlineClient, PolkadotConfig};
+
+/// A handle to an iterator over storage entries. Free it with [`subxt_storage_iter_free()`].
+pub struct SubxtStorageIter {
+    stream: Mutex<StreamOfResults<StorageKeyValuePair<DynamicAddress<Vec<Value>>>>>,
+}
+
+impl std::fmt::Debug for SubxtStorageIter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SubxtStorageIter").finish_non_exhaustive()
+    }
+}
+
+/// Fetch a storage entry and write its value to `out_json` as JSON, or write the string
+/// `null` if there is no value stored at that location.
+///
+/// `keys_json` is a JSON array of the keys needed to locate the entry, and can be null
+/// if it has none. `block_hash` is the hex encoded hash of the block to fetch the entry at,
+/// and if it's null, the entry is fetched at the latest finalized block.
+///
+/// # Safety
+///
+/// `client` must be a live client handle, the strings must be NUL terminated (or null
+/// where that's allowed) and `out_json` must be valid to write to.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn subxt_storage_fetch(
+    client: *const SubxtClient,
+    pallet_name: *const c_char,
+    entry_name: *const c_char,
+    keys_json: *const c_char,
+    block_hash: *const c_char,
+    out_json: *mut *mut c_char,
+) -> SubxtErrorCode {
+    ffi_call(|| {
+        out_arg(out_json, "out_json")?;
+        // SAFETY: The caller guarantees that the arguments are valid.
+        let (client, pallet_name, entry_name, keys, block_hash) = unsafe {
+            (
+                handle_arg(client, "client")?,
+                str_arg(pallet_name, "pallet_name")?,
+                str_arg(entry_name, "entry_name")?,
+                json_arg(keys_json, "keys_json")?,
+                block_hash_arg(block_hash, "block_hash")?,
+            )
+        };
+
+        let keys = storage_keys(client, pallet_name, entry_name, keys)?;
+        let address = subxt::dynamic::storage(pallet_name, entry_name, keys);
+        let value = runtime().block_on(async {
+            let storage = storage_at(&client.client, block_hash).await?;
+            storage.fetch(&address).await
+        })?;
+
+        let json = match value {
+            Some(value) => value_to_json(&value.to_value().map_err(Error::from)?),
+            None => serde_json::Value::Null,
+        };
+        // SAFETY: `out_json` was checked above and the caller guarantees it's valid.
+        unsafe { write_out(out_json, into_c_string(json.to_string())) };
+        Ok(())
+    })
+}
+
+/// Start iterating over the storage entries which share the given partial keys, and write
+/// an iterator handle to `out_iter`. Use [`subxt_storage_iter_next()`] to fetch entries.
+///
+/// `keys_json` and `block_hash` are as in [`subxt_storage_fetch()`]. Pass no keys to iterate
+/// over every entry in a map.
+///
+/// # Safety
+///
+/// `client` must be a live client handle, the strings must be NUL terminated (or null
+/// where that's allowed) and `out_iter` must be valid to write to.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn subxt_storage_iter(
+    client: *const SubxtClient,
+    pallet_name: *const c_char,
+    entry_name: *const c_char,
+    keys_json: *const c_char,
+    block_hash: *const c_char,
+    out_iter: *mut *mut SubxtStorageIter,
+) -> SubxtErrorCode {
+    ffi_call(|| {
+        out_arg(out_iter, "out_iter")?;
+        // SAFETY: The caller guarantees that the arguments are valid.
+        let (client, pallet_name, entry_name, keys, block_hash) = unsafe {
+            (
+                handle_arg(client, "client")?,
+                str_arg(pallet_name, "pallet_name")?,
+                str_arg(entry_name, "entry_name")?,
+                json_arg(keys_json, "keys_json")?,
+                block_hash_arg(block_hash, "block_hash")?,
+            )
+        };
+
+        let keys = storage_keys(client, pallet_name, entry_name, keys)?;
+        let address = subxt::dynamic::storage(pallet_name, entry_name, keys);
+        let stream = runtime().block_on(async {
+            let storage = storage_at(&client.client, block_hash).await?;
+            storage.iter(address).await
+        })?;
+
+        let iter = Box::into_raw(Box::new(SubxtStorageIter {
+            stream: Mutex::new(stream),
+        }));
+        // SAFETY: `out_iter` was checked above and the caller guarantees it's valid.
+        unsafe { write_out(out_iter, iter) };
+        Ok(())
+    })
+}
+
+/// Fetch the next storage entry from an iterator and write it to `out_json`, as a JSON
+/// object like `{ "key": "0x..", "keys": [..], "value": .. }`. `key` is the hex encoded
+/// storage key, `keys` are the map keys that can be decoded from it, and `value` is the
+/// value of the entry. Once there are no more entries, a null pointer is written instead.
+///
+/// # Safety
+///
+/// `iter` must be a live iterator handle and `out_json` must be valid to write to.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn subxt_storage_iter_next(
+    iter: *const SubxtStorageIter,
+    out_json: *mut *mut c_char,
+) -> SubxtErrorCode {
+    ffi_call(|| {
+        out_arg(out_json, "out_json")?;
+        // SAFETY: The caller guarantees that `iter` is a live handle.
+        let iter = unsafe { handle_arg(iter, "iter")? };
+
+        let mut stream = iter.stream.lock().unwrap_or_else(|e| e.into_inner());
+        let next = runtime().block_on(stream.next()).transpose()?;
+
+        let json = match next {
+            Some(kv) => {
+                let value = kv.value.to_value().map_err(Error::from)?;
+                let keys: Vec<_> = kv.keys.iter().map(value_to_json).collect();
+                let json = json!({
+                    "key": to_hex(&kv.key_bytes),
+                    "keys": keys,
+                    "value": value_to_json(&value),
+                });
+                into_c_string(json.to_string())
+            }
+            None => std::ptr::null_mut(),
+        };
+        // SAFETY: `out_json` was checked above and the caller guarantees it's valid.
+        unsafe { write_out(out_json, json) };
+        Ok(())
+    })
+}
+
+/// Free a storage iterator. Does nothing if `iter` is null.
+///
+/// # Safety
+///
+/// `iter` must be null or a handle from [`subxt_storage_iter()`] which has not yet
+/// been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn subxt_storage_iter_free(iter: *mut SubxtStorageIter) {
+    if !iter.is_null() {
+        // SAFETY: The caller guarantees that this came from `Box::into_raw`.
+        drop(unsafe { Box::from_raw(iter) });
+    }
+}
+
+/// Convert the JSON keys of a storage entry using the types of its keys.
+fn storage_keys(
+    client: &SubxtClient,
+    pallet_name: &str,
+    entry_name: &str,
+    keys: Json,
+) -> Result<Vec<Value>, FfiError> {
+    let metadata = client.client.metadata();
+    let entry = metadata
+        .pallet_by_name(pallet_name)
+        .ok_or_else(|| MetadataError::PalletNameNotFound(pallet_name.into()))
+        .and_then(|pallet| {
+            pallet
+                .storage()
+                .ok_or_else(|| MetadataError::StorageNotFoundInPallet(pallet_name.into()))
+        })
+        .and_then(|storage| {
+            storage
+                .entry_by_name(entry_name)
+                .ok_or_else(|| MetadataError::StorageEntryNotFound(entry_name.into()))
+        })
+        .map_err(Error::from)?;
+
+    // Maps with several hashers have a tuple key, with one value per hasher.
+    let key_types = match entry.entry_type() {
+        StorageEntryType::Plain(_) => Vec::new(),
+        StorageEntryType::Map {
+            hashers, key_ty, ..
+        } if hashers.len() == 1 => vec![*key_ty],
+        StorageEntryType::Map { key_ty, .. } => {
+            match metadata.types().resolve(*key_ty).map(|ty| &ty.type_def) {
+                Some(TypeDef::Tuple(tuple)) => tuple.fields.iter().map(|f| f.id).collect(),
+                _ => return Err(Error::from(MetadataError::TypeNotFound(*key_ty)).into()),
+            }
+        }
+    };
+    json_to_values(keys, &key_types, metadata.types())
+}
+
+async fn storage_at(
+    client: &OnlineClient<PolkadotConfig>,
+    block_hash: Option<H256>,
+) -> Result<Storage<PolkadotConfig, OnlineClient<PolkadotConfig>>, Error> {
+    match block_hash {
+        Some(hash) => Ok(client.storage().at(hash)),
+        None => client.storage().at_latest().await,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::last_error_message;
+    use std::ffi::CString;
+
+    #[test]
+    fn null_client_handles_are_reported() {
+        let pallet_name = CString::new("System").unwrap();
+        let entry_name = CString::new("Account").unwrap();
+
+        let mut json = std::ptr::null_mut();
+        let code = unsafe {
+            subxt_storage_fetch(
+                std::ptr::null(),
+                pallet_name.as_ptr(),
+                entry_name.as_ptr(),
+                std::ptr::null(),
+                std::ptr::null(),
+                &mut json,
+            )
+        };
+        assert_eq!(code, SubxtErrorCode::InvalidArgument);
+        assert_eq!(last_error_message().as_deref(), Some("client is null"));
+        assert!(json.is_null());
+
+        let mut iter = std::ptr::null_mut();
+        let code = unsafe {
+            subxt_storage_iter(
+                std::ptr::null(),
+                pallet_name.as_ptr(),
+                entry_name.as_ptr(),
+                std::ptr::null(),
+                std::ptr::null(),
+                &mut iter,
+            )
+        };
+        assert_eq!(code, SubxtErrorCode::InvalidArgument);
+        assert_eq!(last_error_message().as_deref(), Some("client is null"));
+        assert!(iter.is_null());
+    }
+
+    #[test]
+    fn null_out_pointers_are_reported() {
+        let code = unsafe {
+            subxt_storage_fetch(
+                std::ptr::null(),
+                std::ptr::null(),
+                std::ptr::null(),
+                std::ptr::null(),
+                std::ptr::null(),
+                std::ptr::null_mut(),
+            )
+        };
+        assert_eq!(code, SubxtErrorCode::InvalidArgument);
+        assert_eq!(last_error_message().as_deref(), Some("out_json is null"));
+
+        let code = unsafe {
+            subxt_storage_iter(
+                std::ptr::null(),
+                std::ptr::null(),
+                std::ptr::null(),
+                std::ptr::null(),
+                std::ptr::null(),
+                std::ptr::null_mut(),
+            )
+        };
+        assert_eq!(code, SubxtErrorCode::InvalidArgument);
+        assert_eq!(last_error_message().as_deref(), Some("out_iter is null"));
+
+        let code = unsafe { subxt_storage_iter_next(std::ptr::null(), std::ptr::null_mut()) };
+        assert_eq!(code, SubxtErrorCode::InvalidArgument);
+        assert_eq!(last_error_message().as_deref(), Some("out_json is null"));
+    }
+
+    #[test]
+    fn null_iterator_handles_are_reported() {
+        let mut json = std::ptr::null_mut();
+        let code = unsafe { subxt_storage_iter_next(std::ptr::null(), &mut json) };
+        assert_eq!(code, SubxtErrorCode::InvalidArgument);
+        assert_eq!(last_error_message().as_deref(), Some("iter is null"));
+        assert!(json.is_null());
+
+        // Freeing a null iterator does nothing.
+        unsafe { subxt_storage_iter_free(std::ptr::null_mut()) };
+    }
+}
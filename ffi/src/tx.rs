@@ -0,0 +1,276 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Build, sign and submit transactions.
+
+use crate::client::SubxtClient;
+use crate::error::{FfiError, SubxtErrorCode, ffi_call};
+use crate::events::event_to_json;
+use crate::runtime;
+use crate::signer::SubxtSigner;
+use crate::utils::{handle_arg, into_c_string, json_arg, out_arg, str_arg, to_hex, write_out};
+use crate::value::json_to_fields;
+use serde_json::{Value as Json, json};
+use std::os::raw::c_char;
+use subxt::error::{Error, MetadataError};
+use subxt::ext::scale_value::Composite;
+use subxt::tx::{SubmittableTransaction, TxProgress};
+use subxt::{OnlineClient, PolkadotConfig};
+
+/// Build and sign a transaction which calls `call_name` in `pallet_name`, using the next
+/// nonce for the signer's account and default parameters otherwise. The signed transaction
+/// is written to `out_tx` as a `0x` prefixed hex string, which can be handed to
+/// [`subxt_tx_submit()`].
+///
+/// `args_json` is a JSON array (or object, to name them) of the arguments to the call,
+/// and can be null if it takes none.
+///
+/// # Safety
+///
+/// `client` and `signer` must be live handles, the strings must be NUL terminated (or null
+/// where that's allowed) and `out_tx` must be valid to write to.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn subxt_tx_sign(
+    client: *const SubxtClient,
+    pallet_name: *const c_char,
+    call_name: *const c_char,
+    args_json: *const c_char,
+    signer: *const SubxtSigner,
+    out_tx: *mut *mut c_char,
+) -> SubxtErrorCode {
+    ffi_call(|| {
+        out_arg(out_tx, "out_tx")?;
+        // SAFETY: The caller guarantees that the arguments are valid.
+        let (client, pallet_name, call_name, args, signer) = unsafe {
+            (
+                handle_arg(client, "client")?,
+                str_arg(pallet_name, "pallet_name")?,
+                str_arg(call_name, "call_name")?,
+                json_arg(args_json, "args_json")?,
+                handle_arg(signer, "signer")?,
+            )
+        };
+
+        let args = call_args(client, pallet_name, call_name, args)?;
+        let payload = subxt::dynamic::tx(pallet_name, call_name, args);
+        let tx = runtime().block_on(client.client.tx().create_signed(
+            &payload,
+            signer,
+            Default::default(),
+        ))?;
+
+        // SAFETY: `out_tx` was checked above and the caller guarantees it's valid.
+        unsafe { write_out(out_tx, into_c_string(to_hex(tx.encoded()))) };
+        Ok(())
+    })
+}
+
+/// Submit a signed transaction, given as a hex string, and wait for it to be finalized.
+/// If it executes successfully, the outcome is written to `out_json` as a JSON object like
+/// `{ "block_hash": "0x..", "extrinsic_hash": "0x..", "events": [..] }`, where `events` are
+/// the events emitted by the transaction in the form described by
+/// [`crate::events::SubxtEventCallback`].
+///
+/// If the transaction is invalid, is dropped, or fails to execute, then
+/// [`SubxtErrorCode::Transaction`] is returned.
+///
+/// # Safety
+///
+/// `client` must be a live client handle, `tx` must be a NUL terminated string and
+/// `out_json` must be valid to write to.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn subxt_tx_submit(
+    client: *const SubxtClient,
+    tx: *const c_char,
+    out_json: *mut *mut c_char,
+) -> SubxtErrorCode {
+    ffi_call(|| {
+        out_arg(out_json, "out_json")?;
+        // SAFETY: The caller guarantees that the arguments are valid.
+        let (client, tx) = unsafe { (handle_arg(client, "client")?, str_arg(tx, "tx")?) };
+
+        let tx_bytes = hex::decode(tx.strip_prefix("0x").unwrap_or(tx))
+            .map_err(|e| FfiError::invalid_argument(format!("tx is not valid hex: {e}")))?;
+        let tx = SubmittableTransaction::from_bytes(client.client.clone(), tx_bytes)?;
+        let json = runtime().block_on(async {
+            let progress = tx.submit_and_watch().await?;
+            wait_for_success(progress).await
+        })?;
+
+        // SAFETY: `out_json` was checked above and the caller guarantees it's valid.
+        unsafe { write_out(out_json, into_c_string(json.to_string())) };
+        Ok(())
+    })
+}
+
+/// Build, sign and submit a transaction, and wait for it to be finalized. This is the same
+/// as calling [`subxt_tx_sign()`] and then [`subxt_tx_submit()`], and the outcome is written
+/// to `out_json` in the same way.
+///
+/// # Safety
+///
+/// `client` and `signer` must be live handles, the strings must be NUL terminated (or null
+/// where that's allowed) and `out_json` must be valid to write to.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn subxt_tx_sign_and_submit(
+    client: *const SubxtClient,
+    pallet_name: *const c_char,
+    call_name: *const c_char,
+    args_json: *const c_char,
+    signer: *const SubxtSigner,
+    out_json: *mut *mut c_char,
+) -> SubxtErrorCode {
+    ffi_call(|| {
+        out_arg(out_json, "out_json")?;
+        // SAFETY: The caller guarantees that the arguments are valid.
+        let (client, pallet_name, call_name, args, signer) = unsafe {
+            (
+                handle_arg(client, "client")?,
+                str_arg(pallet_name, "pallet_name")?,
+                str_arg(call_name, "call_name")?,
+                json_arg(args_json, "args_json")?,
+                handle_arg(signer, "signer")?,
+            )
+        };
+
+        let args = call_args(client, pallet_name, call_name, args)?;
+        let payload = subxt::dynamic::tx(pallet_name, call_name, args);
+        let json = runtime().block_on(async {
+            let progress = client
+                .client
+                .tx()
+                .sign_and_submit_then_watch_default(&payload, signer)
+                .await?;
+            wait_for_success(progress).await
+        })?;
+
+        // SAFETY: `out_json` was checked above and the caller guarantees it's valid.
+        unsafe { write_out(out_json, into_c_string(json.to_string())) };
+        Ok(())
+    })
+}
+
+/// Convert the JSON arguments to a call using the types of its fields.
+fn call_args(
+    client: &SubxtClient,
+    pallet_name: &str,
+    call_name: &str,
+    args: Json,
+) -> Result<Composite<()>, FfiError> {
+    let metadata = client.client.metadata();
+    let call = metadata
+        .pallet_by_name(pallet_name)
+        .ok_or_else(|| MetadataError::PalletNameNotFound(pallet_name.into()))
+        .and_then(|pallet| {
+            pallet
+                .call_variant_by_name(call_name)
+                .ok_or_else(|| MetadataError::CallNameNotFound(call_name.into()))
+        })
+        .map_err(Error::from)?;
+    let fields = call
+        .fields
+        .iter()
+        .map(|f| (f.name.as_deref(), f.ty.id))
+        .collect();
+    json_to_fields(args, fields, metadata.types())
+}
+
+async fn wait_for_success(
+    progress: TxProgress<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+) -> Result<Json, Error> {
+    let in_block = progress.wait_for_finalized().await?;
+    let events = in_block.wait_for_success().await?;
+    let event_json = events
+        .iter()
+        .map(|event| event_to_json(&event?))
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(json!({
+        "block_hash": to_hex(in_block.block_hash()),
+        "extrinsic_hash": to_hex(in_block.extrinsic_hash()),
+        "events": event_json,
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::last_error_message;
+    use std::ffi::CString;
+
+    #[test]
+    fn null_client_handles_are_reported() {
+        let pallet_name = CString::new("Balances").unwrap();
+        let call_name = CString::new("transfer_keep_alive").unwrap();
+
+        let mut out = std::ptr::null_mut();
+        let code = unsafe {
+            subxt_tx_sign(
+                std::ptr::null(),
+                pallet_name.as_ptr(),
+                call_name.as_ptr(),
+                std::ptr::null(),
+                std::ptr::null(),
+                &mut out,
+            )
+        };
+        assert_eq!(code, SubxtErrorCode::InvalidArgument);
+        assert_eq!(last_error_message().as_deref(), Some("client is null"));
+        assert!(out.is_null());
+
+        let tx = CString::new("0x00").unwrap();
+        let code = unsafe { subxt_tx_submit(std::ptr::null(), tx.as_ptr(), &mut out) };
+        assert_eq!(code, SubxtErrorCode::InvalidArgument);
+        assert_eq!(last_error_message().as_deref(), Some("client is null"));
+        assert!(out.is_null());
+
+        let code = unsafe {
+            subxt_tx_sign_and_submit(
+                std::ptr::null(),
+                pallet_name.as_ptr(),
+                call_name.as_ptr(),
+                std::ptr::null(),
+                std::ptr::null(),
+                &mut out,
+            )
+        };
+        assert_eq!(code, SubxtErrorCode::InvalidArgument);
+        assert_eq!(last_error_message().as_deref(), Some("client is null"));
+        assert!(out.is_null());
+    }
+
+    #[test]
+    fn null_out_pointers_are_reported() {
+        let code = unsafe {
+            subxt_tx_sign(
+                std::ptr::null(),
+                std::ptr::null(),
+                std::ptr::null(),
+                std::ptr::null(),
+                std::ptr::null(),
+                std::ptr::null_mut(),
+            )
+        };
+        assert_eq!(code, SubxtErrorCode::InvalidArgument);
+        assert_eq!(last_error_message().as_deref(), Some("out_tx is null"));
+
+        let code =
+            unsafe { subxt_tx_submit(std::ptr::null(), std::ptr::null(), std::ptr::null_mut()) };
+        assert_eq!(code, SubxtErrorCode::InvalidArgument);
+        assert_eq!(last_error_message().as_deref(), Some("out_json is null"));
+
+        let code = unsafe {
+            subxt_tx_sign_and_submit(
+                std::ptr::null(),
+                std::ptr::null(),
+                std::ptr::null(),
+                std::ptr::null(),
+                std::ptr::null(),
+                std::ptr::null_mut(),
+            )
+        };
+        assert_eq!(code, SubxtErrorCode::InvalidArgument);
+        assert_eq!(last_error_message().as_deref(), Some("out_json is null"));
+    }
+}
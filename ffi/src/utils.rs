@@ -0,0 +1,122 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Helpers for moving arguments and results across the FFI boundary.
+
+use crate::error::FfiError;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use subxt::utils::H256;
+
+/// Read a string argument, failing if it's null or isn't valid UTF-8.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to a NUL terminated string which outlives `'a`.
+pub(crate) unsafe fn str_arg<'a>(ptr: *const c_char, name: &str) -> Result<&'a str, FfiError> {
+    if ptr.is_null() {
+        return Err(FfiError::invalid_argument(format!("{name} is null")));
+    }
+    // SAFETY: The caller guarantees that the pointer is to a valid NUL terminated string.
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .map_err(|e| FfiError::invalid_argument(format!("{name} is not valid UTF-8: {e}")))
+}
+
+/// Read an optional JSON argument. A null pointer is treated as JSON `null`.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to a NUL terminated string.
+pub(crate) unsafe fn json_arg(
+    ptr: *const c_char,
+    name: &str,
+) -> Result<serde_json::Value, FfiError> {
+    if ptr.is_null() {
+        return Ok(serde_json::Value::Null);
+    }
+    // SAFETY: The caller upholds the requirements of `str_arg`.
+    let json = unsafe { str_arg(ptr, name)? };
+    serde_json::from_str(json)
+        .map_err(|e| FfiError::invalid_argument(format!("{name} is not valid JSON: {e}")))
+}
+
+/// Read an optional block hash argument, given as a hex string. A null pointer
+/// means that no block hash was given.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to a NUL terminated string.
+pub(crate) unsafe fn block_hash_arg(
+    ptr: *const c_char,
+    name: &str,
+) -> Result<Option<H256>, FfiError> {
+    if ptr.is_null() {
+        return Ok(None);
+    }
+    // SAFETY: The caller upholds the requirements of `str_arg`.
+    let hash = unsafe { str_arg(ptr, name)? };
+    let bytes = hex::decode(hash.strip_prefix("0x").unwrap_or(hash))
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .ok_or_else(|| FfiError::invalid_argument(format!("{name} is not a 32 byte hex string")))?;
+    Ok(Some(H256(bytes)))
+}
+
+/// Borrow the value behind a handle, failing if it's null.
+///
+/// # Safety
+///
+/// `ptr` must be null or a handle handed out by this library which has not yet been freed.
+pub(crate) unsafe fn handle_arg<'a, T>(ptr: *const T, name: &str) -> Result<&'a T, FfiError> {
+    // SAFETY: The caller guarantees that a non-null pointer is a live handle.
+    unsafe { ptr.as_ref() }.ok_or_else(|| FfiError::invalid_argument(format!("{name} is null")))
+}
+
+/// Check that an output pointer isn't null before doing any work.
+pub(crate) fn out_arg<T>(ptr: *mut T, name: &str) -> Result<(), FfiError> {
+    if ptr.is_null() {
+        return Err(FfiError::invalid_argument(format!("{name} is null")));
+    }
+    Ok(())
+}
+
+/// Write a result to an output pointer which has been checked with [`out_arg()`].
+///
+/// # Safety
+///
+/// `ptr` must be non-null and valid to write a `T` to.
+pub(crate) unsafe fn write_out<T>(ptr: *mut T, value: T) {
+    // SAFETY: The caller guarantees that the pointer is valid to write to.
+    unsafe { ptr.write(value) }
+}
+
+/// Hand a string over to the caller, who must free it with [`subxt_string_free()`].
+/// Any NUL bytes in the string are removed, since they can't be represented.
+pub(crate) fn into_c_string(s: String) -> *mut c_char {
+    let c_string = CString::new(s).unwrap_or_else(|e| {
+        let mut bytes = e.into_vec();
+        bytes.retain(|&b| b != 0);
+        CString::new(bytes).expect("NUL bytes were removed above; qed")
+    });
+    c_string.into_raw()
+}
+
+/// Format some bytes as a `0x` prefixed hex string.
+pub(crate) fn to_hex(bytes: impl AsRef<[u8]>) -> String {
+    format!("0x{}", hex::encode(bytes.as_ref()))
+}
+
+/// Free a string which was handed back by this library. Does nothing if `s` is null.
+///
+/// # Safety
+///
+/// `s` must be null or a string handed back by this library which has not yet been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn subxt_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        // SAFETY: The caller guarantees that this string came from `CString::into_raw`.
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
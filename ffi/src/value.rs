@@ -0,0 +1,452 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Convert between JSON and [`Value`]s. See the crate docs for a description of the format.
+
+use crate::error::{FfiError, SubxtErrorCode};
+use crate::utils::to_hex;
+use scale_info::{PortableRegistry, Type, TypeDef, TypeDefPrimitive, form::PortableForm};
+use serde_json::{Map, Value as Json};
+use subxt::ext::scale_value::{BitSequence, Composite, Primitive, Value, ValueDef};
+
+/// Convert some JSON into a [`Value`] of the type with the given ID.
+pub(crate) fn json_to_value(
+    json: Json,
+    type_id: u32,
+    types: &PortableRegistry,
+) -> Result<Value, FfiError> {
+    let ty = resolve(type_id, types)?;
+    let value = match &ty.type_def {
+        TypeDef::Composite(composite) => {
+            let fields: Vec<_> = composite
+                .fields
+                .iter()
+                .map(|f| (f.name.as_deref(), f.ty.id))
+                .collect();
+            let values = match fields.as_slice() {
+                // Types wrapping a single value, like account IDs, can be given as that value.
+                [(name, ty)] if !is_single_field(&json, *name) => {
+                    let value = json_to_value(json, *ty, types)?;
+                    match name {
+                        Some(name) => Composite::Named(vec![((*name).to_owned(), value)]),
+                        None => Composite::Unnamed(vec![value]),
+                    }
+                }
+                _ => json_to_fields(json, fields, types)?,
+            };
+            composite_value(values)
+        }
+        TypeDef::Variant(variant) => {
+            let (name, values) = match json {
+                // Variants without fields can be given as just their name.
+                Json::String(name) => (name, Json::Null),
+                Json::Object(mut map) => match (map.remove("name"), map.remove("values")) {
+                    (Some(Json::String(name)), values) if map.is_empty() => {
+                        (name, values.unwrap_or_default())
+                    }
+                    _ => return Err(expected_variant()),
+                },
+                _ => return Err(expected_variant()),
+            };
+            let Some(var) = variant.variants.iter().find(|v| v.name == name) else {
+                return Err(FfiError::invalid_argument(format!(
+                    "{name} is not one of the variants of {}",
+                    type_name(ty)
+                )));
+            };
+            let fields = var.fields.iter().map(|f| (f.name.as_deref(), f.ty.id));
+            Value::variant(name, json_to_fields(values, fields.collect(), types)?)
+        }
+        TypeDef::Sequence(seq) => json_to_items(json, seq.type_param.id, types)?,
+        TypeDef::Array(arr) => json_to_items(json, arr.type_param.id, types)?,
+        TypeDef::Tuple(tuple) => {
+            let fields = tuple.fields.iter().map(|f| (None, f.id)).collect();
+            composite_value(json_to_fields(json, fields, types)?)
+        }
+        TypeDef::Primitive(primitive) => json_to_primitive(json, primitive)?,
+        TypeDef::Compact(compact) => json_to_value(json, compact.type_param.id, types)?,
+        TypeDef::BitSequence(_) => match json {
+            Json::Array(items) => {
+                let bits = items
+                    .into_iter()
+                    .map(|item| item.as_bool().ok_or_else(|| expected("a boolean", &item)))
+                    .collect::<Result<BitSequence, _>>()?;
+                Value::bit_sequence(bits)
+            }
+            json => return Err(expected("an array of booleans", &json)),
+        },
+    };
+    Ok(value)
+}
+
+/// Convert some JSON into a [`Composite`] with the given fields, for instance to use as call
+/// arguments. Arrays give the fields in order, objects give them by name, and `null` is
+/// accepted when there are no fields.
+pub(crate) fn json_to_fields(
+    json: Json,
+    fields: Vec<(Option<&str>, u32)>,
+    types: &PortableRegistry,
+) -> Result<Composite<()>, FfiError> {
+    let values = match json {
+        Json::Null if fields.is_empty() => Vec::new(),
+        Json::Array(items) if items.len() == fields.len() => items
+            .into_iter()
+            .zip(&fields)
+            .map(|(item, (_, ty))| json_to_value(item, *ty, types))
+            .collect::<Result<_, _>>()?,
+        Json::Object(mut map) if fields.iter().all(|(name, _)| name.is_some()) => {
+            let values = fields
+                .iter()
+                .map(|(name, ty)| {
+                    let name = name.expect("checked that all fields are named above; qed");
+                    let item = map.remove(name).ok_or_else(|| {
+                        FfiError::invalid_argument(format!("the field {name} is missing"))
+                    })?;
+                    json_to_value(item, *ty, types)
+                })
+                .collect::<Result<_, _>>()?;
+            if let Some(name) = map.keys().next() {
+                return Err(FfiError::invalid_argument(format!(
+                    "there is no field called {name}"
+                )));
+            }
+            values
+        }
+        json => {
+            return Err(expected(
+                &format!("an array or object of {} fields", fields.len()),
+                &json,
+            ));
+        }
+    };
+
+    let composite = if !fields.is_empty() && fields.iter().all(|(name, _)| name.is_some()) {
+        Composite::Named(
+            fields
+                .iter()
+                .map(|(name, _)| name.unwrap_or_default().to_owned())
+                .zip(values)
+                .collect(),
+        )
+    } else {
+        Composite::Unnamed(values)
+    };
+    Ok(composite)
+}
+
+/// Convert some JSON into a list of [`Value`]s with the given types, for instance to use
+/// as storage keys. Fewer values than types can be given, and `null` is an empty list.
+pub(crate) fn json_to_values(
+    json: Json,
+    type_ids: &[u32],
+    types: &PortableRegistry,
+) -> Result<Vec<Value>, FfiError> {
+    match json {
+        Json::Null => Ok(Vec::new()),
+        Json::Array(items) if items.len() <= type_ids.len() => items
+            .into_iter()
+            .zip(type_ids)
+            .map(|(item, ty)| json_to_value(item, *ty, types))
+            .collect(),
+        json => Err(expected(
+            &format!("an array of at most {} values", type_ids.len()),
+            &json,
+        )),
+    }
+}
+
+fn resolve(type_id: u32, types: &PortableRegistry) -> Result<&Type<PortableForm>, FfiError> {
+    types.resolve(type_id).ok_or_else(|| {
+        FfiError::new(
+            SubxtErrorCode::Metadata,
+            format!("type with ID {type_id} not found"),
+        )
+    })
+}
+
+/// Whether the JSON gives the fields of a type with one field, rather than just its value.
+fn is_single_field(json: &Json, name: Option<&str>) -> bool {
+    match json {
+        Json::Array(items) => items.len() == 1,
+        Json::Object(map) => name.is_some_and(|name| map.len() == 1 && map.contains_key(name)),
+        _ => false,
+    }
+}
+
+/// Convert a hex string (for byte sequences) or an array into a [`Value`].
+fn json_to_items(json: Json, item_ty: u32, types: &PortableRegistry) -> Result<Value, FfiError> {
+    let is_u8 = matches!(
+        resolve(item_ty, types)?.type_def,
+        TypeDef::Primitive(TypeDefPrimitive::U8)
+    );
+    match json {
+        Json::String(s) if is_u8 => Ok(Value::from_bytes(hex_to_bytes(&s)?)),
+        Json::Array(items) => Ok(Value::unnamed_composite(
+            items
+                .into_iter()
+                .map(|item| json_to_value(item, item_ty, types))
+                .collect::<Result<Vec<_>, _>>()?,
+        )),
+        json if is_u8 => Err(expected("a hex string or an array", &json)),
+        json => Err(expected("an array", &json)),
+    }
+}
+
+fn json_to_primitive(json: Json, primitive: &TypeDefPrimitive) -> Result<Value, FfiError> {
+    let value = match (primitive, json) {
+        (TypeDefPrimitive::Bool, Json::Bool(b)) => Value::bool(b),
+        (TypeDefPrimitive::Str, Json::String(s)) => Value::string(s),
+        (TypeDefPrimitive::Char, Json::String(s)) => {
+            let mut chars = s.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Value::char(c),
+                _ => return Err(expected("a single character", &Json::String(s))),
+            }
+        }
+        (
+            TypeDefPrimitive::U8
+            | TypeDefPrimitive::U16
+            | TypeDefPrimitive::U32
+            | TypeDefPrimitive::U64
+            | TypeDefPrimitive::U128,
+            json,
+        ) => {
+            let n = match &json {
+                Json::Number(n) => n.as_u64().map(u128::from),
+                Json::String(s) => s.parse().ok(),
+                _ => None,
+            };
+            Value::u128(n.ok_or_else(|| expected("an unsigned integer", &json))?)
+        }
+        (
+            TypeDefPrimitive::I8
+            | TypeDefPrimitive::I16
+            | TypeDefPrimitive::I32
+            | TypeDefPrimitive::I64
+            | TypeDefPrimitive::I128,
+            json,
+        ) => {
+            let n = match &json {
+                Json::Number(n) => n.as_i64().map(i128::from),
+                Json::String(s) => s.parse().ok(),
+                _ => None,
+            };
+            Value::i128(n.ok_or_else(|| expected("an integer", &json))?)
+        }
+        (TypeDefPrimitive::U256 | TypeDefPrimitive::I256, Json::String(s)) => {
+            let bytes: [u8; 32] = hex_to_bytes(&s)?
+                .try_into()
+                .map_err(|_| FfiError::invalid_argument(format!("{s} is not 32 bytes of hex")))?;
+            Value::primitive(match primitive {
+                TypeDefPrimitive::U256 => Primitive::U256(bytes),
+                _ => Primitive::I256(bytes),
+            })
+        }
+        (primitive, json) => return Err(expected(&format!("a {primitive:?}"), &json)),
+    };
+    Ok(value)
+}
+
+fn hex_to_bytes(s: &str) -> Result<Vec<u8>, FfiError> {
+    s.strip_prefix("0x")
+        .and_then(|h| hex::decode(h).ok())
+        .ok_or_else(|| FfiError::invalid_argument(format!("{s} is not a 0x prefixed hex string")))
+}
+
+fn composite_value(values: Composite<()>) -> Value {
+    Value {
+        value: ValueDef::Composite(values),
+        context: (),
+    }
+}
+
+fn type_name(ty: &Type<PortableForm>) -> String {
+    ty.path.segments.last().cloned().unwrap_or_default()
+}
+
+fn expected_variant() -> FfiError {
+    FfiError::invalid_argument(
+        "expected a variant name or an object like { \"name\": .., \"values\": .. }",
+    )
+}
+
+fn expected(what: &str, json: &Json) -> FfiError {
+    FfiError::invalid_argument(format!("expected {what} but got {json}"))
+}
+
+/// Convert a [`Value`] into JSON.
+pub(crate) fn value_to_json<T>(value: &Value<T>) -> Json {
+    match &value.value {
+        ValueDef::Composite(composite) => composite_to_json(composite),
+        ValueDef::Variant(variant) => {
+            let mut map = Map::new();
+            map.insert("name".to_owned(), Json::String(variant.name.clone()));
+            map.insert("values".to_owned(), composite_to_json(&variant.values));
+            Json::Object(map)
+        }
+        ValueDef::BitSequence(bits) => Json::Array(bits.iter().map(Json::Bool).collect()),
+        ValueDef::Primitive(primitive) => primitive_to_json(primitive),
+    }
+}
+
+/// Convert a [`Composite`] into JSON.
+pub(crate) fn composite_to_json<T>(composite: &Composite<T>) -> Json {
+    match composite {
+        Composite::Named(fields) => Json::Object(
+            fields
+                .iter()
+                .map(|(name, value)| (name.clone(), value_to_json(value)))
+                .collect(),
+        ),
+        Composite::Unnamed(values) => Json::Array(values.iter().map(value_to_json).collect()),
+    }
+}
+
+fn primitive_to_json(primitive: &Primitive) -> Json {
+    match primitive {
+        Primitive::Bool(b) => Json::Bool(*b),
+        Primitive::Char(c) => Json::String(c.to_string()),
+        Primitive::String(s) => Json::String(s.clone()),
+        Primitive::U128(n) => match u64::try_from(*n) {
+            Ok(n) => Json::from(n),
+            Err(_) => Json::String(n.to_string()),
+        },
+        Primitive::I128(n) => match i64::try_from(*n) {
+            Ok(n) => Json::from(n),
+            Err(_) => Json::String(n.to_string()),
+        },
+        Primitive::U256(bytes) | Primitive::I256(bytes) => Json::String(to_hex(bytes)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use scale_info::{Registry, TypeInfo, meta_type};
+    use serde_json::json;
+
+    #[allow(dead_code)]
+    #[derive(TypeInfo)]
+    struct AccountId([u8; 32]);
+
+    #[allow(dead_code)]
+    #[derive(TypeInfo)]
+    struct Foo {
+        a: u32,
+        b: bool,
+        c: String,
+    }
+
+    #[allow(dead_code)]
+    #[derive(TypeInfo)]
+    enum Call {
+        Transfer { dest: AccountId, value: u128 },
+        Remark(Vec<u8>),
+        Nothing,
+    }
+
+    fn convert<T: TypeInfo + 'static>(json: Json) -> Result<Value, FfiError> {
+        let mut registry = Registry::new();
+        let id = registry.register_type(&meta_type::<T>()).id;
+        json_to_value(json, id, &registry.into())
+    }
+
+    fn roundtrip<T: TypeInfo + 'static>(json: Json) {
+        assert_eq!(value_to_json(&convert::<T>(json.clone()).unwrap()), json);
+    }
+
+    #[test]
+    fn values_roundtrip() {
+        roundtrip::<bool>(json!(true));
+        roundtrip::<String>(json!("hello"));
+        roundtrip::<u32>(json!(12345));
+        roundtrip::<i64>(json!(-12345));
+        roundtrip::<u128>(json!("340282366920938463463374607431768211455"));
+        roundtrip::<i128>(json!("-170141183460469231731687303715884105728"));
+        roundtrip::<Vec<Vec<u32>>>(json!([[1, 2], [3, 4]]));
+        roundtrip::<(u8, bool)>(json!([1, true]));
+        roundtrip::<()>(json!([]));
+        roundtrip::<Foo>(json!({ "a": 1, "b": true, "c": "hi" }));
+        roundtrip::<Option<u32>>(json!({ "name": "Some", "values": [1] }));
+        roundtrip::<Call>(
+            json!({ "name": "Transfer", "values": { "dest": [vec![1; 32]], "value": 5 } }),
+        );
+    }
+
+    #[test]
+    fn strings_are_converted_according_to_the_type() {
+        assert_eq!(
+            convert::<Vec<u8>>(json!("0x0102")).unwrap(),
+            Value::from_bytes([1u8, 2])
+        );
+        assert_eq!(
+            convert::<String>(json!("0x0102")).unwrap(),
+            Value::string("0x0102")
+        );
+        assert_eq!(convert::<u128>(json!("1000")).unwrap(), Value::u128(1000));
+        assert_eq!(
+            convert::<String>(json!("1000")).unwrap(),
+            Value::string("1000")
+        );
+        assert_eq!(convert::<i32>(json!("-1")).unwrap(), Value::i128(-1));
+        assert_eq!(convert::<char>(json!("a")).unwrap(), Value::char('a'));
+        assert!(convert::<Vec<u8>>(json!("0xnothex")).is_err());
+        assert!(convert::<u32>(json!("one")).is_err());
+    }
+
+    #[test]
+    fn single_field_types_can_be_given_as_their_value() {
+        let account = format!("0x{}", "01".repeat(32));
+        assert_eq!(
+            convert::<AccountId>(json!(account)).unwrap(),
+            Value::unnamed_composite([Value::from_bytes([1u8; 32])])
+        );
+        assert_eq!(
+            convert::<AccountId>(json!([account])).unwrap(),
+            Value::unnamed_composite([Value::from_bytes([1u8; 32])])
+        );
+    }
+
+    #[test]
+    fn variants_are_looked_up_by_name() {
+        assert_eq!(
+            convert::<Call>(json!("Nothing")).unwrap(),
+            Value::variant("Nothing", Composite::Unnamed(vec![]))
+        );
+        assert_eq!(
+            convert::<Call>(json!({ "name": "Remark", "values": ["0x01"] })).unwrap(),
+            Value::unnamed_variant("Remark", [Value::from_bytes([1u8])])
+        );
+        assert!(convert::<Call>(json!("Unknown")).is_err());
+        assert!(convert::<Call>(json!({ "name": "Remark", "values": [], "other": 1 })).is_err());
+    }
+
+    #[test]
+    fn invalid_json_values_are_rejected() {
+        assert!(convert::<u32>(json!(1.5)).is_err());
+        assert!(convert::<u8>(json!(-1)).is_err());
+        assert!(convert::<Foo>(json!({ "a": 1, "b": true })).is_err());
+        assert!(convert::<Foo>(json!({ "a": 1, "b": true, "c": "", "d": 1 })).is_err());
+        assert!(convert::<(u8, bool)>(json!([1])).is_err());
+        assert!(convert::<bool>(json!(null)).is_err());
+    }
+
+    #[test]
+    fn fewer_values_than_types_can_be_given() {
+        let mut registry = Registry::new();
+        let id = registry.register_type(&meta_type::<u32>()).id;
+        let types = registry.into();
+
+        assert_eq!(
+            json_to_values(json!(null), &[id, id], &types).unwrap(),
+            vec![]
+        );
+        assert_eq!(
+            json_to_values(json!([1]), &[id, id], &types).unwrap(),
+            vec![Value::u128(1)]
+        );
+        assert!(json_to_values(json!([1, 2, 3]), &[id, id], &types).is_err());
+        assert!(json_to_values(json!({ "a": 1 }), &[id, id], &types).is_err());
+    }
+}
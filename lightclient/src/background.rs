@@ -4,11 +4,18 @@
 
 use crate::rpc::RpcResponse;
 use crate::shared_client::SharedClient;
-use crate::{JsonRpcError, LightClientRpcError};
+use crate::{JsonRpcError, LightClientError, LightClientRpcError};
 use futures::{FutureExt, stream::StreamExt};
 use serde_json::value::RawValue;
 use smoldot_light::platform::PlatformRef;
-use std::{collections::HashMap, str::FromStr};
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
 use tokio::sync::{mpsc, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
@@ -29,10 +36,22 @@ pub type SubscriptionResponse = Result<
 /// Type of subscription IDs we can get back.
 pub type SubscriptionId = String;
 
-/// Message protocol between the front-end client that submits the RPC requests
-/// and the background task which fetches responses from Smoldot. Hidden behind
+/// Messages sent from the front-end client to the background task. Hidden behind
 /// the [`BackgroundTaskHandle`].
 #[derive(Debug)]
+enum FrontMessage {
+    /// An RPC request or subscription to forward to Smoldot.
+    Rpc(Message),
+    /// Remove the chain from the light client and stop the background task.
+    RemoveChain {
+        /// Channel used to acknowledge that the chain was removed.
+        sender: oneshot::Sender<()>,
+    },
+}
+
+/// Message protocol between the front-end client that submits the RPC requests
+/// and the background task which fetches responses from Smoldot.
+#[derive(Debug)]
 enum Message {
     /// The RPC method request.
     Request {
@@ -59,7 +78,9 @@ enum Message {
 /// A handle to communicate with the background task.
 #[derive(Clone, Debug)]
 pub struct BackgroundTaskHandle {
-    to_backend: mpsc::UnboundedSender<Message>,
+    to_backend: mpsc::UnboundedSender<FrontMessage>,
+    /// Set by the background task once the chain has been removed.
+    removed: Arc<AtomicBool>,
 }
 
 impl BackgroundTaskHandle {
@@ -67,11 +88,11 @@ impl BackgroundTaskHandle {
     pub async fn request(&self, method: String, params: Option<Box<RawValue>>) -> MethodResponse {
         let (tx, rx) = oneshot::channel();
         self.to_backend
-            .send(Message::Request {
+            .send(FrontMessage::Rpc(Message::Request {
                 method,
                 params,
                 sender: tx,
-            })
+            }))
             .map_err(|_e| LightClientRpcError::BackgroundTaskDropped)?;
 
         match rx.await {
@@ -89,12 +110,12 @@ impl BackgroundTaskHandle {
     ) -> SubscriptionResponse {
         let (tx, rx) = oneshot::channel();
         self.to_backend
-            .send(Message::Subscription {
+            .send(FrontMessage::Rpc(Message::Subscription {
                 method,
                 params,
                 unsubscribe_method,
                 sender: tx,
-            })
+            }))
             .map_err(|_e| LightClientRpcError::BackgroundTaskDropped)?;
 
         match rx.await {
@@ -102,6 +123,80 @@ impl BackgroundTaskHandle {
             Ok(response) => response,
         }
     }
+
+    /// Remove the chain from the light client. Once this completes, the background
+    /// task will have stopped and any further requests will fail.
+    pub async fn remove_chain(&self) -> Result<(), LightClientError> {
+        let (tx, rx) = oneshot::channel();
+        let sent = self
+            .to_backend
+            .send(FrontMessage::RemoveChain { sender: tx })
+            .is_ok();
+
+        if sent && rx.await.is_ok() {
+            return Ok(());
+        }
+
+        // The background task has stopped, either because a previous call removed
+        // the chain or because it was dropped for some other reason.
+        if self.removed.load(Ordering::Acquire) {
+            Err(LightClientError::ChainAlreadyRemoved)
+        } else {
+            Err(LightClientError::BackgroundTaskDropped)
+        }
+    }
+}
+
+#[cfg(test)]
+impl BackgroundTaskHandle {
+    /// Create a handle whose RPC requests are answered by the given function rather
+    /// than by smoldot. Subscriptions are rejected.
+    pub(crate) fn mock(
+        respond: impl Fn(&str, Option<&RawValue>) -> MethodResponse + Send + 'static,
+    ) -> Self {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let removed = Arc::new(AtomicBool::new(false));
+        let removed2 = removed.clone();
+
+        tokio::spawn(async move {
+            while let Some(message) = rx.recv().await {
+                match message {
+                    FrontMessage::Rpc(Message::Request {
+                        method,
+                        params,
+                        sender,
+                    }) => {
+                        let _ = sender.send(respond(&method, params.as_deref()));
+                    }
+                    FrontMessage::Rpc(Message::Subscription { sender, .. }) => {
+                        let _ = sender.send(Err(LightClientRpcError::SmoldotError(
+                            "Subscriptions are not supported by the mock".into(),
+                        )));
+                    }
+                    FrontMessage::RemoveChain { sender } => {
+                        removed2.store(true, Ordering::Release);
+                        let _ = sender.send(());
+                        break;
+                    }
+                }
+            }
+        });
+
+        BackgroundTaskHandle {
+            to_backend: tx,
+            removed,
+        }
+    }
+
+    /// Create a handle whose background task has already stopped without the chain
+    /// being removed.
+    pub(crate) fn dropped() -> Self {
+        let (tx, _) = mpsc::unbounded_channel();
+        BackgroundTaskHandle {
+            to_backend: tx,
+            removed: Arc::new(AtomicBool::new(false)),
+        }
+    }
 }
 
 /// A background task which runs with [`BackgroundTask::run()`] and manages messages
@@ -110,6 +205,7 @@ impl BackgroundTaskHandle {
 pub struct BackgroundTask<TPlatform: PlatformRef, TChain> {
     channels: BackgroundTaskChannels<TPlatform>,
     data: BackgroundTaskData<TPlatform, TChain>,
+    removed: Arc<AtomicBool>,
 }
 
 impl<TPlatform: PlatformRef, TChain> BackgroundTask<TPlatform, TChain> {
@@ -120,6 +216,7 @@ impl<TPlatform: PlatformRef, TChain> BackgroundTask<TPlatform, TChain> {
         from_back: smoldot_light::JsonRpcResponses<TPlatform>,
     ) -> (BackgroundTask<TPlatform, TChain>, BackgroundTaskHandle) {
         let (tx, rx) = mpsc::unbounded_channel();
+        let removed = Arc::new(AtomicBool::new(false));
 
         let bg_task = BackgroundTask {
             channels: BackgroundTaskChannels {
@@ -134,9 +231,13 @@ impl<TPlatform: PlatformRef, TChain> BackgroundTask<TPlatform, TChain> {
                 requests: HashMap::new(),
                 subscriptions: HashMap::new(),
             },
+            removed: removed.clone(),
         };
 
-        let bg_handle = BackgroundTaskHandle { to_backend: tx };
+        let bg_handle = BackgroundTaskHandle {
+            to_backend: tx,
+            removed,
+        };
 
         (bg_task, bg_handle)
     }
@@ -148,6 +249,7 @@ impl<TPlatform: PlatformRef, TChain> BackgroundTask<TPlatform, TChain> {
         let chain_id = self.data.chain_id;
         let mut channels = self.channels;
         let mut data = self.data;
+        let removed = self.removed;
 
         loop {
             tokio::pin! {
@@ -168,7 +270,18 @@ impl<TPlatform: PlatformRef, TChain> BackgroundTask<TPlatform, TChain> {
                         message
                     );
 
-                    data.handle_requests(message).await;
+                    match message {
+                        FrontMessage::Rpc(message) => data.handle_requests(message).await,
+                        // Removing the chain is handled here, because we must stop
+                        // sending anything to smoldot for this chain afterwards.
+                        FrontMessage::RemoveChain { sender } => {
+                            data.client.remove_chain(chain_id);
+                            tracing::debug!(target: LOG_TARGET, "Removed chain {chain_id:?}");
+                            removed.store(true, Ordering::Release);
+                            let _ = sender.send(());
+                            break;
+                        }
+                    }
                 },
                 // Message coming from Smoldot.
                 back_message = from_back_fut => {
@@ -194,7 +307,7 @@ impl<TPlatform: PlatformRef, TChain> BackgroundTask<TPlatform, TChain> {
 
 struct BackgroundTaskChannels<TPlatform: PlatformRef> {
     /// Messages sent into this background task from the front end.
-    from_front: UnboundedReceiverStream<Message>,
+    from_front: UnboundedReceiverStream<FrontMessage>,
     /// Messages sent into the background task from Smoldot.
     from_back: smoldot_light::JsonRpcResponses<TPlatform>,
 }
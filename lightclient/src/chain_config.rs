@@ -4,6 +4,7 @@
 
 use serde_json::Value;
 use std::borrow::Cow;
+use std::num::NonZeroU32;
 
 /// Something went wrong building chain config.
 #[non_exhaustive]
@@ -18,6 +19,12 @@ pub enum ChainConfigError {
 pub struct ChainConfig<'a> {
     // The chain spec to use.
     chain_spec: Cow<'a, str>,
+    // The database content to start from (empty if none).
+    database_content: Cow<'a, str>,
+    // The maximum number of JSON-RPC requests that can be queued.
+    max_pending_requests: NonZeroU32,
+    // The maximum number of active JSON-RPC subscriptions.
+    max_subscriptions: u32,
 }
 
 impl<'a> From<&'a str> for ChainConfig<'a> {
@@ -37,9 +44,37 @@ impl<'a> ChainConfig<'a> {
     pub fn chain_spec(chain_spec: impl Into<Cow<'a, str>>) -> Self {
         ChainConfig {
             chain_spec: chain_spec.into(),
+            database_content: Cow::Borrowed(""),
+            max_pending_requests: NonZeroU32::MAX,
+            max_subscriptions: u32::MAX,
         }
     }
 
+    /// Set the maximum number of JSON-RPC requests that can be queued up for this chain before
+    /// further requests are rejected. Defaults to `u32::MAX`.
+    pub fn max_pending_requests(mut self, max_pending_requests: NonZeroU32) -> Self {
+        self.max_pending_requests = max_pending_requests;
+        self
+    }
+
+    /// Set the maximum number of JSON-RPC subscriptions that can be active on this chain
+    /// at once. Defaults to `u32::MAX`.
+    pub fn max_subscriptions(mut self, max_subscriptions: u32) -> Self {
+        self.max_subscriptions = max_subscriptions;
+        self
+    }
+
+    /// Start the chain from some database content which was previously obtained by calling
+    /// [`crate::LightClient::export_database()`] or [`crate::LightClientRpc::export_database()`].
+    ///
+    /// Restoring from a recent database allows the light client to avoid a full warp sync
+    /// on startup. Invalid or outdated database content is ignored by the light client, which
+    /// will then sync from scratch.
+    pub fn database(mut self, database_content: impl Into<Cow<'a, str>>) -> Self {
+        self.database_content = database_content.into();
+        self
+    }
+
     /// Set the bootnodes to the given ones.
     pub fn set_bootnodes<S: AsRef<str>>(
         self,
@@ -61,6 +96,9 @@ impl<'a> ChainConfig<'a> {
 
         Ok(ChainConfig {
             chain_spec: Cow::Owned(chain_spec_json.to_string()),
+            database_content: self.database_content,
+            max_pending_requests: self.max_pending_requests,
+            max_subscriptions: self.max_subscriptions,
         })
     }
 
@@ -68,4 +106,70 @@ impl<'a> ChainConfig<'a> {
     pub(crate) fn as_chain_spec(&self) -> &str {
         &self.chain_spec
     }
+
+    // Used internally to build the JSON-RPC configuration for the chain.
+    pub(crate) fn as_json_rpc_config(&self) -> smoldot_light::AddChainConfigJsonRpc {
+        smoldot_light::AddChainConfigJsonRpc::Enabled {
+            max_pending_requests: self.max_pending_requests,
+            max_subscriptions: self.max_subscriptions,
+        }
+    }
+
+    // Used internally to fetch the database content back out.
+    pub(crate) fn as_database_content(&self) -> &str {
+        &self.database_content
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Pull the limits back out of the JSON-RPC config handed to smoldot.
+    fn json_rpc_limits(config: &ChainConfig<'_>) -> (NonZeroU32, u32) {
+        match config.as_json_rpc_config() {
+            smoldot_light::AddChainConfigJsonRpc::Enabled {
+                max_pending_requests,
+                max_subscriptions,
+            } => (max_pending_requests, max_subscriptions),
+            smoldot_light::AddChainConfigJsonRpc::Disabled => panic!("JSON-RPC should be enabled"),
+        }
+    }
+
+    #[test]
+    fn json_rpc_limits_default_to_max() {
+        let config = ChainConfig::chain_spec("{}");
+        assert_eq!(json_rpc_limits(&config), (NonZeroU32::MAX, u32::MAX));
+    }
+
+    #[test]
+    fn json_rpc_limits_are_passed_to_smoldot() {
+        let config = ChainConfig::chain_spec("{}")
+            .max_pending_requests(NonZeroU32::new(16).unwrap())
+            .max_subscriptions(4);
+        assert_eq!(json_rpc_limits(&config), (NonZeroU32::new(16).unwrap(), 4));
+    }
+
+    #[test]
+    fn set_bootnodes_keeps_limits_and_database() {
+        let config = ChainConfig::chain_spec(r#"{"name":"test","bootNodes":["a"]}"#)
+            .max_pending_requests(NonZeroU32::new(16).unwrap())
+            .max_subscriptions(4)
+            .database("db")
+            .set_bootnodes(["b", "c"])
+            .unwrap();
+
+        assert_eq!(json_rpc_limits(&config), (NonZeroU32::new(16).unwrap(), 4));
+        assert_eq!(config.as_database_content(), "db");
+
+        let spec: Value = serde_json::from_str(config.as_chain_spec()).unwrap();
+        assert_eq!(spec["bootNodes"], serde_json::json!(["b", "c"]));
+        assert_eq!(spec["name"], "test");
+    }
+
+    #[test]
+    fn set_bootnodes_rejects_invalid_chain_specs() {
+        let err = ChainConfig::chain_spec("[]").set_bootnodes(["a"]);
+        assert!(matches!(err, Err(ChainConfigError::InvalidSpecFormat)));
+    }
 }
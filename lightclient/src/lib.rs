@@ -32,14 +32,25 @@ use tokio::sync::mpsc;
 pub use chain_config::{ChainConfig, ChainConfigError};
 
 /// Things that can go wrong when constructing the [`LightClient`].
+#[non_exhaustive]
 #[derive(Debug, thiserror::Error)]
 pub enum LightClientError {
     /// Error encountered while adding the chain to the light-client.
     #[error("Failed to add the chain to the light client: {0}.")]
     AddChainError(String),
+    /// The relay chain cannot be removed while the [`LightClient`] is still in use.
+    #[error("The relay chain cannot be removed from the light client.")]
+    CannotRemoveRelayChain,
+    /// The chain has already been removed from the light client.
+    #[error("The chain has already been removed from the light client.")]
+    ChainAlreadyRemoved,
+    /// The background task for the chain stopped without the chain being removed.
+    #[error("The background task was dropped.")]
+    BackgroundTaskDropped,
 }
 
 /// Things that can go wrong calling methods of [`LightClientRpc`].
+#[non_exhaustive]
 #[derive(Debug, thiserror::Error)]
 pub enum LightClientRpcError {
     /// Error response from the JSON-RPC server.
@@ -51,6 +62,9 @@ pub enum LightClientRpcError {
     /// Background task dropped.
     #[error("The background task was dropped.")]
     BackgroundTaskDropped,
+    /// The response from smoldot could not be deserialized.
+    #[error("Cannot deserialize the response from smoldot: {0}.")]
+    DeserializationError(serde_json::Error),
 }
 
 /// An error response from the JSON-RPC server (ie smoldot) in response to
@@ -71,10 +85,23 @@ impl JsonRpcError {
 /// This represents a single light client connection to the network. Instantiate
 /// it with [`LightClient::relay_chain()`] to communicate with a relay chain, and
 /// then call [`LightClient::parachain()`] to establish connections to parachains.
+///
+/// # Peers
+///
+/// The initial peers of a chain are the bootnodes in its chain spec, which can be replaced with
+/// [`ChainConfig::set_bootnodes()`] before the chain is added. Further peers can be added at
+/// runtime with [`LightClient::add_peer()`] or [`LightClientRpc::add_peer()`]. The JSON-RPC
+/// limits of each chain are set with [`ChainConfig::max_pending_requests()`] and
+/// [`ChainConfig::max_subscriptions()`].
+///
+/// Smoldot does not support removing peers, or connecting _only_ to the given bootnodes; peers
+/// discovered from the network are always used too. Neither is offered here as a result, so to
+/// talk only to a specific node, that node should be the only one reachable on its network.
 #[derive(Clone)]
 pub struct LightClient {
     client: SharedClient<DefaultPlatform>,
     relay_chain_id: smoldot_light::ChainId,
+    relay_chain_rpc: LightClientRpc,
 }
 
 impl LightClient {
@@ -102,11 +129,8 @@ impl LightClient {
 
         let config = smoldot_light::AddChainConfig {
             specification: chain_spec,
-            json_rpc: smoldot_light::AddChainConfigJsonRpc::Enabled {
-                max_pending_requests: u32::MAX.try_into().unwrap(),
-                max_subscriptions: u32::MAX,
-            },
-            database_content: "",
+            json_rpc: chain_config.as_json_rpc_config(),
+            database_content: chain_config.as_database_content(),
             potential_relay_chains: std::iter::empty(),
             user_data: (),
         };
@@ -126,6 +150,7 @@ impl LightClient {
         let light_client = Self {
             client: shared_client,
             relay_chain_id,
+            relay_chain_rpc: light_client_rpc.clone(),
         };
 
         Ok((light_client, light_client_rpc))
@@ -154,11 +179,8 @@ impl LightClient {
 
         let config = smoldot_light::AddChainConfig {
             specification: chain_spec,
-            json_rpc: smoldot_light::AddChainConfigJsonRpc::Enabled {
-                max_pending_requests: u32::MAX.try_into().unwrap(),
-                max_subscriptions: u32::MAX,
-            },
-            database_content: "",
+            json_rpc: chain_config.as_json_rpc_config(),
+            database_content: chain_config.as_database_content(),
             potential_relay_chains: std::iter::once(self.relay_chain_id),
             user_data: (),
         };
@@ -179,6 +201,30 @@ impl LightClient {
             rpc_responses,
         ))
     }
+
+    /// Remove a parachain that was previously added via [`LightClient::parachain()`]. Once
+    /// removed, any requests made via the given [`LightClientRpc`] (or clones of it) will fail.
+    ///
+    /// The relay chain cannot be removed; drop the [`LightClient`] and all associated
+    /// [`LightClientRpc`]s to shut it down instead.
+    pub async fn remove_chain(&self, chain: &LightClientRpc) -> Result<(), LightClientError> {
+        if chain.chain_id == self.relay_chain_id {
+            return Err(LightClientError::CannotRemoveRelayChain);
+        }
+
+        chain.handle.remove_chain().await
+    }
+
+    /// Export the database of the relay chain. See [`LightClientRpc::export_database()`].
+    pub async fn export_database(&self) -> Result<String, LightClientRpcError> {
+        self.relay_chain_rpc.export_database().await
+    }
+
+    /// Ask the light client to connect to the given peer for the relay chain. See
+    /// [`LightClientRpc::add_peer()`].
+    pub async fn add_peer(&self, multiaddr: &str) -> Result<(), LightClientRpcError> {
+        self.relay_chain_rpc.add_peer(multiaddr).await
+    }
 }
 
 /// This represents a single RPC connection to a specific chain, and is constructed by calling
@@ -186,6 +232,7 @@ impl LightClient {
 #[derive(Clone, Debug)]
 pub struct LightClientRpc {
     handle: BackgroundTaskHandle,
+    chain_id: smoldot_light::ChainId,
 }
 
 impl LightClientRpc {
@@ -209,6 +256,7 @@ impl LightClientRpc {
 
         LightClientRpc {
             handle: background_handle,
+            chain_id,
         }
     }
 
@@ -231,6 +279,104 @@ impl LightClientRpc {
         let (id, notifications) = self.handle.subscribe(method, params, unsub).await?;
         Ok(LightClientRpcSubscription { id, notifications })
     }
+
+    /// Export the current state of this chain's database. This is an opaque string which can be
+    /// persisted and then handed back to [`ChainConfig::database()`] when the chain is next added,
+    /// so that the light client can avoid a full warp sync on startup.
+    pub async fn export_database(&self) -> Result<String, LightClientRpcError> {
+        self.request_deserialized("chainHead_unstable_finalizedDatabase", None)
+            .await
+    }
+
+    /// Ask the light client to connect to the given peer for this chain. The address must be
+    /// a multiaddress ending in `/p2p/<peer-id>`, for example
+    /// `/ip4/127.0.0.1/tcp/30333/ws/p2p/12D3KooW...`.
+    ///
+    /// Peers added this way are used in addition to those in the chain spec and any discovered
+    /// from the network, and cannot be removed again (see [the `LightClient` docs](LightClient#peers)).
+    /// To restrict the initial set of peers, use [`ChainConfig::set_bootnodes()`] before adding
+    /// the chain.
+    pub async fn add_peer(&self, multiaddr: &str) -> Result<(), LightClientRpcError> {
+        let params = serde_json::value::to_raw_value(&[multiaddr])
+            .map_err(LightClientRpcError::DeserializationError)?;
+        self.request("sudo_unstable_p2pDiscover".to_owned(), Some(params))
+            .await?;
+        Ok(())
+    }
+
+    /// Report on the health and sync status of this chain.
+    pub async fn status(&self) -> Result<ChainStatus, LightClientRpcError> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct SystemHealth {
+            is_syncing: bool,
+            peers: u64,
+            should_have_peers: bool,
+        }
+
+        let health: SystemHealth = self.request_deserialized("system_health", None).await?;
+        let best_block_number = self.block_number(None).await?;
+        let finalized_hash: String = self
+            .request_deserialized("chain_getFinalizedHead", None)
+            .await?;
+        let finalized_block_number = self.block_number(Some(finalized_hash)).await?;
+
+        Ok(ChainStatus {
+            is_syncing: health.is_syncing,
+            peers: health.peers,
+            should_have_peers: health.should_have_peers,
+            best_block_number,
+            finalized_block_number,
+        })
+    }
+
+    // Fetch the number of the block with the given hash, or of the best block if no hash is given.
+    async fn block_number(&self, hash: Option<String>) -> Result<u64, LightClientRpcError> {
+        #[derive(serde::Deserialize)]
+        struct Header {
+            number: String,
+        }
+
+        let params = match hash {
+            Some(hash) => Some(
+                serde_json::value::to_raw_value(&[hash])
+                    .map_err(LightClientRpcError::DeserializationError)?,
+            ),
+            None => None,
+        };
+        let header: Header = self.request_deserialized("chain_getHeader", params).await?;
+        let number = header.number.trim_start_matches("0x");
+
+        u64::from_str_radix(number, 16).map_err(|e| {
+            LightClientRpcError::SmoldotError(format!("Invalid block number '{number}': {e}"))
+        })
+    }
+
+    // Make an RPC request and deserialize the response into some type.
+    async fn request_deserialized<R: serde::de::DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Option<Box<RawValue>>,
+    ) -> Result<R, LightClientRpcError> {
+        let res = self.request(method.to_owned(), params).await?;
+        serde_json::from_str(res.get()).map_err(LightClientRpcError::DeserializationError)
+    }
+}
+
+/// The health and sync status of a chain, as returned from [`LightClientRpc::status()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainStatus {
+    /// Is the light client still syncing to the head of the chain (for instance
+    /// performing a warp sync)?
+    pub is_syncing: bool,
+    /// The number of peers that we are syncing from.
+    pub peers: u64,
+    /// Should this chain have peers? This is false for chains without any bootnodes.
+    pub should_have_peers: bool,
+    /// The number of the current best block.
+    pub best_block_number: u64,
+    /// The number of the current finalized block.
+    pub finalized_block_number: u64,
 }
 
 /// A stream of notifications handed back when [`LightClientRpc::subscribe`] is called.
@@ -267,3 +413,183 @@ fn spawn<F: Future + Send + 'static>(future: F) {
         future.await;
     });
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // A [`LightClientRpc`] whose requests are answered by the given function.
+    fn mock_rpc(
+        respond: impl Fn(&str, Option<&RawValue>) -> Result<&'static str, LightClientRpcError>
+        + Send
+        + 'static,
+    ) -> LightClientRpc {
+        mock_chain_rpc(0, respond)
+    }
+
+    // A [`LightClientRpc`] for the given chain whose requests are answered by the given function.
+    fn mock_chain_rpc(
+        chain_id: usize,
+        respond: impl Fn(&str, Option<&RawValue>) -> Result<&'static str, LightClientRpcError>
+        + Send
+        + 'static,
+    ) -> LightClientRpc {
+        let handle = BackgroundTaskHandle::mock(move |method, params| {
+            respond(method, params).map(|res| RawValue::from_string(res.to_owned()).unwrap())
+        });
+        LightClientRpc {
+            handle,
+            chain_id: chain_id.into(),
+        }
+    }
+
+    // A [`LightClient`] whose relay chain is chain 0.
+    fn light_client() -> LightClient {
+        let client = smoldot_light::Client::new(platform::build_platform());
+        LightClient {
+            client: client.into(),
+            relay_chain_id: 0.into(),
+            relay_chain_rpc: mock_rpc(|_, _| Ok("null")),
+        }
+    }
+
+    #[tokio::test]
+    async fn export_database_returns_database_content() {
+        let rpc = mock_rpc(|method, params| {
+            assert_eq!(method, "chainHead_unstable_finalizedDatabase");
+            assert!(params.is_none());
+            Ok(r#""some database content""#)
+        });
+
+        let database = rpc.export_database().await.unwrap();
+        assert_eq!(database, "some database content");
+    }
+
+    #[tokio::test]
+    async fn export_database_rejects_non_string_responses() {
+        let rpc = mock_rpc(|_, _| Ok("123"));
+
+        let err = rpc.export_database().await.unwrap_err();
+        assert!(matches!(err, LightClientRpcError::DeserializationError(_)));
+    }
+
+    #[tokio::test]
+    async fn remove_chain_stops_the_chain() {
+        let client = light_client();
+        let parachain = mock_chain_rpc(1, |_, _| Ok("null"));
+
+        client.remove_chain(&parachain).await.unwrap();
+
+        let err = parachain
+            .request("system_health".into(), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, LightClientRpcError::BackgroundTaskDropped));
+
+        let err = client.remove_chain(&parachain).await.unwrap_err();
+        assert!(matches!(err, LightClientError::ChainAlreadyRemoved));
+    }
+
+    #[tokio::test]
+    async fn remove_chain_rejects_the_relay_chain() {
+        let client = light_client();
+        let relay_chain = client.relay_chain_rpc.clone();
+
+        let err = client.remove_chain(&relay_chain).await.unwrap_err();
+        assert!(matches!(err, LightClientError::CannotRemoveRelayChain));
+    }
+
+    #[tokio::test]
+    async fn remove_chain_reports_dropped_background_task() {
+        let client = light_client();
+        let parachain = LightClientRpc {
+            handle: BackgroundTaskHandle::dropped(),
+            chain_id: 1.into(),
+        };
+
+        let err = client.remove_chain(&parachain).await.unwrap_err();
+        assert!(matches!(err, LightClientError::BackgroundTaskDropped));
+    }
+
+    #[tokio::test]
+    async fn status_combines_health_and_block_numbers() {
+        let rpc = mock_rpc(|method, params| {
+            let params = params.map(|p| p.get());
+            match (method, params) {
+                ("system_health", None) => {
+                    Ok(r#"{"isSyncing":false,"peers":5,"shouldHavePeers":true}"#)
+                }
+                ("chain_getFinalizedHead", None) => Ok(r#""0x1234""#),
+                ("chain_getHeader", None) => Ok(r#"{"number":"0x10"}"#),
+                ("chain_getHeader", Some(r#"["0x1234"]"#)) => Ok(r#"{"number":"0xe"}"#),
+                other => panic!("unexpected request {other:?}"),
+            }
+        });
+
+        let status = rpc.status().await.unwrap();
+        assert_eq!(
+            status,
+            ChainStatus {
+                is_syncing: false,
+                peers: 5,
+                should_have_peers: true,
+                best_block_number: 16,
+                finalized_block_number: 14,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn status_rejects_invalid_block_numbers() {
+        let rpc = mock_rpc(|method, _| match method {
+            "system_health" => Ok(r#"{"isSyncing":true,"peers":0,"shouldHavePeers":true}"#),
+            "chain_getHeader" => Ok(r#"{"number":"0xnope"}"#),
+            _ => Ok(r#""0x1234""#),
+        });
+
+        let err = rpc.status().await.unwrap_err();
+        assert!(matches!(err, LightClientRpcError::SmoldotError(_)));
+    }
+
+    #[tokio::test]
+    async fn add_peer_asks_smoldot_to_discover_the_peer() {
+        let rpc = mock_rpc(|method, params| {
+            assert_eq!(method, "sudo_unstable_p2pDiscover");
+            assert_eq!(
+                params.map(|p| p.get()),
+                Some(r#"["/ip4/127.0.0.1/tcp/30333/ws/p2p/12D3KooW"]"#)
+            );
+            Ok("null")
+        });
+
+        rpc.add_peer("/ip4/127.0.0.1/tcp/30333/ws/p2p/12D3KooW")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn add_peer_returns_smoldot_errors() {
+        let rpc = mock_rpc(|_, _| {
+            Err(LightClientRpcError::SmoldotError(
+                "Invalid multiaddress".into(),
+            ))
+        });
+
+        let err = rpc.add_peer("nonsense").await.unwrap_err();
+        assert!(matches!(err, LightClientRpcError::SmoldotError(_)));
+    }
+
+    #[tokio::test]
+    async fn light_client_adds_peers_to_the_relay_chain() {
+        let light_client = LightClient {
+            relay_chain_rpc: mock_rpc(|method, params| {
+                assert_eq!(method, "sudo_unstable_p2pDiscover");
+                assert_eq!(params.map(|p| p.get()), Some(r#"["/dns/node/tcp/30333"]"#));
+                Ok("null")
+            }),
+            ..light_client()
+        };
+
+        light_client.add_peer("/dns/node/tcp/30333").await.unwrap();
+    }
+}
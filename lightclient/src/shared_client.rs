@@ -44,4 +44,12 @@ impl<TPlat: sl::platform::PlatformRef, TChain> SharedClient<TPlat, TChain> {
             .expect("mutex should not be poisoned")
             .add_chain(config)
     }
+
+    /// Delegates to [`smoldot_light::Client::remove_chain()`].
+    pub(crate) fn remove_chain(&self, chain_id: sl::ChainId) -> TChain {
+        self.client
+            .lock()
+            .expect("mutex should not be poisoned")
+            .remove_chain(chain_id)
+    }
 }
@@ -43,6 +43,9 @@ type ArcStr = Arc<str>;
 
 pub use from::SUPPORTED_METADATA_VERSIONS;
 pub use from::TryFromError;
+#[cfg(feature = "merkleized-metadata")]
+pub use utils::merkleized::MerkleizedMetadataError;
+pub use utils::runtime_version::RuntimeVersionPrefix;
 pub use utils::validation::MetadataHasher;
 
 type CustomMetadataInner = frame_metadata::v15::CustomMetadata<PortableForm>;
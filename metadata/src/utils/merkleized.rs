@@ -0,0 +1,332 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Support for the merkleized metadata described in
+//! [RFC-78](https://polkadot-fellows.github.io/RFCs/approved/0078-merkleized-metadata.html),
+//! which is checked by the `CheckMetadataHash` transaction extension, and which offline
+//! signers (for instance hardware wallets) use to decode transactions they are asked to sign.
+
+use crate::{Metadata, RuntimeVersionPrefix};
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use codec::{Decode, Encode};
+use frame_metadata::v15;
+use merkleized_metadata::{ExtraInfo, SignedExtrinsicData};
+use thiserror::Error as DeriveError;
+
+/// An error obtaining a merkleized metadata hash or proof.
+#[derive(Debug, Clone, PartialEq, Eq, DeriveError)]
+#[non_exhaustive]
+pub enum MerkleizedMetadataError {
+    /// A constant needed to compute the metadata hash is missing from the metadata.
+    #[error("The constant {pallet}::{constant} is missing from the metadata")]
+    ConstantNotFound {
+        /// The pallet that the constant should be in.
+        pallet: &'static str,
+        /// The name of the constant.
+        constant: &'static str,
+    },
+    /// A constant needed to compute the metadata hash could not be decoded.
+    #[error("The constant {pallet}::{constant} could not be decoded")]
+    CannotDecodeConstant {
+        /// The pallet that the constant is in.
+        pallet: &'static str,
+        /// The name of the constant.
+        constant: &'static str,
+    },
+    /// Something went wrong merkleizing the metadata or building a proof.
+    #[error("Cannot merkleize metadata: {0}")]
+    Merkleize(String),
+}
+
+impl Metadata {
+    /// Compute the RFC-78 metadata hash for this metadata. This is the hash that the
+    /// `CheckMetadataHash` transaction extension expects in the signer payload when
+    /// metadata checking is enabled.
+    ///
+    /// The spec name, spec version and SS58 prefix are taken from the `System` pallet
+    /// constants. The number of `decimals` and the `token_symbol` of the chain's native
+    /// token are not in the metadata, and so must be provided. They must match the values
+    /// that the runtime was built with for the hash to be accepted.
+    pub fn merkleized_metadata_hash(
+        &self,
+        decimals: u8,
+        token_symbol: &str,
+    ) -> Result<[u8; 32], MerkleizedMetadataError> {
+        let version: RuntimeVersionPrefix = self.system_constant("Version")?;
+        let base58_prefix: u16 = self.system_constant("SS58Prefix")?;
+
+        let extra_info = ExtraInfo {
+            spec_version: version.spec_version,
+            spec_name: version.spec_name,
+            base58_prefix,
+            decimals,
+            token_symbol: token_symbol.to_string(),
+        };
+
+        let digest = merkleized_metadata::generate_metadata_digest(&self.to_v15(), extra_info)
+            .map_err(MerkleizedMetadataError::Merkleize)?;
+        Ok(digest.hash())
+    }
+
+    /// Generate the SCALE encoded RFC-78 proof containing the type information needed to decode
+    /// the given (SCALE encoded, length prefixed) V4 extrinsic. If `additional_signed` is
+    /// provided, the type information needed to decode it is included too.
+    pub fn merkleized_metadata_proof(
+        &self,
+        extrinsic: &[u8],
+        additional_signed: Option<&[u8]>,
+    ) -> Result<Vec<u8>, MerkleizedMetadataError> {
+        let proof = merkleized_metadata::generate_proof_for_extrinsic(
+            extrinsic,
+            additional_signed,
+            &self.to_v15(),
+        )
+        .map_err(MerkleizedMetadataError::Merkleize)?;
+        Ok(proof.encode())
+    }
+
+    /// Generate the SCALE encoded RFC-78 proof containing the type information needed to decode
+    /// a V4 extrinsic built from the given call data and, if it is to be signed, the encoded
+    /// transaction extension values and implicit data. This is useful when handing a transaction
+    /// to an offline signer, since the transaction hasn't yet been signed.
+    pub fn merkleized_metadata_proof_for_parts(
+        &self,
+        call_data: &[u8],
+        transaction_extensions: Option<(&[u8], &[u8])>,
+    ) -> Result<Vec<u8>, MerkleizedMetadataError> {
+        let signed_ext_data = transaction_extensions.map(|(value, implicit)| SignedExtrinsicData {
+            included_in_extrinsic: value,
+            included_in_signed_data: implicit,
+        });
+
+        let proof = merkleized_metadata::generate_proof_for_extrinsic_parts(
+            call_data,
+            signed_ext_data,
+            &self.to_v15(),
+        )
+        .map_err(MerkleizedMetadataError::Merkleize)?;
+        Ok(proof.encode())
+    }
+
+    fn system_constant<T: Decode>(
+        &self,
+        constant: &'static str,
+    ) -> Result<T, MerkleizedMetadataError> {
+        let pallet = "System";
+        let value = self
+            .pallet_by_name(pallet)
+            .and_then(|p| p.constant_by_name(constant))
+            .ok_or(MerkleizedMetadataError::ConstantNotFound { pallet, constant })?
+            .value();
+        T::decode(&mut &*value)
+            .map_err(|_| MerkleizedMetadataError::CannotDecodeConstant { pallet, constant })
+    }
+
+    // Merkleizing metadata only relies on the type registry and the V4 extrinsic details,
+    // so this builds V15 metadata containing just those. Only the address, call and signature
+    // types and the transaction extensions are read from the extrinsic details.
+    //
+    // We don't keep the type IDs of the runtime type or of the tuple of transaction extensions,
+    // which V15 metadata expects in `ty` and `extrinsic.extra_ty`. These aren't read, and so we
+    // fill them with the (valid, but otherwise meaningless here) call type ID.
+    fn to_v15(&self) -> frame_metadata::RuntimeMetadata {
+        let outer_enums = self.outer_enums();
+        let extrinsic = &self.extrinsic;
+
+        // V4 extrinsics always use version 0 of the transaction extensions.
+        let signed_extensions = extrinsic
+            .transaction_extensions_by_version(0)
+            .map(|exts| {
+                exts.map(|e| v15::SignedExtensionMetadata {
+                    identifier: e.identifier().to_string(),
+                    ty: e.extra_ty().into(),
+                    additional_signed: e.additional_ty().into(),
+                })
+                .collect()
+            })
+            .unwrap_or_default();
+
+        frame_metadata::RuntimeMetadata::V15(v15::RuntimeMetadataV15 {
+            types: self.types.clone(),
+            pallets: Vec::new(),
+            extrinsic: v15::ExtrinsicMetadata {
+                version: 4,
+                address_ty: extrinsic.address_ty.into(),
+                call_ty: outer_enums.call_enum_ty().into(),
+                signature_ty: extrinsic.signature_ty.into(),
+                // Not used when merkleizing metadata; see above.
+                extra_ty: outer_enums.call_enum_ty().into(),
+                signed_extensions,
+            },
+            // Not used when merkleizing metadata; see above.
+            ty: outer_enums.call_enum_ty().into(),
+            apis: Vec::new(),
+            outer_enums: v15::OuterEnums {
+                call_enum_ty: outer_enums.call_enum_ty().into(),
+                event_enum_ty: outer_enums.event_enum_ty().into(),
+                error_enum_ty: outer_enums.error_enum_ty().into(),
+            },
+            custom: v15::CustomMetadata {
+                map: BTreeMap::new(),
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load_metadata() -> Metadata {
+        let bytes = include_bytes!("../../../artifacts/polkadot_metadata_full.scale");
+        Metadata::decode(&mut &bytes[..]).expect("valid metadata")
+    }
+
+    #[test]
+    fn merkleized_metadata_hash_matches_original_metadata() {
+        let bytes = include_bytes!("../../../artifacts/polkadot_metadata_full.scale");
+        let original = frame_metadata::RuntimeMetadataPrefixed::decode(&mut &bytes[..]).unwrap();
+        let metadata = load_metadata();
+
+        let version: RuntimeVersionPrefix = metadata.system_constant("Version").unwrap();
+        let extra_info = ExtraInfo {
+            spec_version: version.spec_version,
+            spec_name: version.spec_name,
+            base58_prefix: metadata.system_constant("SS58Prefix").unwrap(),
+            decimals: 10,
+            token_symbol: "DOT".to_string(),
+        };
+
+        // Merkleizing the original V15 metadata gives the same hash as our reconstructed metadata:
+        let expected = merkleized_metadata::generate_metadata_digest(&original.1, extra_info)
+            .unwrap()
+            .hash();
+        assert_eq!(
+            metadata.merkleized_metadata_hash(10, "DOT").unwrap(),
+            expected
+        );
+
+        // The decimals and token symbol are a part of the hash:
+        assert_ne!(
+            metadata.merkleized_metadata_hash(12, "DOT").unwrap(),
+            expected
+        );
+        assert_ne!(
+            metadata.merkleized_metadata_hash(10, "KSM").unwrap(),
+            expected
+        );
+    }
+
+    // System::remark(b"hi").
+    fn remark_call_data(metadata: &Metadata) -> Vec<u8> {
+        let system = metadata.pallet_by_name("System").unwrap();
+        let remark = system.call_variant_by_name("remark").unwrap();
+        let mut call_data = vec![system.index(), remark.index];
+        b"hi".to_vec().encode_to(&mut call_data);
+        call_data
+    }
+
+    #[test]
+    fn merkleized_metadata_proof_for_unsigned_call() {
+        let metadata = load_metadata();
+        let mut call_data = remark_call_data(&metadata);
+
+        let proof = metadata
+            .merkleized_metadata_proof_for_parts(&call_data, None)
+            .unwrap();
+        assert!(!proof.is_empty());
+
+        // Trailing bytes are an error.
+        call_data.push(0);
+        assert!(
+            metadata
+                .merkleized_metadata_proof_for_parts(&call_data, None)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn merkleized_metadata_proof_for_unsigned_extrinsic() {
+        let metadata = load_metadata();
+        let call_data = remark_call_data(&metadata);
+
+        // A V4 unsigned extrinsic is the version byte followed by the call data.
+        let mut extrinsic = vec![4u8];
+        extrinsic.extend(&call_data);
+        let mut extrinsic = extrinsic.encode();
+
+        let proof = metadata
+            .merkleized_metadata_proof(&extrinsic, None)
+            .unwrap();
+        assert_eq!(
+            proof,
+            metadata
+                .merkleized_metadata_proof_for_parts(&call_data, None)
+                .unwrap()
+        );
+
+        // Trailing bytes are an error.
+        extrinsic.push(0);
+        assert!(
+            metadata
+                .merkleized_metadata_proof(&extrinsic, None)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn merkleized_metadata_proof_for_signed_extrinsic() {
+        let metadata = load_metadata();
+        let call_data = remark_call_data(&metadata);
+
+        // The values of each transaction extension: an immortal era, a nonce and tip of 0, and
+        // CheckMetadataHash disabled. The others are empty.
+        let extension_values = [0u8; 4];
+        // The implicit data of each transaction extension: the spec and transaction versions,
+        // the genesis hash, the mortality checkpoint hash and no metadata hash.
+        let mut implicit = Vec::new();
+        1_000_000u32.encode_to(&mut implicit);
+        26u32.encode_to(&mut implicit);
+        [1u8; 32].encode_to(&mut implicit);
+        [1u8; 32].encode_to(&mut implicit);
+        None::<[u8; 32]>.encode_to(&mut implicit);
+
+        // A V4 signed extrinsic is the version byte with the signed bit set, the address
+        // (MultiAddress::Id), the signature (MultiSignature::Sr25519), the transaction extension
+        // values and then the call data.
+        let mut extrinsic = vec![0b1000_0100u8, 0];
+        extrinsic.extend([2u8; 32]);
+        extrinsic.push(1);
+        extrinsic.extend([3u8; 64]);
+        extrinsic.extend(extension_values);
+        extrinsic.extend(&call_data);
+        let extrinsic = extrinsic.encode();
+
+        let proof = metadata
+            .merkleized_metadata_proof(&extrinsic, Some(&implicit))
+            .unwrap();
+
+        // Decoding the signed extrinsic requires more type information than the bare call:
+        let unsigned_proof = metadata
+            .merkleized_metadata_proof_for_parts(&call_data, None)
+            .unwrap();
+        assert!(proof.len() > unsigned_proof.len());
+
+        // But less than a signed extrinsic whose address and signature aren't yet known, since
+        // then the proof must cover every address and signature variant:
+        let parts_proof = metadata
+            .merkleized_metadata_proof_for_parts(&call_data, Some((&extension_values, &implicit)))
+            .unwrap();
+        assert!(proof.len() < parts_proof.len());
+
+        // Truncated implicit data is an error.
+        assert!(
+            metadata
+                .merkleized_metadata_proof(&extrinsic, Some(&implicit[..implicit.len() - 1]))
+                .is_err()
+        );
+    }
+}
@@ -2,6 +2,9 @@
 // This file is dual-licensed as Apache-2.0 or GPL-3.0.
 // see LICENSE for license details.
 
+#[cfg(feature = "merkleized-metadata")]
+pub mod merkleized;
 pub mod ordered_map;
+pub mod runtime_version;
 pub mod validation;
 pub mod variant_index;
@@ -0,0 +1,28 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use codec::Decode;
+
+/// The start of `sp_version::RuntimeVersion`, as returned from the `Core_version` runtime API
+/// and stored in the `System::Version` constant. Any fields after `transaction_version` are
+/// not decoded, since they have changed over time.
+#[derive(Debug, Clone, PartialEq, Eq, Decode)]
+pub struct RuntimeVersionPrefix {
+    /// The name of the runtime.
+    pub spec_name: String,
+    /// The name of the implementation of the runtime.
+    pub impl_name: String,
+    /// The authoring version.
+    pub authoring_version: u32,
+    /// The version of the runtime specification.
+    pub spec_version: u32,
+    /// The version of the implementation of the runtime specification.
+    pub impl_version: u32,
+    /// The runtime APIs, and their versions, that the runtime exposes.
+    pub apis: Vec<([u8; 8], u32)>,
+    /// The version of the transaction format that the runtime accepts.
+    pub transaction_version: u32,
+}
@@ -0,0 +1,99 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Subscribe to blocks and the events in them.
+
+use crate::convert::{composite_to_py, to_hex};
+use crate::error::to_py_err;
+use pyo3::IntoPyObjectExt;
+use pyo3::exceptions::PyStopAsyncIteration;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use scale_info::PortableRegistry;
+use std::sync::Arc;
+use subxt::backend::StreamOfResults;
+use subxt::blocks::Block;
+use subxt::error::Error;
+use subxt::events::{EventDetails, Phase};
+use subxt::{OnlineClient, PolkadotConfig};
+use tokio::sync::Mutex;
+
+type BlockStream = StreamOfResults<Block<PolkadotConfig, OnlineClient<PolkadotConfig>>>;
+
+/// An async iterator over new blocks, returned from `Client.subscribe_finalized_blocks()`
+/// and `Client.subscribe_best_blocks()`.
+///
+/// Each block is a `dict` containing its `number`, its `hash` as a hex string, and its
+/// `events`, in the form returned by `Client.sign_and_submit()`.
+#[pyclass(frozen, module = "subxt_py")]
+pub struct BlockSubscription {
+    stream: Arc<Mutex<BlockStream>>,
+}
+
+impl BlockSubscription {
+    pub fn new(stream: BlockStream) -> Self {
+        BlockSubscription {
+            stream: Arc::new(Mutex::new(stream)),
+        }
+    }
+}
+
+#[pymethods]
+impl BlockSubscription {
+    fn __aiter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    fn __anext__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let stream = self.stream.clone();
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let Some(block) = stream.lock().await.next().await else {
+                return Err(PyStopAsyncIteration::new_err(()));
+            };
+            let block = block.map_err(to_py_err)?;
+            let events = block.events().await.map_err(to_py_err)?;
+            let metadata = events.metadata().clone();
+            let events = events
+                .iter()
+                .collect::<Result<Vec<_>, Error>>()
+                .map_err(to_py_err)?;
+
+            Python::with_gil(|py| {
+                let events = events
+                    .iter()
+                    .map(|event| event_to_py(py, event, metadata.types()))
+                    .collect::<PyResult<Vec<_>>>()?;
+
+                let dict = PyDict::new(py);
+                dict.set_item("number", block.number())?;
+                dict.set_item("hash", to_hex(block.hash()))?;
+                dict.set_item("events", events)?;
+                dict.into_py_any(py)
+            })
+        })
+    }
+}
+
+/// Convert an event into a `dict` containing its `index` in the block, the `pallet` that
+/// emitted it, its `name`, the `extrinsic_index` of the extrinsic which emitted it (or `None`),
+/// and its `fields`.
+pub fn event_to_py(
+    py: Python<'_>,
+    event: &EventDetails<PolkadotConfig>,
+    types: &PortableRegistry,
+) -> PyResult<Py<PyAny>> {
+    let extrinsic_index = match event.phase() {
+        Phase::ApplyExtrinsic(index) => Some(index),
+        Phase::Finalization | Phase::Initialization => None,
+    };
+    let fields = event.field_values().map_err(to_py_err)?;
+
+    let dict = PyDict::new(py);
+    dict.set_item("index", event.index())?;
+    dict.set_item("pallet", event.pallet_name())?;
+    dict.set_item("name", event.variant_name())?;
+    dict.set_item("extrinsic_index", extrinsic_index)?;
+    dict.set_item("fields", composite_to_py(py, &fields, types)?)?;
+    dict.into_py_any(py)
+}
@@ -0,0 +1,357 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! A client connected to a node, which can query metadata and storage, call runtime
+//! APIs, submit transactions and subscribe to blocks.
+
+use crate::blocks::{BlockSubscription, event_to_py};
+use crate::convert::{py_to_composite, py_to_values, to_hex, value_to_py};
+use crate::error::to_py_err;
+use crate::signer::AnySigner;
+use crate::storage::StorageIter;
+use pyo3::IntoPyObjectExt;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict};
+use scale_info::Variant;
+use scale_info::form::PortableForm;
+use subxt::error::{Error, MetadataError};
+use subxt::ext::scale_value::Composite;
+use subxt::storage::Storage;
+use subxt::tx::{SubmittableTransaction, TxProgress};
+use subxt::utils::H256;
+use subxt::{OnlineClient, PolkadotConfig};
+
+/// A client connected to a Substrate based node.
+///
+/// Create one with `await Client.from_url("ws://127.0.0.1:9944")`.
+#[pyclass(frozen, module = "subxt_py")]
+pub struct Client {
+    client: OnlineClient<PolkadotConfig>,
+}
+
+#[pymethods]
+impl Client {
+    /// Connect to the node at the given URL.
+    #[staticmethod]
+    fn from_url<'py>(py: Python<'py>, url: String) -> PyResult<Bound<'py, PyAny>> {
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let client = OnlineClient::<PolkadotConfig>::from_url(url)
+                .await
+                .map_err(to_py_err)?;
+            Ok(Client { client })
+        })
+    }
+
+    /// The genesis hash of the chain, as a hex string.
+    #[getter]
+    fn genesis_hash(&self) -> String {
+        to_hex(self.client.genesis_hash())
+    }
+
+    /// The `spec_version` and `transaction_version` of the runtime, as a `dict`.
+    #[getter]
+    fn runtime_version<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let version = self.client.runtime_version();
+        let dict = PyDict::new(py);
+        dict.set_item("spec_version", version.spec_version)?;
+        dict.set_item("transaction_version", version.transaction_version)?;
+        Ok(dict)
+    }
+
+    /// The pallets in the metadata, as a list of `dict`s like `{"name": "Balances", "index": 5}`.
+    fn pallets<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        let metadata = self.client.metadata();
+        metadata
+            .pallets()
+            .map(|pallet| {
+                let dict = PyDict::new(py);
+                dict.set_item("name", pallet.name())?;
+                dict.set_item("index", pallet.index())?;
+                Ok(dict)
+            })
+            .collect()
+    }
+
+    /// The details of a pallet, as a `dict` containing its `name` and `index`, and the
+    /// names of its `calls`, `storage` entries, `events`, `errors` and `constants`.
+    fn pallet<'py>(&self, py: Python<'py>, name: &str) -> PyResult<Bound<'py, PyDict>> {
+        let metadata = self.client.metadata();
+        let pallet = metadata
+            .pallet_by_name(name)
+            .ok_or_else(|| to_py_err(MetadataError::PalletNameNotFound(name.into())))?;
+
+        let storage: Vec<_> = pallet
+            .storage()
+            .map(|storage| storage.entries().iter().map(|e| e.name()).collect())
+            .unwrap_or_default();
+        let constants: Vec<_> = pallet.constants().map(|c| c.name()).collect();
+        let variant_names = |variants: Option<&[Variant<PortableForm>]>| -> Vec<String> {
+            variants
+                .unwrap_or_default()
+                .iter()
+                .map(|v| v.name.clone())
+                .collect()
+        };
+
+        let dict = PyDict::new(py);
+        dict.set_item("name", pallet.name())?;
+        dict.set_item("index", pallet.index())?;
+        dict.set_item("calls", variant_names(pallet.call_variants()))?;
+        dict.set_item("storage", storage)?;
+        dict.set_item("events", variant_names(pallet.event_variants()))?;
+        dict.set_item("errors", variant_names(pallet.error_variants()))?;
+        dict.set_item("constants", constants)?;
+        Ok(dict)
+    }
+
+    /// The runtime APIs in the metadata, as a list of `dict`s like
+    /// `{"name": "Core", "methods": ["version", "execute_block", "initialize_block"]}`.
+    fn runtime_apis<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        let metadata = self.client.metadata();
+        metadata
+            .runtime_api_traits()
+            .map(|api| {
+                let methods: Vec<_> = api.methods().map(|m| m.name()).collect();
+                let dict = PyDict::new(py);
+                dict.set_item("name", api.name())?;
+                dict.set_item("methods", methods)?;
+                Ok(dict)
+            })
+            .collect()
+    }
+
+    /// Look up the value of a constant.
+    fn constant(&self, py: Python<'_>, pallet: &str, name: &str) -> PyResult<Py<PyAny>> {
+        let address = subxt::dynamic::constant(pallet, name);
+        let value = self
+            .client
+            .constants()
+            .at(&address)
+            .map_err(to_py_err)?
+            .to_value()
+            .map_err(to_py_err)?;
+        value_to_py(py, &value, self.client.metadata().types())
+    }
+
+    /// Fetch a storage entry, returning `None` if there is no value at it. `keys` is a list
+    /// of keys for storage maps, and `at` is the hash of the block to fetch it at, which
+    /// defaults to the latest finalized block.
+    #[pyo3(signature = (pallet, entry, keys = None, at = None))]
+    fn storage_fetch<'py>(
+        &self,
+        py: Python<'py>,
+        pallet: &str,
+        entry: &str,
+        keys: Option<&Bound<'py, PyAny>>,
+        at: Option<&str>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let keys = keys.map(py_to_values).transpose()?.unwrap_or_default();
+        let at = at.map(parse_block_hash).transpose()?;
+        let address = subxt::dynamic::storage(pallet, entry, keys);
+        let client = self.client.clone();
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let storage = storage_at(&client, at).await.map_err(to_py_err)?;
+            let value = storage.fetch(&address).await.map_err(to_py_err)?;
+            let value = value
+                .map(|value| value.to_value())
+                .transpose()
+                .map_err(to_py_err)?;
+
+            Python::with_gil(|py| match value {
+                Some(value) => value_to_py(py, &value, client.metadata().types()),
+                None => Ok(py.None()),
+            })
+        })
+    }
+
+    /// Iterate over the entries in a storage map, returning a [`StorageIter`]. `keys` is a
+    /// (possibly partial) list of keys to iterate under, and `at` is the hash of the block
+    /// to iterate at, which defaults to the latest finalized block.
+    #[pyo3(signature = (pallet, entry, keys = None, at = None))]
+    fn storage_iter<'py>(
+        &self,
+        py: Python<'py>,
+        pallet: &str,
+        entry: &str,
+        keys: Option<&Bound<'py, PyAny>>,
+        at: Option<&str>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let keys = keys.map(py_to_values).transpose()?.unwrap_or_default();
+        let at = at.map(parse_block_hash).transpose()?;
+        let address = subxt::dynamic::storage(pallet, entry, keys);
+        let client = self.client.clone();
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let storage = storage_at(&client, at).await.map_err(to_py_err)?;
+            let stream = storage.iter(address).await.map_err(to_py_err)?;
+            Ok(StorageIter::new(stream, client.metadata()))
+        })
+    }
+
+    /// Call a runtime API method. `args` is a list or `dict` of arguments, and `at` is the
+    /// hash of the block to call it at, which defaults to the latest finalized block.
+    #[pyo3(signature = (trait_name, method, args = None, at = None))]
+    fn runtime_api_call<'py>(
+        &self,
+        py: Python<'py>,
+        trait_name: &str,
+        method: &str,
+        args: Option<&Bound<'py, PyAny>>,
+        at: Option<&str>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let args = args
+            .map(py_to_composite)
+            .transpose()?
+            .unwrap_or_else(|| Composite::Unnamed(Vec::new()));
+        let at = at.map(parse_block_hash).transpose()?;
+        let payload = subxt::dynamic::runtime_api_call(trait_name, method, args);
+        let client = self.client.clone();
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let runtime_api = match at {
+                Some(hash) => client.runtime_api().at(hash),
+                None => client.runtime_api().at_latest().await.map_err(to_py_err)?,
+            };
+            let value = runtime_api.call(payload).await.map_err(to_py_err)?;
+            let value = value.to_value().map_err(to_py_err)?;
+
+            Python::with_gil(|py| value_to_py(py, &value, client.metadata().types()))
+        })
+    }
+
+    /// Sign a transaction without submitting it, returning the encoded transaction as `bytes`.
+    /// `args` is a list or `dict` of call arguments, and `keypair` is an [`Sr25519Keypair`]
+    /// or [`EcdsaKeypair`].
+    fn sign<'py>(
+        &self,
+        py: Python<'py>,
+        pallet: &str,
+        call: &str,
+        args: &Bound<'py, PyAny>,
+        keypair: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let payload = subxt::dynamic::tx(pallet, call, py_to_composite(args)?);
+        let signer = AnySigner::from_py(keypair)?;
+        let client = self.client.clone();
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let tx = client
+                .tx()
+                .create_signed(&payload, &signer, Default::default())
+                .await
+                .map_err(to_py_err)?;
+
+            Python::with_gil(|py| PyBytes::new(py, tx.encoded()).into_py_any(py))
+        })
+    }
+
+    /// Submit a transaction which was previously signed with [`Client::sign`], and wait for
+    /// it to be finalized and succeed. Returns the same as [`Client::sign_and_submit`].
+    fn submit<'py>(&self, py: Python<'py>, tx: Vec<u8>) -> PyResult<Bound<'py, PyAny>> {
+        let tx = SubmittableTransaction::from_bytes(self.client.clone(), tx).map_err(to_py_err)?;
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let progress = tx.submit_and_watch().await.map_err(to_py_err)?;
+            wait_for_success(progress).await
+        })
+    }
+
+    /// Sign and submit a transaction, and wait for it to be finalized and succeed. Returns a
+    /// `dict` containing the `block_hash` it was finalized in, its `extrinsic_hash` and the
+    /// `events` that it emitted.
+    fn sign_and_submit<'py>(
+        &self,
+        py: Python<'py>,
+        pallet: &str,
+        call: &str,
+        args: &Bound<'py, PyAny>,
+        keypair: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let payload = subxt::dynamic::tx(pallet, call, py_to_composite(args)?);
+        let signer = AnySigner::from_py(keypair)?;
+        let client = self.client.clone();
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let progress = client
+                .tx()
+                .sign_and_submit_then_watch_default(&payload, &signer)
+                .await
+                .map_err(to_py_err)?;
+            wait_for_success(progress).await
+        })
+    }
+
+    /// Subscribe to finalized blocks, returning a [`BlockSubscription`].
+    fn subscribe_finalized_blocks<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let client = self.client.clone();
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let stream = client
+                .blocks()
+                .subscribe_finalized()
+                .await
+                .map_err(to_py_err)?;
+            Ok(BlockSubscription::new(stream))
+        })
+    }
+
+    /// Subscribe to best blocks, returning a [`BlockSubscription`]. Best blocks may not be
+    /// finalized, and can be reorganized away.
+    fn subscribe_best_blocks<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let client = self.client.clone();
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let stream = client.blocks().subscribe_best().await.map_err(to_py_err)?;
+            Ok(BlockSubscription::new(stream))
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Client(genesis_hash={})", self.genesis_hash())
+    }
+}
+
+/// Parse a block hash given as a 32 byte hex string.
+fn parse_block_hash(hash: &str) -> PyResult<H256> {
+    hex::decode(hash.strip_prefix("0x").unwrap_or(hash))
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .map(H256)
+        .ok_or_else(|| PyValueError::new_err("block hashes must be 32 byte hex strings"))
+}
+
+async fn storage_at(
+    client: &OnlineClient<PolkadotConfig>,
+    at: Option<H256>,
+) -> Result<Storage<PolkadotConfig, OnlineClient<PolkadotConfig>>, Error> {
+    match at {
+        Some(hash) => Ok(client.storage().at(hash)),
+        None => client.storage().at_latest().await,
+    }
+}
+
+async fn wait_for_success(
+    progress: TxProgress<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+) -> PyResult<Py<PyAny>> {
+    let in_block = progress.wait_for_finalized().await.map_err(to_py_err)?;
+    let events = in_block.wait_for_success().await.map_err(to_py_err)?;
+    let metadata = events.all_events_in_block().metadata().clone();
+    let events = events
+        .iter()
+        .collect::<Result<Vec<_>, Error>>()
+        .map_err(to_py_err)?;
+
+    Python::with_gil(|py| {
+        let events = events
+            .iter()
+            .map(|event| event_to_py(py, event, metadata.types()))
+            .collect::<PyResult<Vec<_>>>()?;
+
+        let dict = PyDict::new(py);
+        dict.set_item("block_hash", to_hex(in_block.block_hash()))?;
+        dict.set_item("extrinsic_hash", to_hex(in_block.extrinsic_hash()))?;
+        dict.set_item("events", events)?;
+        dict.into_py_any(py)
+    })
+}
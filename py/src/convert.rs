@@ -0,0 +1,403 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Convert between Python objects and [`Value`]s.
+//!
+//! Python objects are converted into values as follows:
+//!
+//! - `None` is the unit value `()`.
+//! - `bool`, `int` and `str` are booleans, integers (up to 128 bits) and strings.
+//! - `bytes` are sequences of `u8`s.
+//! - `list`s and `tuple`s are unnamed composites (tuples, tuple structs and sequences), and
+//!   `dict`s with `str` keys are named composites (structs).
+//! - [`Variant`]s are enum variants.
+//!
+//! Decoded values are converted back in the same way. Named composites become `dict`s, unnamed
+//! composites become `list`s, and sequences or arrays of `u8`s become `bytes`. Integers bigger
+//! than 128 bits become `int`s too.
+
+use pyo3::IntoPyObjectExt;
+use pyo3::exceptions::{PyOverflowError, PyTypeError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyBytes, PyDict, PyInt, PyList, PyString, PyTuple};
+use scale_info::{PortableRegistry, TypeDef, TypeDefPrimitive};
+use subxt::ext::scale_value::{Composite, Primitive, Value, ValueDef};
+
+/// An enum variant, with a `name` and some `values`. The values are a `list` of unnamed
+/// fields or a `dict` of named ones.
+#[pyclass(frozen, module = "subxt_py")]
+pub struct Variant {
+    /// The name of the variant.
+    #[pyo3(get)]
+    name: String,
+    /// The fields of the variant.
+    #[pyo3(get)]
+    values: Py<PyAny>,
+}
+
+#[pymethods]
+impl Variant {
+    #[new]
+    #[pyo3(signature = (name, values = None))]
+    fn new(py: Python<'_>, name: String, values: Option<Py<PyAny>>) -> PyResult<Self> {
+        let values = match values {
+            Some(values) => values,
+            None => PyList::empty(py).into_any().unbind(),
+        };
+        Ok(Variant { name, values })
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        Ok(format!(
+            "Variant({:?}, {})",
+            self.name,
+            self.values.bind(py).repr()?
+        ))
+    }
+
+    fn __eq__(&self, py: Python<'_>, other: &Bound<'_, PyAny>) -> PyResult<bool> {
+        let Ok(other) = other.downcast::<Variant>() else {
+            return Ok(false);
+        };
+        let other = other.get();
+        Ok(self.name == other.name && self.values.bind(py).eq(other.values.bind(py))?)
+    }
+}
+
+/// Convert a Python object into a [`Value`].
+pub fn py_to_value(obj: &Bound<'_, PyAny>) -> PyResult<Value> {
+    if obj.is_none() {
+        return Ok(Value::unnamed_composite([]));
+    }
+    // `bool` is a subclass of `int`, so check for it first.
+    if obj.is_instance_of::<PyBool>() {
+        return Ok(Value::bool(obj.extract()?));
+    }
+    if obj.is_instance_of::<PyInt>() {
+        if let Ok(n) = obj.extract::<u128>() {
+            return Ok(Value::u128(n));
+        }
+        if let Ok(n) = obj.extract::<i128>() {
+            return Ok(Value::i128(n));
+        }
+        return Err(PyOverflowError::new_err(
+            "integers must fit into 128 bits to be converted into values",
+        ));
+    }
+    if obj.is_instance_of::<PyString>() {
+        return Ok(Value::string(obj.extract::<String>()?));
+    }
+    if let Ok(bytes) = obj.downcast::<PyBytes>() {
+        return Ok(Value::from_bytes(bytes.as_bytes()));
+    }
+    if let Ok(variant) = obj.downcast::<Variant>() {
+        let variant = variant.get();
+        let values = py_to_composite(variant.values.bind(obj.py()))?;
+        return Ok(Value::variant(variant.name.clone(), values));
+    }
+    if obj.is_instance_of::<PyDict>()
+        || obj.is_instance_of::<PyList>()
+        || obj.is_instance_of::<PyTuple>()
+    {
+        let composite = py_to_composite(obj)?;
+        return Ok(Value {
+            value: ValueDef::Composite(composite),
+            context: (),
+        });
+    }
+    Err(PyTypeError::new_err(format!(
+        "cannot convert {} into a value",
+        obj.get_type().name()?
+    )))
+}
+
+/// Convert a Python object into a [`Composite`], for instance to use as call arguments.
+/// `dict`s become named composites, `list`s and `tuple`s become unnamed ones, and `None`
+/// is an empty composite.
+pub fn py_to_composite(obj: &Bound<'_, PyAny>) -> PyResult<Composite<()>> {
+    if obj.is_none() {
+        return Ok(Composite::Unnamed(Vec::new()));
+    }
+    if let Ok(dict) = obj.downcast::<PyDict>() {
+        let fields = dict
+            .iter()
+            .map(|(name, value)| Ok((name.extract::<String>()?, py_to_value(&value)?)))
+            .collect::<PyResult<_>>()?;
+        return Ok(Composite::Named(fields));
+    }
+    if obj.is_instance_of::<PyList>() || obj.is_instance_of::<PyTuple>() {
+        let values = obj
+            .try_iter()?
+            .map(|value| py_to_value(&value?))
+            .collect::<PyResult<_>>()?;
+        return Ok(Composite::Unnamed(values));
+    }
+    Err(PyTypeError::new_err(format!(
+        "expected a dict, list or tuple but got {}",
+        obj.get_type().name()?
+    )))
+}
+
+/// Convert a Python object into a list of [`Value`]s, for instance to use as storage keys.
+/// `None` is an empty list.
+pub fn py_to_values(obj: &Bound<'_, PyAny>) -> PyResult<Vec<Value>> {
+    if obj.is_none() {
+        return Ok(Vec::new());
+    }
+    if obj.is_instance_of::<PyList>() || obj.is_instance_of::<PyTuple>() {
+        return obj.try_iter()?.map(|value| py_to_value(&value?)).collect();
+    }
+    Err(PyTypeError::new_err(format!(
+        "expected a list or tuple but got {}",
+        obj.get_type().name()?
+    )))
+}
+
+/// Something which may know the ID of the type that a [`Value`] was decoded from.
+pub trait TypeIdOf {
+    /// The type ID, if known.
+    fn type_id_of(&self) -> Option<u32>;
+}
+
+impl TypeIdOf for u32 {
+    fn type_id_of(&self) -> Option<u32> {
+        Some(*self)
+    }
+}
+
+impl TypeIdOf for () {
+    fn type_id_of(&self) -> Option<u32> {
+        None
+    }
+}
+
+/// Convert a [`Value`] into a Python object. `types` is used to spot sequences of bytes.
+pub fn value_to_py<T: TypeIdOf>(
+    py: Python<'_>,
+    value: &Value<T>,
+    types: &PortableRegistry,
+) -> PyResult<Py<PyAny>> {
+    match &value.value {
+        ValueDef::Composite(composite) => {
+            let is_bytes = value
+                .context
+                .type_id_of()
+                .is_some_and(|id| is_byte_sequence(types, id));
+            match composite_bytes(composite) {
+                Some(bytes) if is_bytes => PyBytes::new(py, &bytes).into_py_any(py),
+                _ => composite_to_py(py, composite, types),
+            }
+        }
+        ValueDef::Variant(variant) => Variant {
+            name: variant.name.clone(),
+            values: composite_to_py(py, &variant.values, types)?,
+        }
+        .into_py_any(py),
+        ValueDef::BitSequence(bits) => {
+            PyList::new(py, bits.iter().collect::<Vec<bool>>())?.into_py_any(py)
+        }
+        ValueDef::Primitive(primitive) => primitive_to_py(py, primitive),
+    }
+}
+
+/// Convert a [`Composite`] into a Python `dict` or `list`.
+pub fn composite_to_py<T: TypeIdOf>(
+    py: Python<'_>,
+    composite: &Composite<T>,
+    types: &PortableRegistry,
+) -> PyResult<Py<PyAny>> {
+    match composite {
+        Composite::Named(fields) => {
+            let dict = PyDict::new(py);
+            for (name, value) in fields {
+                dict.set_item(name, value_to_py(py, value, types)?)?;
+            }
+            dict.into_py_any(py)
+        }
+        Composite::Unnamed(values) => {
+            let values = values
+                .iter()
+                .map(|value| value_to_py(py, value, types))
+                .collect::<PyResult<Vec<_>>>()?;
+            PyList::new(py, values)?.into_py_any(py)
+        }
+    }
+}
+
+fn primitive_to_py(py: Python<'_>, primitive: &Primitive) -> PyResult<Py<PyAny>> {
+    match primitive {
+        Primitive::Bool(b) => b.into_py_any(py),
+        Primitive::Char(c) => c.into_py_any(py),
+        Primitive::String(s) => s.into_py_any(py),
+        Primitive::U128(n) => n.into_py_any(py),
+        Primitive::I128(n) => n.into_py_any(py),
+        Primitive::U256(bytes) => int_from_le_bytes(py, bytes, false),
+        Primitive::I256(bytes) => int_from_le_bytes(py, bytes, true),
+    }
+}
+
+fn int_from_le_bytes(py: Python<'_>, bytes: &[u8; 32], signed: bool) -> PyResult<Py<PyAny>> {
+    let kwargs = PyDict::new(py);
+    kwargs.set_item("signed", signed)?;
+    py.get_type::<PyInt>()
+        .call_method(
+            "from_bytes",
+            (PyBytes::new(py, bytes), "little"),
+            Some(&kwargs),
+        )?
+        .into_py_any(py)
+}
+
+/// Is the given type a sequence or array of `u8`s?
+fn is_byte_sequence(types: &PortableRegistry, type_id: u32) -> bool {
+    let is_u8 = |id: u32| {
+        matches!(
+            types.resolve(id).map(|ty| &ty.type_def),
+            Some(TypeDef::Primitive(TypeDefPrimitive::U8))
+        )
+    };
+    match types.resolve(type_id).map(|ty| &ty.type_def) {
+        Some(TypeDef::Sequence(seq)) => is_u8(seq.type_param.id),
+        Some(TypeDef::Array(arr)) => is_u8(arr.type_param.id),
+        _ => false,
+    }
+}
+
+/// Return the bytes in an unnamed composite of `u8` sized integers.
+fn composite_bytes<T>(composite: &Composite<T>) -> Option<Vec<u8>> {
+    let Composite::Unnamed(values) = composite else {
+        return None;
+    };
+    values
+        .iter()
+        .map(|value| match &value.value {
+            ValueDef::Primitive(Primitive::U128(n)) => u8::try_from(*n).ok(),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Encode some bytes as a `0x` prefixed hex string.
+pub fn to_hex(bytes: impl AsRef<[u8]>) -> String {
+    format!("0x{}", hex::encode(bytes.as_ref()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use scale_info::{Registry, meta_type};
+    use std::ffi::CString;
+
+    /// Evaluate some Python, which can use [`Variant`], and pass the result to `f`.
+    fn with_eval<R>(code: &str, f: impl FnOnce(&Bound<'_, PyAny>) -> R) -> R {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let globals = PyDict::new(py);
+            globals
+                .set_item("Variant", py.get_type::<Variant>())
+                .unwrap();
+            let code = CString::new(code).unwrap();
+            f(&py.eval(&code, Some(&globals), None).unwrap())
+        })
+    }
+
+    /// Convert some Python into a value and back again.
+    fn roundtrip(code: &str) -> String {
+        with_eval(code, |obj| {
+            let value = py_to_value(obj).unwrap();
+            let back =
+                value_to_py(obj.py(), &value, &PortableRegistry::from(Registry::new())).unwrap();
+            back.bind(obj.py()).repr().unwrap().to_string()
+        })
+    }
+
+    #[test]
+    fn values_roundtrip() {
+        let values = [
+            "True",
+            "'hello'",
+            "12345",
+            "-12345",
+            "340282366920938463463374607431768211455",
+            "-170141183460469231731687303715884105728",
+            "[1, 2, [3, 4]]",
+            "{'a': 1, 'b': {'c': [True]}}",
+            "Variant(\"Some\", [1])",
+            "Variant(\"Foo\", {'a': 1})",
+        ];
+
+        for value in values {
+            assert_eq!(roundtrip(value), value);
+        }
+    }
+
+    #[test]
+    fn other_objects_are_converted() {
+        assert_eq!(roundtrip("None"), "[]");
+        assert_eq!(roundtrip("(1, False)"), "[1, False]");
+        assert_eq!(roundtrip("Variant('None')"), "Variant(\"None\", [])");
+        // Without a type to say otherwise, bytes come back as a list of integers.
+        assert_eq!(roundtrip("b'\\x01\\x02'"), "[1, 2]");
+    }
+
+    #[test]
+    fn byte_sequences_are_returned_as_bytes() {
+        let mut registry = Registry::new();
+        let bytes_id = registry.register_type(&meta_type::<Vec<u8>>()).id;
+        let numbers_id = registry.register_type(&meta_type::<Vec<u32>>()).id;
+        let types: PortableRegistry = registry.into();
+
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let to_py = |id: u32| {
+                let value = Value::from_bytes([1u8, 2]).map_context(|_| id);
+                let obj = value_to_py(py, &value, &types).unwrap();
+                obj.bind(py).repr().unwrap().to_string()
+            };
+            assert_eq!(to_py(bytes_id), "b'\\x01\\x02'");
+            assert_eq!(to_py(numbers_id), "[1, 2]");
+        });
+    }
+
+    #[test]
+    fn big_integers_are_converted() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let mut bytes = [0u8; 32];
+            bytes[16] = 1;
+            let obj = primitive_to_py(py, &Primitive::U256(bytes)).unwrap();
+            assert_eq!(
+                obj.bind(py).repr().unwrap().to_string(),
+                "340282366920938463463374607431768211456"
+            );
+        });
+    }
+
+    #[test]
+    fn invalid_objects_are_rejected() {
+        let error_type = |code: &str, convert: fn(&Bound<'_, PyAny>) -> PyResult<()>| {
+            with_eval(code, |obj| {
+                let err = convert(obj).unwrap_err();
+                err.get_type(obj.py()).name().unwrap().to_string()
+            })
+        };
+
+        assert_eq!(
+            error_type("2**128", |obj| py_to_value(obj).map(drop)),
+            "OverflowError"
+        );
+        assert_eq!(
+            error_type("1.5", |obj| py_to_value(obj).map(drop)),
+            "TypeError"
+        );
+        assert_eq!(
+            error_type("1", |obj| py_to_composite(obj).map(drop)),
+            "TypeError"
+        );
+        assert_eq!(
+            error_type("{'a': 1}", |obj| py_to_values(obj).map(drop)),
+            "TypeError"
+        );
+    }
+}
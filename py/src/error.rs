@@ -0,0 +1,85 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! The Python exceptions raised by this module.
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use subxt::error::Error;
+
+create_exception!(
+    subxt_py,
+    SubxtError,
+    PyException,
+    "The base class for errors raised by subxt_py."
+);
+create_exception!(
+    subxt_py,
+    RpcError,
+    SubxtError,
+    "Something went wrong talking to the node."
+);
+create_exception!(
+    subxt_py,
+    RequestTimeoutError,
+    RpcError,
+    "A request to the node took too long to complete."
+);
+create_exception!(
+    subxt_py,
+    MetadataError,
+    SubxtError,
+    "The thing being asked for could not be found in the metadata."
+);
+create_exception!(
+    subxt_py,
+    EncodeError,
+    SubxtError,
+    "A value could not be encoded into the shape that the node expects."
+);
+create_exception!(
+    subxt_py,
+    DecodeError,
+    SubxtError,
+    "Some bytes handed back from the node could not be decoded."
+);
+create_exception!(
+    subxt_py,
+    TransactionError,
+    SubxtError,
+    "A transaction was invalid, was dropped, or failed to execute."
+);
+
+/// Add the exceptions to the module so that they can be caught from Python.
+pub fn add_exceptions(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+    m.add("SubxtError", py.get_type::<SubxtError>())?;
+    m.add("RpcError", py.get_type::<RpcError>())?;
+    m.add("RequestTimeoutError", py.get_type::<RequestTimeoutError>())?;
+    m.add("MetadataError", py.get_type::<MetadataError>())?;
+    m.add("EncodeError", py.get_type::<EncodeError>())?;
+    m.add("DecodeError", py.get_type::<DecodeError>())?;
+    m.add("TransactionError", py.get_type::<TransactionError>())?;
+    Ok(())
+}
+
+/// Convert a Subxt error into the matching Python exception.
+pub fn to_py_err(err: impl Into<Error>) -> PyErr {
+    let err = err.into();
+    let message = err.to_string();
+    if err.is_request_timeout() {
+        return RequestTimeoutError::new_err(message);
+    }
+    match err {
+        Error::Rpc(_) | Error::Io(_) => RpcError::new_err(message),
+        Error::Metadata(_) | Error::MetadataDecoding(_) => MetadataError::new_err(message),
+        Error::Encode(_) | Error::StorageAddress(_) | Error::Extrinsic(_) => {
+            EncodeError::new_err(message)
+        }
+        Error::Decode(_) | Error::Codec(_) => DecodeError::new_err(message),
+        Error::Runtime(_) | Error::Transaction(_) => TransactionError::new_err(message),
+        _ => SubxtError::new_err(message),
+    }
+}
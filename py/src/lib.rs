@@ -0,0 +1,54 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! # Subxt-py
+//!
+//! Python bindings for the dynamic parts of Subxt, built with [PyO3](https://pyo3.rs) and
+//! [maturin](https://www.maturin.rs). Everything which talks to a node is async, and runs
+//! on a tokio runtime managed by `pyo3-async-runtimes`.
+//!
+//! ```python
+//! import asyncio
+//! from subxt_py import Client, Sr25519Keypair, Variant
+//!
+//! async def main():
+//!     client = await Client.from_url("ws://127.0.0.1:9944")
+//!     alice = Sr25519Keypair.from_uri("//Alice")
+//!     bob = Sr25519Keypair.from_uri("//Bob")
+//!
+//!     account = await client.storage_fetch("System", "Account", [bob.public_key])
+//!     print(account["data"]["free"])
+//!
+//!     outcome = await client.sign_and_submit(
+//!         "Balances", "transfer_keep_alive", [Variant("Id", [bob.public_key]), 10_000], alice
+//!     )
+//!     print(outcome["events"])
+//!
+//! asyncio.run(main())
+//! ```
+//!
+//! Values are converted between Python and `scale_value::Value`s as described in the
+//! `convert` module, so they decode exactly as they would when using Subxt from Rust.
+
+mod blocks;
+mod client;
+mod convert;
+mod error;
+mod signer;
+mod storage;
+
+use pyo3::prelude::*;
+
+/// The `subxt_py` Python module.
+#[pymodule]
+fn subxt_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<client::Client>()?;
+    m.add_class::<storage::StorageIter>()?;
+    m.add_class::<blocks::BlockSubscription>()?;
+    m.add_class::<convert::Variant>()?;
+    m.add_class::<signer::Sr25519Keypair>()?;
+    m.add_class::<signer::EcdsaKeypair>()?;
+    error::add_exceptions(m)?;
+    Ok(())
+}
@@ -0,0 +1,152 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! `sr25519` and `ecdsa` keypairs, which can be used to sign transactions.
+
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use std::str::FromStr;
+use subxt::PolkadotConfig;
+use subxt::tx::Signer;
+use subxt::utils::{AccountId32, MultiSignature};
+use subxt_signer::{SecretUri, bip39::Mnemonic, ecdsa, sr25519};
+
+/// An sr25519 keypair, which most Substrate based chains use by default.
+#[pyclass(frozen, module = "subxt_py")]
+#[derive(Clone)]
+pub struct Sr25519Keypair(sr25519::Keypair);
+
+#[pymethods]
+impl Sr25519Keypair {
+    /// Create a keypair from a secret URI, like `//Alice` or `<mnemonic phrase>//path///password`.
+    #[staticmethod]
+    fn from_uri(uri: &str) -> PyResult<Self> {
+        let keypair = sr25519::Keypair::from_uri(&parse_uri(uri)?)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Sr25519Keypair(keypair))
+    }
+
+    /// Create a keypair from a BIP-39 mnemonic phrase and optional password.
+    #[staticmethod]
+    #[pyo3(signature = (phrase, password = None))]
+    fn from_phrase(phrase: &str, password: Option<&str>) -> PyResult<Self> {
+        let keypair = sr25519::Keypair::from_phrase(&parse_phrase(phrase)?, password)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Sr25519Keypair(keypair))
+    }
+
+    /// The 32 byte public key.
+    #[getter]
+    fn public_key<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.0.public_key().0)
+    }
+
+    /// The SS58 encoded account ID.
+    #[getter]
+    fn account_id(&self) -> String {
+        self.0.public_key().to_account_id().to_string()
+    }
+
+    /// Sign a message, returning the 64 byte signature.
+    fn sign<'py>(&self, py: Python<'py>, message: &[u8]) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.0.sign(message).0)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Sr25519Keypair({})", self.account_id())
+    }
+}
+
+/// An ecdsa keypair.
+#[pyclass(frozen, module = "subxt_py")]
+#[derive(Clone)]
+pub struct EcdsaKeypair(ecdsa::Keypair);
+
+#[pymethods]
+impl EcdsaKeypair {
+    /// Create a keypair from a secret URI, like `//Alice` or `<mnemonic phrase>//path///password`.
+    #[staticmethod]
+    fn from_uri(uri: &str) -> PyResult<Self> {
+        let keypair = ecdsa::Keypair::from_uri(&parse_uri(uri)?)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(EcdsaKeypair(keypair))
+    }
+
+    /// Create a keypair from a BIP-39 mnemonic phrase and optional password.
+    #[staticmethod]
+    #[pyo3(signature = (phrase, password = None))]
+    fn from_phrase(phrase: &str, password: Option<&str>) -> PyResult<Self> {
+        let keypair = ecdsa::Keypair::from_phrase(&parse_phrase(phrase)?, password)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(EcdsaKeypair(keypair))
+    }
+
+    /// The 33 byte compressed public key.
+    #[getter]
+    fn public_key<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.0.public_key().0)
+    }
+
+    /// The SS58 encoded account ID, which is the blake2 hash of the public key.
+    #[getter]
+    fn account_id(&self) -> String {
+        self.0.public_key().to_account_id().to_string()
+    }
+
+    /// Sign a message, returning the 65 byte recoverable signature.
+    fn sign<'py>(&self, py: Python<'py>, message: &[u8]) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.0.sign(message).0)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("EcdsaKeypair({})", self.account_id())
+    }
+}
+
+/// Either kind of keypair, which can be used to sign transactions.
+#[derive(Clone)]
+pub enum AnySigner {
+    Sr25519(sr25519::Keypair),
+    Ecdsa(ecdsa::Keypair),
+}
+
+impl AnySigner {
+    /// Take a signer from a Python [`Sr25519Keypair`] or [`EcdsaKeypair`].
+    pub fn from_py(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+        if let Ok(keypair) = obj.downcast::<Sr25519Keypair>() {
+            return Ok(AnySigner::Sr25519(keypair.get().0.clone()));
+        }
+        if let Ok(keypair) = obj.downcast::<EcdsaKeypair>() {
+            return Ok(AnySigner::Ecdsa(keypair.get().0.clone()));
+        }
+        Err(PyTypeError::new_err(
+            "expected an Sr25519Keypair or EcdsaKeypair to sign with",
+        ))
+    }
+}
+
+impl Signer<PolkadotConfig> for AnySigner {
+    fn account_id(&self) -> AccountId32 {
+        match self {
+            AnySigner::Sr25519(keypair) => keypair.public_key().into(),
+            AnySigner::Ecdsa(keypair) => keypair.public_key().into(),
+        }
+    }
+
+    fn sign(&self, signer_payload: &[u8]) -> MultiSignature {
+        match self {
+            AnySigner::Sr25519(keypair) => keypair.sign(signer_payload).into(),
+            AnySigner::Ecdsa(keypair) => keypair.sign(signer_payload).into(),
+        }
+    }
+}
+
+fn parse_uri(uri: &str) -> PyResult<SecretUri> {
+    SecretUri::from_str(uri).map_err(|e| PyValueError::new_err(format!("invalid secret URI: {e}")))
+}
+
+fn parse_phrase(phrase: &str) -> PyResult<Mnemonic> {
+    Mnemonic::parse(phrase).map_err(|e| PyValueError::new_err(format!("invalid phrase: {e}")))
+}
@@ -0,0 +1,73 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Iterate over storage entries.
+
+use crate::convert::value_to_py;
+use crate::error::to_py_err;
+use pyo3::IntoPyObjectExt;
+use pyo3::exceptions::PyStopAsyncIteration;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict};
+use std::sync::Arc;
+use subxt::Metadata;
+use subxt::backend::StreamOfResults;
+use subxt::dynamic::Value;
+use subxt::storage::{DynamicAddress, StorageKeyValuePair};
+use tokio::sync::Mutex;
+
+type KeyValueStream = StreamOfResults<StorageKeyValuePair<DynamicAddress<Vec<Value>>>>;
+
+/// An async iterator over storage entries, returned from `Client.storage_iter()`.
+///
+/// Each entry is a `dict` containing the storage `key` as `bytes`, the map `keys`
+/// that could be decoded from it, and the `value` of the entry.
+#[pyclass(frozen, module = "subxt_py")]
+pub struct StorageIter {
+    stream: Arc<Mutex<KeyValueStream>>,
+    metadata: Metadata,
+}
+
+impl StorageIter {
+    pub fn new(stream: KeyValueStream, metadata: Metadata) -> Self {
+        StorageIter {
+            stream: Arc::new(Mutex::new(stream)),
+            metadata,
+        }
+    }
+}
+
+#[pymethods]
+impl StorageIter {
+    fn __aiter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    fn __anext__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let stream = self.stream.clone();
+        let metadata = self.metadata.clone();
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let Some(kv) = stream.lock().await.next().await else {
+                return Err(PyStopAsyncIteration::new_err(()));
+            };
+            let kv = kv.map_err(to_py_err)?;
+            let value = kv.value.to_value().map_err(to_py_err)?;
+
+            Python::with_gil(|py| {
+                let types = metadata.types();
+                let keys = kv
+                    .keys
+                    .iter()
+                    .map(|key| value_to_py(py, key, types))
+                    .collect::<PyResult<Vec<_>>>()?;
+
+                let entry = PyDict::new(py);
+                entry.set_item("key", PyBytes::new(py, &kv.key_bytes))?;
+                entry.set_item("keys", keys)?;
+                entry.set_item("value", value_to_py(py, &value, types)?)?;
+                entry.into_py_any(py)
+            })
+        })
+    }
+}
@@ -0,0 +1,59 @@
+# Tests for the parts of subxt_py which don't need a node to talk to.
+# Build the module with `maturin develop` and then run `pytest tests`.
+
+import pytest
+from subxt_py import EcdsaKeypair, Sr25519Keypair, SubxtError, Variant
+
+DEV_PHRASE = "bottom drive obey lake curtain smoke basket hold race lonely fit walk"
+ALICE_ACCOUNT_ID = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+
+
+def test_variants_compare_by_name_and_values():
+    assert Variant("Id", [1]) == Variant("Id", [1])
+    assert Variant("Id", [1]) != Variant("Id", [2])
+    assert Variant("Id", [1]) != Variant("Index", [1])
+    assert Variant("Id", [1]) != "Id"
+
+
+def test_variants_default_to_no_values():
+    variant = Variant("None")
+    assert variant.name == "None"
+    assert variant.values == []
+    assert repr(variant) == 'Variant("None", [])'
+    assert repr(Variant("Foo", {"a": 1})) == "Variant(\"Foo\", {'a': 1})"
+
+
+def test_sr25519_keypairs_from_uris():
+    alice = Sr25519Keypair.from_uri("//Alice")
+    assert alice.account_id == ALICE_ACCOUNT_ID
+    assert len(alice.public_key) == 32
+    assert repr(alice) == f"Sr25519Keypair({ALICE_ACCOUNT_ID})"
+    assert len(alice.sign(b"hello")) == 64
+
+
+def test_keypairs_from_phrases_match_uris():
+    from_phrase = Sr25519Keypair.from_phrase(DEV_PHRASE)
+    from_uri = Sr25519Keypair.from_uri(DEV_PHRASE)
+    assert from_phrase.public_key == from_uri.public_key
+
+    from_phrase = EcdsaKeypair.from_phrase(DEV_PHRASE)
+    from_uri = EcdsaKeypair.from_uri(DEV_PHRASE)
+    assert from_phrase.public_key == from_uri.public_key
+
+
+def test_ecdsa_keypairs_from_uris():
+    alice = EcdsaKeypair.from_uri("//Alice")
+    assert len(alice.public_key) == 33
+    assert len(alice.sign(b"hello")) == 65
+    assert alice.account_id != ALICE_ACCOUNT_ID
+
+
+def test_invalid_secrets_are_rejected():
+    with pytest.raises(ValueError):
+        Sr25519Keypair.from_phrase("not a valid phrase")
+    with pytest.raises(ValueError):
+        EcdsaKeypair.from_uri("0xnothex")
+
+
+def test_errors_are_exported():
+    assert issubclass(SubxtError, Exception)
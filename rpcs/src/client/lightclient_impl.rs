@@ -55,6 +55,8 @@ impl From<LightClientRpcError> for Error {
             },
             LightClientRpcError::SmoldotError(e) => Error::Client(Box::<CoreError>::from(e)),
             LightClientRpcError::BackgroundTaskDropped => Error::Client(Box::<CoreError>::from("Smoldot background task was dropped")),
+            LightClientRpcError::DeserializationError(e) => Error::Deserialization(e),
+            e => Error::Client(Box::<CoreError>::from(e.to_string())),
         }
     }
 }
@@ -0,0 +1,216 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Helpers to record metrics about RPC requests and subscriptions. When the `metrics`
+//! feature is not enabled, these do nothing. The metrics recorded are listed in the
+//! [`crate::client`] docs.
+
+use crate::Error;
+
+/// Record the outcome and duration of an RPC request.
+pub struct RequestMetrics {
+    #[cfg(feature = "metrics")]
+    method: String,
+    #[cfg(feature = "metrics")]
+    started: web_time::Instant,
+}
+
+impl RequestMetrics {
+    /// Start timing a request to the given method.
+    pub fn start(method: &str) -> Self {
+        #[cfg(not(feature = "metrics"))]
+        let _ = method;
+
+        RequestMetrics {
+            #[cfg(feature = "metrics")]
+            method: method.to_owned(),
+            #[cfg(feature = "metrics")]
+            started: web_time::Instant::now(),
+        }
+    }
+
+    /// Record the result of the request.
+    pub fn finish<T>(self, res: &Result<T, Error>) {
+        #[cfg(feature = "metrics")]
+        {
+            let outcome = if res.is_ok() { "ok" } else { "error" };
+            metrics::counter!(
+                "subxt_rpc_requests_total",
+                "method" => self.method.clone(),
+                "outcome" => outcome
+            )
+            .increment(1);
+            metrics::histogram!(
+                "subxt_rpc_request_duration_seconds",
+                "method" => self.method
+            )
+            .record(self.started.elapsed().as_secs_f64());
+        }
+        #[cfg(not(feature = "metrics"))]
+        let _ = res;
+    }
+}
+
+/// Tracks an open subscription. The subscription is counted as active
+/// until this is dropped.
+pub struct SubscriptionMetrics {
+    #[cfg(feature = "metrics")]
+    method: String,
+}
+
+impl SubscriptionMetrics {
+    /// Start tracking a newly opened subscription to the given method.
+    pub fn start(method: &str) -> Self {
+        #[cfg(feature = "metrics")]
+        metrics::gauge!("subxt_rpc_subscriptions_active", "method" => method.to_owned())
+            .increment(1.0);
+        #[cfg(not(feature = "metrics"))]
+        let _ = method;
+
+        SubscriptionMetrics {
+            #[cfg(feature = "metrics")]
+            method: method.to_owned(),
+        }
+    }
+
+    /// Record that an item was received on this subscription.
+    pub fn item_received(&self) {
+        #[cfg(feature = "metrics")]
+        metrics::counter!(
+            "subxt_rpc_subscription_items_total",
+            "method" => self.method.clone()
+        )
+        .increment(1);
+    }
+}
+
+impl Drop for SubscriptionMetrics {
+    fn drop(&mut self) {
+        #[cfg(feature = "metrics")]
+        metrics::gauge!(
+            "subxt_rpc_subscriptions_active",
+            "method" => std::mem::take(&mut self.method)
+        )
+        .decrement(1.0);
+    }
+}
+
+/// Record an attempt to reconnect to an RPC node.
+#[cfg(all(
+    feature = "reconnecting-rpc-client",
+    any(feature = "native", feature = "web")
+))]
+pub fn record_reconnect(succeeded: bool) {
+    #[cfg(feature = "metrics")]
+    {
+        let outcome = if succeeded { "ok" } else { "error" };
+        metrics::counter!("subxt_rpc_reconnects_total", "outcome" => outcome).increment(1);
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = succeeded;
+}
+
+#[cfg(all(test, feature = "metrics"))]
+mod test {
+    use super::*;
+    use metrics::{SharedString, Unit};
+    use metrics_util::CompositeKey;
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder, Snapshotter};
+
+    type Snapshot = Vec<(CompositeKey, Option<Unit>, Option<SharedString>, DebugValue)>;
+
+    // Take a snapshot of the metrics recorded. This drains any histogram values.
+    fn snapshot(snapshotter: &Snapshotter) -> Snapshot {
+        snapshotter.snapshot().into_vec()
+    }
+
+    // Return the value of the metric with the given name and labels.
+    fn value<'a>(
+        snapshot: &'a Snapshot,
+        name: &str,
+        labels: &[(&str, &str)],
+    ) -> Option<&'a DebugValue> {
+        snapshot
+            .iter()
+            .find(|(key, _, _, _)| {
+                let key = key.key();
+                key.name() == name
+                    && key.labels().count() == labels.len()
+                    && labels.iter().all(|(k, v)| {
+                        key.labels()
+                            .any(|label| label.key() == *k && label.value() == *v)
+                    })
+            })
+            .map(|(_, _, _, value)| value)
+    }
+
+    #[test]
+    fn records_requests() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+
+        metrics::with_local_recorder(&recorder, || {
+            RequestMetrics::start("foo").finish(&Ok(()));
+            RequestMetrics::start("foo").finish(&Ok(()));
+            RequestMetrics::start("foo").finish::<()>(&Err(Error::Client("bad".into())));
+        });
+
+        let metrics = snapshot(&snapshotter);
+        let ok = [("method", "foo"), ("outcome", "ok")];
+        let error = [("method", "foo"), ("outcome", "error")];
+        assert_eq!(
+            value(&metrics, "subxt_rpc_requests_total", &ok),
+            Some(&DebugValue::Counter(2))
+        );
+        assert_eq!(
+            value(&metrics, "subxt_rpc_requests_total", &error),
+            Some(&DebugValue::Counter(1))
+        );
+        let Some(DebugValue::Histogram(durations)) = value(
+            &metrics,
+            "subxt_rpc_request_duration_seconds",
+            &[("method", "foo")],
+        ) else {
+            panic!("expected a histogram of request durations");
+        };
+        assert_eq!(durations.len(), 3);
+    }
+
+    #[test]
+    fn records_subscriptions() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let labels = [("method", "foo")];
+        let active = |snapshotter: &Snapshotter| {
+            let metrics = snapshot(snapshotter);
+            match value(&metrics, "subxt_rpc_subscriptions_active", &labels) {
+                Some(DebugValue::Gauge(n)) => n.into_inner(),
+                other => panic!("expected a gauge, got {other:?}"),
+            }
+        };
+
+        metrics::with_local_recorder(&recorder, || {
+            let sub1 = SubscriptionMetrics::start("foo");
+            let sub2 = SubscriptionMetrics::start("foo");
+            assert_eq!(active(&snapshotter), 2.0);
+
+            sub1.item_received();
+            sub2.item_received();
+            sub2.item_received();
+            drop(sub1);
+            assert_eq!(active(&snapshotter), 1.0);
+            drop(sub2);
+        });
+
+        assert_eq!(active(&snapshotter), 0.0);
+        assert_eq!(
+            value(
+                &snapshot(&snapshotter),
+                "subxt_rpc_subscription_items_total",
+                &labels
+            ),
+            Some(&DebugValue::Counter(3))
+        );
+    }
+}
@@ -22,8 +22,31 @@
 //!   the hood to communicate with the network of choice.
 //! - **reconnecting-rpc-client**: Enable an RPC client based on `jsonrpsee` which handles
 //!   reconnecting automatically in the event of network issues.
+//! - **rate-limited-rpc-client**: Enable an RPC client which wraps any other, limiting the rate
+//!   of requests and the number of requests and subscriptions in flight at once.
 //! - **mock-rpc-client**: Enable a mock RPC client that can be used in tests.
 //!
+//! # Observability
+//!
+//! [`RpcClient::request`] and [`RpcClient::subscribe`] each enter a `tracing` span (target
+//! `subxt_rpcs`) recording the method name and, for subscriptions, the ID of the subscription
+//! once it has been established. The parameters are only logged, within the span, at the
+//! `trace` level, since they can be large or contain sensitive data.
+//!
+//! If the **metrics** feature is enabled, the following metrics are also recorded using the
+//! [`metrics`](https://docs.rs/metrics) facade, and can be exported by installing a recorder:
+//!
+//! - `subxt_rpc_requests_total` (counter, labels `method` and `outcome`): the number of
+//!   RPC requests and subscriptions made, where `outcome` is either `ok` or `error`.
+//! - `subxt_rpc_request_duration_seconds` (histogram, label `method`): how long each RPC
+//!   request (or subscription request) took to complete.
+//! - `subxt_rpc_subscriptions_active` (gauge, label `method`): the number of subscriptions
+//!   which are currently open.
+//! - `subxt_rpc_subscription_items_total` (counter, label `method`): the number of items
+//!   received on subscriptions.
+//! - `subxt_rpc_reconnects_total` (counter, label `outcome`): the number of attempts that
+//!   the reconnecting RPC client has made to re-establish a connection.
+//!
 
 crate::macros::cfg_jsonrpsee! {
     mod jsonrpsee_impl;
@@ -40,11 +63,17 @@ crate::macros::cfg_reconnecting_rpc_client! {
    pub use reconnecting_rpc_client::RpcClient as ReconnectingRpcClient;
 }
 
+crate::macros::cfg_rate_limited_rpc_client! {
+    pub mod rate_limited_rpc_client;
+    pub use rate_limited_rpc_client::RateLimitedRpcClient;
+}
+
 crate::macros::cfg_mock_rpc_client! {
     pub mod mock_rpc_client;
     pub use mock_rpc_client::MockRpcClient;
 }
 
+mod metrics;
 mod rpc_client;
 mod rpc_client_t;
 
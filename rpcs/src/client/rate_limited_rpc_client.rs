@@ -0,0 +1,630 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! This module exposes a [`RateLimitedRpcClient`], which wraps any other [`RpcClient`] and
+//! limits the rate at which requests are made, as well as how many requests and subscriptions
+//! can be in flight at once.
+//!
+//! Requests which can't be sent yet wait in one of three queues, depending on their [`Priority`].
+//! Requests are sent in the order that they were made, except that any waiting [`Priority::High`]
+//! request is sent before any [`Priority::Normal`] one, which in turn is sent before any
+//! [`Priority::Low`] one. By default, transaction submission is high priority and bulk storage
+//! reads (like those made when iterating over storage entries) are low priority.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! use std::time::Duration;
+//! use subxt_rpcs::client::{RpcClient, RateLimitedRpcClient};
+//! use subxt_rpcs::client::rate_limited_rpc_client::Priority;
+//!
+//! let client = RpcClient::from_url("wss://rpc.polkadot.io").await?;
+//!
+//! let rate_limited_client = RateLimitedRpcClient::builder()
+//!     // Send at most 20 requests per second:
+//!     .rate_limit(20, Duration::from_secs(1))
+//!     // Have at most 8 requests and 4 subscriptions in flight at once:
+//!     .max_concurrent_requests(8)
+//!     .max_concurrent_subscriptions(4)
+//!     // Let this method jump the queue:
+//!     .method_priority("state_getRuntimeVersion", Priority::High)
+//!     .build(client);
+//!
+//! let rpc_client = RpcClient::new(rate_limited_client);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{RawRpcFuture, RawRpcSubscription, RpcClient, RpcClientT};
+use futures::{FutureExt, StreamExt};
+use futures_timer::Delay;
+use serde_json::value::RawValue;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+use web_time::Instant;
+
+/// The priority of an RPC request or subscription. Waiting requests with a higher
+/// priority are always sent before those with a lower priority.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    /// Sent ahead of everything else. By default, this is used for transaction submission.
+    High,
+    /// The default priority.
+    Normal,
+    /// Sent only when nothing else is waiting. By default, this is used for bulk storage reads.
+    Low,
+}
+
+impl Priority {
+    fn index(self) -> usize {
+        match self {
+            Priority::High => 0,
+            Priority::Normal => 1,
+            Priority::Low => 2,
+        }
+    }
+}
+
+/// Methods which are given [`Priority::High`] by default.
+const HIGH_PRIORITY_METHODS: &[&str] = &[
+    "author_submitExtrinsic",
+    "author_submitAndWatchExtrinsic",
+    "transaction_v1_broadcast",
+    "transactionWatch_v1_submitAndWatch",
+];
+
+/// Methods which are given [`Priority::Low`] by default. These are only used when iterating
+/// over storage entries; methods which are also used to fetch single values are left alone.
+const LOW_PRIORITY_METHODS: &[&str] = &["state_getKeysPaged", "state_queryStorageAt"];
+
+/// Configure and build a [`RateLimitedRpcClient`].
+#[derive(Debug, Clone)]
+pub struct RateLimitedRpcClientBuilder {
+    rate_limit: Option<(u32, Duration)>,
+    max_concurrent_requests: usize,
+    max_concurrent_subscriptions: usize,
+    priorities: HashMap<String, Priority>,
+}
+
+impl Default for RateLimitedRpcClientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RateLimitedRpcClientBuilder {
+    /// Create a new [`RateLimitedRpcClientBuilder`]. By default, no limits are applied.
+    pub fn new() -> Self {
+        let priorities = HIGH_PRIORITY_METHODS
+            .iter()
+            .map(|m| (m.to_string(), Priority::High))
+            .chain(
+                LOW_PRIORITY_METHODS
+                    .iter()
+                    .map(|m| (m.to_string(), Priority::Low)),
+            )
+            .collect();
+
+        Self {
+            rate_limit: None,
+            max_concurrent_requests: usize::MAX,
+            max_concurrent_subscriptions: usize::MAX,
+            priorities,
+        }
+    }
+
+    /// Send at most `max_requests` requests (including subscription requests) in any period
+    /// of time `per`. Up to `max_requests` requests can be sent at once after a quiet period,
+    /// after which requests are sent evenly spaced apart.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_requests` or `per` is zero.
+    pub fn rate_limit(mut self, max_requests: u32, per: Duration) -> Self {
+        assert!(max_requests > 0, "max_requests must be greater than zero");
+        assert!(!per.is_zero(), "per must be greater than zero");
+        self.rate_limit = Some((max_requests, per));
+        self
+    }
+
+    /// The maximum number of requests that can be waiting for a response at once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is zero.
+    pub fn max_concurrent_requests(mut self, max: usize) -> Self {
+        assert!(max > 0, "max_concurrent_requests must be greater than zero");
+        self.max_concurrent_requests = max;
+        self
+    }
+
+    /// The maximum number of subscriptions that can be open at once. A subscription
+    /// counts towards this until it is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is zero.
+    pub fn max_concurrent_subscriptions(mut self, max: usize) -> Self {
+        assert!(
+            max > 0,
+            "max_concurrent_subscriptions must be greater than zero"
+        );
+        self.max_concurrent_subscriptions = max;
+        self
+    }
+
+    /// Set the [`Priority`] of requests or subscriptions to the given method. Methods
+    /// are [`Priority::Normal`] unless configured otherwise.
+    pub fn method_priority(mut self, method: impl Into<String>, priority: Priority) -> Self {
+        self.priorities.insert(method.into(), priority);
+        self
+    }
+
+    /// Wrap the given client in a [`RateLimitedRpcClient`].
+    pub fn build(self, client: impl Into<RpcClient>) -> RateLimitedRpcClient {
+        let bucket = self.rate_limit.map(|(max_requests, per)| TokenBucket {
+            capacity: max_requests as f64,
+            tokens: max_requests as f64,
+            tokens_per_sec: max_requests as f64 / per.as_secs_f64(),
+            last_refill: Instant::now(),
+        });
+
+        RateLimitedRpcClient {
+            inner: client.into(),
+            priorities: Arc::new(self.priorities),
+            limiter: Arc::new(Limiter {
+                max_in_flight: [
+                    self.max_concurrent_requests,
+                    self.max_concurrent_subscriptions,
+                ],
+                state: Mutex::new(LimiterState {
+                    bucket,
+                    in_flight: [0, 0],
+                    queues: Default::default(),
+                    next_ticket: 0,
+                }),
+            }),
+        }
+    }
+}
+
+/// An [`RpcClientT`] which wraps another one, limiting the rate at which requests are sent
+/// and how many requests and subscriptions can be in flight at once. See the
+/// [module docs](self) for more.
+///
+/// Clones share the same limits.
+#[derive(Clone)]
+pub struct RateLimitedRpcClient {
+    inner: RpcClient,
+    priorities: Arc<HashMap<String, Priority>>,
+    limiter: Arc<Limiter>,
+}
+
+impl std::fmt::Debug for RateLimitedRpcClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.limiter.state.lock().unwrap();
+        f.debug_struct("RateLimitedRpcClient")
+            .field(
+                "requests_in_flight",
+                &state.in_flight[Kind::Request as usize],
+            )
+            .field(
+                "subscriptions_open",
+                &state.in_flight[Kind::Subscription as usize],
+            )
+            .field(
+                "waiting",
+                &state.queues.iter().map(|q| q.len()).sum::<usize>(),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+impl RateLimitedRpcClient {
+    /// Configure and construct a [`RateLimitedRpcClient`].
+    pub fn builder() -> RateLimitedRpcClientBuilder {
+        RateLimitedRpcClientBuilder::new()
+    }
+
+    /// Return a reference to the wrapped client. Requests made via this are not limited.
+    pub fn inner(&self) -> &RpcClient {
+        &self.inner
+    }
+
+    fn priority(&self, method: &str) -> Priority {
+        self.priorities
+            .get(method)
+            .copied()
+            .unwrap_or(Priority::Normal)
+    }
+}
+
+impl RpcClientT for RateLimitedRpcClient {
+    fn request_raw<'a>(
+        &'a self,
+        method: &'a str,
+        params: Option<Box<RawValue>>,
+    ) -> RawRpcFuture<'a, Box<RawValue>> {
+        let acquire = Acquire::new(self.limiter.clone(), self.priority(method), Kind::Request);
+        Box::pin(async move {
+            // The permit is held until the response comes back.
+            let _permit = acquire.await;
+            self.inner.request_raw(method, params).await
+        })
+    }
+
+    fn subscribe_raw<'a>(
+        &'a self,
+        sub: &'a str,
+        params: Option<Box<RawValue>>,
+        unsub: &'a str,
+    ) -> RawRpcFuture<'a, RawRpcSubscription> {
+        let acquire = Acquire::new(self.limiter.clone(), self.priority(sub), Kind::Subscription);
+        Box::pin(async move {
+            // The permit is held until the subscription is dropped.
+            let permit = acquire.await;
+            let subscription = self.inner.subscribe_raw(sub, params, unsub).await?;
+            let stream = subscription.stream.map(move |item| {
+                let _permit = &permit;
+                item
+            });
+            Ok(RawRpcSubscription {
+                stream: stream.boxed(),
+                id: subscription.id,
+            })
+        })
+    }
+}
+
+/// Whether a permit is for a request or a subscription. These are limited separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Request = 0,
+    Subscription = 1,
+}
+
+struct Limiter {
+    max_in_flight: [usize; 2],
+    state: Mutex<LimiterState>,
+}
+
+struct LimiterState {
+    bucket: Option<TokenBucket>,
+    in_flight: [usize; 2],
+    // One queue of waiters for each priority, highest priority first.
+    queues: [VecDeque<Waiter>; 3],
+    next_ticket: u64,
+}
+
+struct Waiter {
+    ticket: u64,
+    kind: Kind,
+    waker: Option<Waker>,
+}
+
+impl LimiterState {
+    // The ticket of the waiter which should go next; this is the first in the highest priority
+    // queue which isn't waiting for an in flight request or subscription to finish.
+    fn next_ticket(&self, max_in_flight: &[usize; 2]) -> Option<u64> {
+        self.queues
+            .iter()
+            .flatten()
+            .find(|w| self.in_flight[w.kind as usize] < max_in_flight[w.kind as usize])
+            .map(|w| w.ticket)
+    }
+
+    fn waiter_mut(&mut self, ticket: u64) -> Option<&mut Waiter> {
+        self.queues
+            .iter_mut()
+            .flatten()
+            .find(|w| w.ticket == ticket)
+    }
+
+    fn remove(&mut self, ticket: u64) {
+        for queue in &mut self.queues {
+            queue.retain(|w| w.ticket != ticket);
+        }
+    }
+
+    // Wake the waiter which should go next, so that it can check whether it can go.
+    fn wake_next(&mut self, max_in_flight: &[usize; 2]) {
+        if let Some(ticket) = self.next_ticket(max_in_flight) {
+            if let Some(waker) = self.waiter_mut(ticket).and_then(|w| w.waker.take()) {
+                waker.wake();
+            }
+        }
+    }
+}
+
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    tokens_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Take a token, or return how long to wait until one is available.
+    fn take(&mut self) -> Result<(), Duration> {
+        let now = Instant::now();
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.tokens =
+            (self.tokens + elapsed.as_secs_f64() * self.tokens_per_sec).min(self.capacity);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - self.tokens) / self.tokens_per_sec,
+            ))
+        }
+    }
+}
+
+/// Wait until a request or subscription can be sent. If this is dropped before
+/// then, it gives up its place in the queue.
+struct Acquire {
+    limiter: Arc<Limiter>,
+    priority: Priority,
+    kind: Kind,
+    ticket: Option<u64>,
+    delay: Option<Delay>,
+}
+
+impl Acquire {
+    fn new(limiter: Arc<Limiter>, priority: Priority, kind: Kind) -> Self {
+        Acquire {
+            limiter,
+            priority,
+            kind,
+            ticket: None,
+            delay: None,
+        }
+    }
+}
+
+impl Future for Acquire {
+    type Output = Permit;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let limiter = this.limiter.clone();
+        let max_in_flight = &limiter.max_in_flight;
+
+        loop {
+            let wait = {
+                let mut state = limiter.state.lock().unwrap();
+
+                let ticket = *this.ticket.get_or_insert_with(|| {
+                    let ticket = state.next_ticket;
+                    state.next_ticket += 1;
+                    state.queues[this.priority.index()].push_back(Waiter {
+                        ticket,
+                        kind: this.kind,
+                        waker: None,
+                    });
+                    ticket
+                });
+
+                if state.next_ticket(max_in_flight) != Some(ticket) {
+                    // Something else goes first; we'll be woken when it's our turn.
+                    if let Some(waiter) = state.waiter_mut(ticket) {
+                        waiter.waker = Some(cx.waker().clone());
+                    }
+                    this.delay = None;
+                    return Poll::Pending;
+                }
+
+                match state.bucket.as_mut().map(|b| b.take()).unwrap_or(Ok(())) {
+                    Ok(()) => {
+                        state.remove(ticket);
+                        state.in_flight[this.kind as usize] += 1;
+                        this.ticket = None;
+                        // Something else may be able to go now too.
+                        state.wake_next(max_in_flight);
+                        return Poll::Ready(Permit {
+                            limiter: limiter.clone(),
+                            kind: this.kind,
+                        });
+                    }
+                    Err(wait) => {
+                        // Something with a higher priority may show up while we wait for a
+                        // token, in which case we'll be woken to step aside for it.
+                        if let Some(waiter) = state.waiter_mut(ticket) {
+                            waiter.waker = Some(cx.waker().clone());
+                        }
+                        wait
+                    }
+                }
+            };
+
+            let delay = this.delay.get_or_insert_with(|| Delay::new(wait));
+            match delay.poll_unpin(cx) {
+                Poll::Ready(()) => this.delay = None,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl Drop for Acquire {
+    fn drop(&mut self) {
+        if let Some(ticket) = self.ticket.take() {
+            let mut state = self.limiter.state.lock().unwrap();
+            state.remove(ticket);
+            state.wake_next(&self.limiter.max_in_flight);
+        }
+    }
+}
+
+/// Counts towards the number of requests or subscriptions in flight until dropped.
+struct Permit {
+    limiter: Arc<Limiter>,
+    kind: Kind,
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        let mut state = self.limiter.state.lock().unwrap();
+        state.in_flight[self.kind as usize] -= 1;
+        state.wake_next(&self.limiter.max_in_flight);
+    }
+}
+
+#[cfg(all(test, feature = "mock-rpc-client"))]
+mod test {
+    use super::*;
+    use crate::client::{MockRpcClient, RpcClient, mock_rpc_client::Json};
+    use crate::rpc_params;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn limits_concurrent_requests() {
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_seen = Arc::new(AtomicUsize::new(0));
+
+        let (in_flight2, max_seen2) = (in_flight.clone(), max_seen.clone());
+        let mock_client = MockRpcClient::builder()
+            .method_handler("foo", move |_params| {
+                let in_flight = in_flight2.clone();
+                let max_seen = max_seen2.clone();
+                async move {
+                    let n = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_seen.fetch_max(n, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                    Json(())
+                }
+            })
+            .build();
+
+        let client = RpcClient::new(
+            RateLimitedRpcClient::builder()
+                .max_concurrent_requests(2)
+                .build(mock_client),
+        );
+
+        let requests = (0..10).map(|_| client.request::<()>("foo", rpc_params![]));
+        for res in futures::future::join_all(requests).await {
+            res.unwrap();
+        }
+
+        assert_eq!(max_seen.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn limits_request_rate() {
+        let mock_client = MockRpcClient::builder()
+            .method_handler("foo", async |_params| Json(()))
+            .build();
+
+        let client = RpcClient::new(
+            RateLimitedRpcClient::builder()
+                .rate_limit(5, Duration::from_millis(100))
+                .build(mock_client),
+        );
+
+        // The first 5 requests can go at once, and the next 5 must be spread over 100ms.
+        let started = Instant::now();
+        let requests = (0..10).map(|_| client.request::<()>("foo", rpc_params![]));
+        for res in futures::future::join_all(requests).await {
+            res.unwrap();
+        }
+
+        assert!(started.elapsed() >= Duration::from_millis(90));
+    }
+
+    #[tokio::test]
+    async fn high_priority_requests_go_first() {
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let started = Arc::new(tokio::sync::Notify::new());
+        let release = Arc::new(tokio::sync::Notify::new());
+
+        let (order1, order2) = (order.clone(), order.clone());
+        let (started1, release1) = (started.clone(), release.clone());
+        let mock_client = MockRpcClient::builder()
+            .method_handler("block", move |_params| {
+                started1.notify_one();
+                let release = release1.clone();
+                async move {
+                    release.notified().await;
+                    Json(())
+                }
+            })
+            .method_handler("state_getKeysPaged", move |_params| {
+                order1.lock().unwrap().push("low");
+                async { Json(()) }
+            })
+            .method_handler("author_submitExtrinsic", move |_params| {
+                order2.lock().unwrap().push("high");
+                async { Json(()) }
+            })
+            .build();
+
+        let client = RpcClient::new(
+            RateLimitedRpcClient::builder()
+                .max_concurrent_requests(1)
+                .build(mock_client),
+        );
+
+        // Hold the only permit so that everything after this has to queue.
+        let blocking = tokio::spawn({
+            let client = client.clone();
+            async move { client.request::<()>("block", rpc_params![]).await }
+        });
+        started.notified().await;
+
+        // Poll each request once, which puts it in the queue, so that the
+        // low priority requests are queued before the high priority one.
+        let mut requests: Vec<_> = (0..3)
+            .map(|_| client.request::<()>("state_getKeysPaged", rpc_params![]))
+            .chain([client.request::<()>("author_submitExtrinsic", rpc_params![])])
+            .map(Box::pin)
+            .collect();
+        for request in &mut requests {
+            assert!(request.as_mut().now_or_never().is_none());
+        }
+
+        release.notify_one();
+        blocking.await.unwrap().unwrap();
+        for res in futures::future::join_all(requests).await {
+            res.unwrap();
+        }
+
+        assert_eq!(*order.lock().unwrap(), ["high", "low", "low", "low"]);
+    }
+
+    #[tokio::test]
+    async fn subscriptions_count_until_dropped() {
+        let mock_client = MockRpcClient::builder()
+            .subscription_handler("sub", async |_params, _unsub| vec![Json(1), Json(2)])
+            .build();
+
+        let client = RpcClient::new(
+            RateLimitedRpcClient::builder()
+                .max_concurrent_subscriptions(1)
+                .build(mock_client),
+        );
+
+        let sub = client
+            .subscribe::<u8>("sub", rpc_params![], "unsub")
+            .await
+            .unwrap();
+
+        // The second subscription has to wait for the first to be dropped:
+        let second = client.subscribe::<u8>("sub", rpc_params![], "unsub");
+        let mut second = std::pin::pin!(second);
+        assert!(second.as_mut().now_or_never().is_none());
+
+        drop(sub);
+        second.await.unwrap();
+    }
+}
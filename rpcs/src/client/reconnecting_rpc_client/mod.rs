@@ -481,8 +481,12 @@ async fn background_task<P>(
                 };
 
                 client = match reconnect(params).await {
-                    Ok(client) => client,
+                    Ok(client) => {
+                        super::metrics::record_reconnect(true);
+                        client
+                    }
                     Err(e) => {
+                        super::metrics::record_reconnect(false);
                         tracing::debug!(target: LOG_TARGET, "Failed to reconnect: {e}; terminating the connection");
                         break;
                     }
@@ -3,12 +3,12 @@
 // see LICENSE for license details.
 
 use super::*;
-use futures::{future::Either, FutureExt};
+use futures::{FutureExt, future::Either};
 
 use jsonrpsee::core::BoxError;
 use jsonrpsee::server::{
-    http, stop_channel, ws, ConnectionGuard, ConnectionState, HttpRequest, HttpResponse, RpcModule,
-    RpcServiceBuilder, ServerConfig, SubscriptionMessage,
+    ConnectionGuard, ConnectionState, HttpRequest, HttpResponse, RpcModule, RpcServiceBuilder,
+    ServerConfig, SubscriptionMessage, http, stop_channel, ws,
 };
 
 #[tokio::test]
@@ -2,12 +2,14 @@
 // This file is dual-licensed as Apache-2.0 or GPL-3.0.
 // see LICENSE for license details.
 
+use super::metrics::{RequestMetrics, SubscriptionMetrics};
 use super::{RawRpcSubscription, RpcClientT};
 use crate::Error;
 use futures::{Stream, StreamExt};
 use serde::{Serialize, de::DeserializeOwned};
 use serde_json::value::RawValue;
 use std::{pin::Pin, sync::Arc, task::Poll};
+use tracing::Instrument;
 
 /// A concrete wrapper around an [`RpcClientT`] which provides some higher level helper methods
 /// and is cheaply cloneable.
@@ -54,9 +56,23 @@ impl RpcClient {
         method: &str,
         params: RpcParams,
     ) -> Result<Res, Error> {
-        let res = self.client.request_raw(method, params.build()).await?;
-        let val = serde_json::from_str(res.get()).map_err(Error::Deserialization)?;
-        Ok(val)
+        let params = params.build();
+        let span = tracing::debug_span!(target: "subxt_rpcs", "rpc_request", method);
+
+        async move {
+            trace_params(&params);
+            let metrics = RequestMetrics::start(method);
+            let res = self.client.request_raw(method, params).await;
+            metrics.finish(&res);
+
+            let res = res.inspect_err(|e| {
+                tracing::debug!(target: "subxt_rpcs", error = %e, "RPC request failed");
+            })?;
+            let val = serde_json::from_str(res.get()).map_err(Error::Deserialization)?;
+            Ok(val)
+        }
+        .instrument(span)
+        .await
     }
 
     /// Subscribe to an RPC endpoint, providing the parameters and the method to call to
@@ -70,14 +86,53 @@ impl RpcClient {
         params: RpcParams,
         unsub: &str,
     ) -> Result<RpcSubscription<Res>, Error> {
-        let sub = self
-            .client
-            .subscribe_raw(sub, params.build(), unsub)
-            .await?;
-        Ok(RpcSubscription::new(sub))
+        let method = sub;
+        let params = params.build();
+        let span = tracing::debug_span!(
+            target: "subxt_rpcs",
+            "rpc_subscribe",
+            method,
+            subscription_id = tracing::field::Empty,
+        );
+
+        async move {
+            trace_params(&params);
+            let metrics = RequestMetrics::start(method);
+            let res = self.client.subscribe_raw(method, params, unsub).await;
+            metrics.finish(&res);
+
+            let mut sub = res.inspect_err(|e| {
+                tracing::debug!(target: "subxt_rpcs", error = %e, "RPC subscription failed");
+            })?;
+            if let Some(id) = &sub.id {
+                tracing::Span::current().record("subscription_id", id.as_str());
+            }
+
+            // Count items as they arrive, and keep the subscription counted as
+            // active until the stream is dropped.
+            let sub_metrics = SubscriptionMetrics::start(method);
+            sub.stream = sub
+                .stream
+                .inspect(move |_| sub_metrics.item_received())
+                .boxed();
+
+            Ok(RpcSubscription::new(sub))
+        }
+        .instrument(span)
+        .await
     }
 }
 
+// Params can contain large or sensitive data (for instance whole transactions), and so they
+// are only logged at trace level rather than being recorded on the request spans.
+fn trace_params(params: &Option<Box<RawValue>>) {
+    tracing::trace!(
+        target: "subxt_rpcs",
+        params = params.as_ref().map(|p| p.get()).unwrap_or("[]"),
+        "RPC params"
+    );
+}
+
 impl<C: RpcClientT> From<C> for RpcClient {
     fn from(client: C) -> Self {
         RpcClient::new(client)
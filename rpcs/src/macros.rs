@@ -34,6 +34,16 @@ macro_rules! cfg_reconnecting_rpc_client {
 	}
 }
 
+macro_rules! cfg_rate_limited_rpc_client {
+	($($item:item)*) => {
+		$(
+			#[cfg(all(feature = "rate-limited-rpc-client", any(feature = "native", feature = "web")))]
+			#[cfg_attr(docsrs, doc(cfg(feature = "rate-limited-rpc-client")))]
+			$item
+		)*
+	}
+}
+
 macro_rules! cfg_mock_rpc_client {
 	($($item:item)*) => {
 		crate::macros::cfg_feature!("mock-rpc-client", $($item)*);
@@ -41,6 +51,6 @@ macro_rules! cfg_mock_rpc_client {
 }
 
 pub(crate) use {
-    cfg_feature, cfg_jsonrpsee, cfg_mock_rpc_client, cfg_reconnecting_rpc_client,
-    cfg_unstable_light_client,
+    cfg_feature, cfg_jsonrpsee, cfg_mock_rpc_client, cfg_rate_limited_rpc_client,
+    cfg_reconnecting_rpc_client, cfg_unstable_light_client,
 };
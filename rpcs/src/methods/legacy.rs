@@ -317,6 +317,27 @@ impl<T: RpcConfig> LegacyRpcMethods<T> {
         Ok(subscription)
     }
 
+    /// Return the SCALE encoded extrinsics that are currently in the node's transaction pool.
+    pub async fn author_pending_extrinsics(&self) -> Result<Vec<Vec<u8>>, Error> {
+        let extrinsics: Vec<Bytes> = self
+            .client
+            .request("author_pendingExtrinsics", rpc_params![])
+            .await?;
+        Ok(extrinsics.into_iter().map(|e| e.0).collect())
+    }
+
+    /// Remove the given extrinsics from the node's transaction pool, along with any extrinsics
+    /// which depend on them. Returns the hashes of all of the extrinsics that were removed.
+    ///
+    /// This is an unsafe RPC method, and so may not be available on public nodes.
+    pub async fn author_remove_extrinsic(
+        &self,
+        extrinsics: Vec<ExtrinsicOrHash<T::Hash>>,
+    ) -> Result<Vec<T::Hash>, Error> {
+        let params = rpc_params![extrinsics];
+        self.client.request("author_removeExtrinsic", params).await
+    }
+
     /// Insert a key into the keystore.
     pub async fn author_insert_key(
         &self,
@@ -578,6 +599,17 @@ pub enum DryRunDecodeError {
     InvalidBytes,
 }
 
+/// Identifies an extrinsic to remove from the transaction pool with
+/// [`LegacyRpcMethods::author_remove_extrinsic()`].
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum ExtrinsicOrHash<Hash> {
+    /// The hash of the extrinsic.
+    Hash(Hash),
+    /// The SCALE encoded extrinsic.
+    Extrinsic(Bytes),
+}
+
 /// Storage change set
 #[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Debug)]
 #[serde(rename_all = "camelCase")]
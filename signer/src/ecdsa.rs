@@ -195,6 +195,27 @@ pub fn verify<M: AsRef<[u8]>>(sig: &Signature, message: M, pubkey: &PublicKey) -
     internal::verify(&sig.0, &wrapped, pubkey)
 }
 
+/// Recover the [`PublicKey`] whose key pair created some signature for a message. Returns
+/// `None` if no public key can be recovered from the signature. The recovery ID in the last
+/// byte of the signature may be given either as 0 or 1, or in the Ethereum style as 27 or 28.
+///
+/// ```rust,standalone_crate
+/// use subxt_signer::ecdsa;
+///
+/// let keypair = ecdsa::dev::alice();
+/// let message = b"Hello!";
+///
+/// let signature = keypair.sign(message);
+/// let public_key = ecdsa::recover(&signature, message).unwrap();
+/// assert_eq!(public_key.0, keypair.public_key().0);
+/// ```
+pub fn recover<M: AsRef<[u8]>>(sig: &Signature, message: M) -> Option<PublicKey> {
+    let message_hash = sp_crypto_hashing::blake2_256(message.as_ref());
+    let wrapped = Message::from_digest_slice(&message_hash).expect("Message is 32 bytes; qed");
+
+    internal::recover(&sig.0, &wrapped).map(|public| PublicKey(public.serialize()))
+}
+
 pub(crate) mod internal {
     use super::*;
 
@@ -220,6 +241,20 @@ pub(crate) mod internal {
             .verify_ecdsa(message, &signature, &public)
             .is_ok()
     }
+
+    pub fn recover(sig: &[u8; 65], message: &Message) -> Option<secp256k1::PublicKey> {
+        // Ethereum style signatures offset the recovery ID by 27.
+        let v = match sig[64] {
+            v @ 27.. => v - 27,
+            v => v,
+        };
+        let recid = secp256k1::ecdsa::RecoveryId::try_from(i32::from(v)).ok()?;
+        let signature = RecoverableSignature::from_compact(&sig[..64], recid).ok()?;
+
+        Secp256k1::verification_only()
+            .recover_ecdsa(message, &signature)
+            .ok()
+    }
 }
 
 /// An error handed back if creating a keypair fails.
@@ -438,6 +473,19 @@ mod test {
         }
     }
 
+    #[test]
+    fn check_recover_accepts_ethereum_style_recovery_ids() {
+        let pair = dev::alice();
+        let message = b"Hello world";
+        let mut sig = pair.sign(message);
+
+        let public_key = Some(pair.public_key().0);
+
+        assert_eq!(recover(&sig, message).map(|p| p.0), public_key);
+        sig.0[64] += 27;
+        assert_eq!(recover(&sig, message).map(|p| p.0), public_key);
+    }
+
     #[test]
     fn check_hex_uris() {
         // Hex URIs seem to ignore the password on sp_core and here. Check that this is consistent.
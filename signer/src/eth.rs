@@ -201,6 +201,28 @@ pub fn verify<M: AsRef<[u8]>>(sig: &Signature, message: M, pubkey: &PublicKey) -
         .is_ok()
 }
 
+/// Recover the [`PublicKey`] whose key pair created some signature for a message. Returns
+/// `None` if no public key can be recovered from the signature.
+///
+/// ```rust,standalone_crate
+/// use subxt_signer::eth;
+///
+/// let keypair = eth::dev::alith();
+/// let message = b"Hello!";
+///
+/// let signature = keypair.sign(message);
+/// let public_key = eth::recover(&signature, message).unwrap();
+/// assert_eq!(public_key.0, keypair.public_key().0);
+/// ```
+pub fn recover<M: AsRef<[u8]>>(sig: &Signature, message: M) -> Option<PublicKey> {
+    let message_hash = keccak(message.as_ref());
+    let wrapped =
+        Message::from_digest_slice(message_hash.as_bytes()).expect("Message is 32 bytes; qed");
+
+    ecdsa::internal::recover(&sig.0, &wrapped)
+        .map(|public| PublicKey(public.serialize_uncompressed()))
+}
+
 /// An error handed back if creating a keypair fails.
 #[derive(Debug, PartialEq, DeriveError)]
 pub enum Error {
@@ -0,0 +1,143 @@
+//! Example of wrapping a backend in order to log each request that it handles.
+
+#![allow(missing_docs)]
+
+use std::sync::Arc;
+use subxt::backend::legacy::LegacyBackend;
+use subxt::backend::rpc::RpcClient;
+use subxt::backend::{Backend, BlockRef, StorageResponse, StreamOfResults, TransactionStatus};
+use subxt::client::RuntimeVersion;
+use subxt::config::{Config, HashFor};
+use subxt::{Error, OnlineClient, PolkadotConfig};
+
+// A backend which logs every request and then hands it to another backend.
+struct LoggingBackend<B> {
+    inner: B,
+}
+
+#[subxt::ext::async_trait::async_trait]
+impl<T: Config, B: Backend<T>> Backend<T> for LoggingBackend<B> {
+    async fn storage_fetch_values(
+        &self,
+        keys: Vec<Vec<u8>>,
+        at: HashFor<T>,
+    ) -> Result<StreamOfResults<StorageResponse>, Error> {
+        println!("storage_fetch_values: {} keys at {at:?}", keys.len());
+        self.inner.storage_fetch_values(keys, at).await
+    }
+
+    async fn storage_fetch_descendant_keys(
+        &self,
+        key: Vec<u8>,
+        at: HashFor<T>,
+    ) -> Result<StreamOfResults<Vec<u8>>, Error> {
+        println!("storage_fetch_descendant_keys at {at:?}");
+        self.inner.storage_fetch_descendant_keys(key, at).await
+    }
+
+    async fn storage_fetch_descendant_values(
+        &self,
+        key: Vec<u8>,
+        at: HashFor<T>,
+    ) -> Result<StreamOfResults<StorageResponse>, Error> {
+        println!("storage_fetch_descendant_values at {at:?}");
+        self.inner.storage_fetch_descendant_values(key, at).await
+    }
+
+    async fn genesis_hash(&self) -> Result<HashFor<T>, Error> {
+        println!("genesis_hash");
+        self.inner.genesis_hash().await
+    }
+
+    async fn block_header(&self, at: HashFor<T>) -> Result<Option<T::Header>, Error> {
+        println!("block_header at {at:?}");
+        self.inner.block_header(at).await
+    }
+
+    async fn block_body(&self, at: HashFor<T>) -> Result<Option<Vec<Vec<u8>>>, Error> {
+        println!("block_body at {at:?}");
+        self.inner.block_body(at).await
+    }
+
+    async fn latest_finalized_block_ref(&self) -> Result<BlockRef<HashFor<T>>, Error> {
+        println!("latest_finalized_block_ref");
+        self.inner.latest_finalized_block_ref().await
+    }
+
+    async fn current_runtime_version(&self) -> Result<RuntimeVersion, Error> {
+        println!("current_runtime_version");
+        self.inner.current_runtime_version().await
+    }
+
+    async fn stream_runtime_version(&self) -> Result<StreamOfResults<RuntimeVersion>, Error> {
+        println!("stream_runtime_version");
+        self.inner.stream_runtime_version().await
+    }
+
+    async fn stream_all_block_headers(
+        &self,
+        hasher: T::Hasher,
+    ) -> Result<StreamOfResults<(T::Header, BlockRef<HashFor<T>>)>, Error> {
+        println!("stream_all_block_headers");
+        self.inner.stream_all_block_headers(hasher).await
+    }
+
+    async fn stream_best_block_headers(
+        &self,
+        hasher: T::Hasher,
+    ) -> Result<StreamOfResults<(T::Header, BlockRef<HashFor<T>>)>, Error> {
+        println!("stream_best_block_headers");
+        self.inner.stream_best_block_headers(hasher).await
+    }
+
+    async fn stream_finalized_block_headers(
+        &self,
+        hasher: T::Hasher,
+    ) -> Result<StreamOfResults<(T::Header, BlockRef<HashFor<T>>)>, Error> {
+        println!("stream_finalized_block_headers");
+        self.inner.stream_finalized_block_headers(hasher).await
+    }
+
+    async fn submit_transaction(
+        &self,
+        bytes: &[u8],
+    ) -> Result<StreamOfResults<TransactionStatus<HashFor<T>>>, Error> {
+        println!("submit_transaction: {} bytes", bytes.len());
+        self.inner.submit_transaction(bytes).await
+    }
+
+    async fn call(
+        &self,
+        method: &str,
+        call_parameters: Option<&[u8]>,
+        at: HashFor<T>,
+    ) -> Result<Vec<u8>, Error> {
+        println!("call: {method} at {at:?}");
+        self.inner.call(method, call_parameters, at).await
+    }
+
+    async fn pending_transactions(&self) -> Result<Vec<Vec<u8>>, Error> {
+        println!("pending_transactions");
+        self.inner.pending_transactions().await
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let rpc_client = RpcClient::from_url("ws://localhost:9944").await?;
+    let backend = LoggingBackend {
+        inner: LegacyBackend::<PolkadotConfig>::builder().build(rpc_client),
+    };
+
+    // Any backend can be used to power an `OnlineClient`:
+    let api = OnlineClient::<PolkadotConfig>::from_backend(Arc::new(backend)).await?;
+
+    let block = api.blocks().at_latest().await?;
+    println!(
+        "Block #{} has {} extrinsics",
+        block.number(),
+        block.extrinsics().await?.len()
+    );
+
+    Ok(())
+}
@@ -38,6 +38,7 @@ impl Config for CustomConfig {
             transaction_extensions::ChargeAssetTxPayment<Self>,
             transaction_extensions::ChargeTransactionPayment,
             transaction_extensions::CheckMetadataHash,
+            transaction_extensions::DynamicTransactionExtensions,
             // And add a new one of our own:
             CustomTransactionExtension,
         ),
@@ -84,8 +85,8 @@ impl ExtrinsicParamsEncoder for CustomTransactionExtension {
 pub fn custom(
     params: DefaultExtrinsicParamsBuilder<CustomConfig>,
 ) -> <<CustomConfig as Config>::ExtrinsicParams as ExtrinsicParams<CustomConfig>>::Params {
-    let (a, b, c, d, e, f, g, h, i) = params.build();
-    (a, b, c, d, e, f, g, h, i, ())
+    let (a, b, c, d, e, f, g, h, i, j) = params.build();
+    (a, b, c, d, e, f, g, h, i, j, ())
 }
 
 #[tokio::main]
@@ -0,0 +1,580 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! This module exposes a [`CachingBackend`], which wraps another [`Backend`] and caches
+//! data from finalized blocks, which can never change.
+
+use crate::backend::{
+    Backend, BlockRef, RuntimeVersion, StorageResponse, StreamOf, StreamOfResults,
+    TransactionStatus,
+};
+use crate::config::{Config, HashFor};
+use crate::error::Error;
+use async_trait::async_trait;
+use codec::{Decode, Encode};
+use futures::{StreamExt, TryStreamExt};
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Configure and build a [`CachingBackend`].
+pub struct CachingBackendBuilder {
+    max_bytes: NonZeroUsize,
+    max_finalized_blocks: NonZeroUsize,
+}
+
+impl Default for CachingBackendBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CachingBackendBuilder {
+    /// Create a new [`CachingBackendBuilder`].
+    pub fn new() -> Self {
+        Self {
+            max_bytes: NonZeroUsize::new(64 * 1024 * 1024).expect("64MiB is not zero; qed"),
+            max_finalized_blocks: NonZeroUsize::new(4096).expect("4096 is not zero; qed"),
+        }
+    }
+
+    /// The approximate number of bytes of headers, block bodies, storage values and runtime API
+    /// call results to cache (default: 64MiB). The least recently used entries are evicted
+    /// when this limit is reached, and anything larger than the limit is not cached at all.
+    pub fn max_bytes(mut self, max_bytes: NonZeroUsize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// The maximum number of finalized block hashes to remember (default: 4096). Only data
+    /// from blocks that are known to be finalized is cached.
+    pub fn max_finalized_blocks(mut self, max_finalized_blocks: NonZeroUsize) -> Self {
+        self.max_finalized_blocks = max_finalized_blocks;
+        self
+    }
+
+    /// Wrap the given [`Backend`] in a [`CachingBackend`].
+    pub fn build<B>(self, backend: B) -> CachingBackend<B> {
+        CachingBackend {
+            inner: backend,
+            cache: Arc::new(Cache {
+                entries: Mutex::new(Entries {
+                    lru: LruCache::unbounded(),
+                    bytes: 0,
+                    max_bytes: self.max_bytes.get(),
+                }),
+                finalized: Mutex::new(LruCache::new(self.max_finalized_blocks)),
+                hits: AtomicU64::new(0),
+                misses: AtomicU64::new(0),
+            }),
+        }
+    }
+}
+
+/// A [`Backend`] which wraps another one, and caches block headers, block bodies, storage
+/// values and runtime API call results (which includes metadata) for finalized blocks.
+/// Everything else is passed straight through to the wrapped backend.
+///
+/// Blocks are known to be finalized once they have been handed back from
+/// [`Backend::latest_finalized_block_ref()`] or [`Backend::stream_finalized_block_headers()`],
+/// which is the case for blocks obtained via [`crate::blocks::BlocksClient::at_latest()`] or
+/// [`crate::blocks::BlocksClient::subscribe_finalized()`], for instance.
+///
+/// # Example
+///
+/// ```rust,no_run,standalone_crate
+/// use std::sync::Arc;
+/// use subxt::backend::{caching::CachingBackend, legacy::LegacyBackend, rpc::RpcClient};
+/// use subxt::{OnlineClient, PolkadotConfig};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let rpc_client = RpcClient::from_url("ws://localhost:9944").await?;
+/// let backend = CachingBackend::builder().build(LegacyBackend::builder().build(rpc_client));
+/// let backend = Arc::new(backend);
+///
+/// let api = OnlineClient::<PolkadotConfig>::from_backend(backend.clone()).await?;
+/// let block = api.blocks().at_latest().await?;
+///
+/// // The second lookup will be served from the cache:
+/// block.events().await?;
+/// block.events().await?;
+/// println!("{:?}", backend.stats());
+/// # Ok(())
+/// # }
+/// ```
+pub struct CachingBackend<B> {
+    inner: B,
+    cache: Arc<Cache>,
+}
+
+impl<B> std::fmt::Debug for CachingBackend<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachingBackend")
+            .field("stats", &self.stats())
+            .finish_non_exhaustive()
+    }
+}
+
+impl CachingBackend<()> {
+    /// Configure and construct a [`CachingBackend`].
+    pub fn builder() -> CachingBackendBuilder {
+        CachingBackendBuilder::new()
+    }
+}
+
+impl<B> CachingBackend<B> {
+    /// The number of cache hits and misses so far. Requests about blocks which aren't known to
+    /// be finalized are not counted.
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.cache.hits.load(Ordering::Relaxed),
+            misses: self.cache.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Remove everything from the cache. This does not reset the hit and miss counters, and
+    /// does not forget which blocks are known to be finalized, since that can't change.
+    pub fn clear(&self) {
+        let mut entries = self.cache.entries.lock().unwrap();
+        entries.lru.clear();
+        entries.bytes = 0;
+    }
+
+    /// Return a reference to the wrapped backend.
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+}
+
+/// The number of hits and misses recorded by a [`CachingBackend`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheStats {
+    /// The number of requests which were answered from the cache.
+    pub hits: u64,
+    /// The number of requests about finalized blocks which had to be passed on to
+    /// the wrapped backend.
+    pub misses: u64,
+}
+
+struct Cache {
+    entries: Mutex<Entries>,
+    // Block hashes are stored as bytes, so that the cache doesn't depend on the config.
+    finalized: Mutex<LruCache<Vec<u8>, ()>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+struct Entries {
+    lru: LruCache<CacheKey, CacheValue>,
+    // The total size of the keys and values in the cache.
+    bytes: usize,
+    max_bytes: usize,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum CacheKey {
+    Header(Vec<u8>),
+    Body(Vec<u8>),
+    Storage(Vec<u8>, Vec<u8>),
+    Call(Vec<u8>, String, Option<Vec<u8>>),
+}
+
+#[derive(Clone)]
+enum CacheValue {
+    // Headers are stored encoded, since they aren't required to be `Clone`.
+    Header(Vec<u8>),
+    Body(Vec<Vec<u8>>),
+    Storage(Option<Vec<u8>>),
+    Call(Vec<u8>),
+}
+
+impl CacheKey {
+    fn len(&self) -> usize {
+        match self {
+            CacheKey::Header(hash) | CacheKey::Body(hash) => hash.len(),
+            CacheKey::Storage(hash, key) => hash.len() + key.len(),
+            CacheKey::Call(hash, method, params) => {
+                hash.len() + method.len() + params.as_ref().map_or(0, Vec::len)
+            }
+        }
+    }
+}
+
+impl CacheValue {
+    fn len(&self) -> usize {
+        match self {
+            CacheValue::Header(bytes) | CacheValue::Call(bytes) => bytes.len(),
+            CacheValue::Body(extrinsics) => extrinsics.iter().map(Vec::len).sum(),
+            CacheValue::Storage(value) => value.as_ref().map_or(0, Vec::len),
+        }
+    }
+}
+
+impl Cache {
+    fn mark_finalized(&self, hash: &[u8]) {
+        self.finalized.lock().unwrap().put(hash.to_vec(), ());
+    }
+
+    fn is_finalized(&self, hash: &[u8]) -> bool {
+        self.finalized.lock().unwrap().contains(hash)
+    }
+
+    // Return the cached value, recording a hit or miss. Returns `None` without recording
+    // anything if the block isn't known to be finalized, since nothing is cached for it.
+    fn get(&self, at: &[u8], key: &CacheKey) -> Option<Option<CacheValue>> {
+        if !self.is_finalized(at) {
+            return None;
+        }
+        let value = self.entries.lock().unwrap().lru.get(key).cloned();
+        let counter = if value.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        Some(value)
+    }
+
+    fn put(&self, key: CacheKey, value: CacheValue) {
+        let len = key.len() + value.len();
+        let mut entries = self.entries.lock().unwrap();
+        if len > entries.max_bytes {
+            return;
+        }
+
+        if let Some((old_key, old_value)) = entries.lru.push(key, value) {
+            entries.bytes -= old_key.len() + old_value.len();
+        }
+        entries.bytes += len;
+        while entries.bytes > entries.max_bytes {
+            let Some((old_key, old_value)) = entries.lru.pop_lru() else {
+                break;
+            };
+            entries.bytes -= old_key.len() + old_value.len();
+        }
+    }
+}
+
+#[async_trait]
+impl<T: Config, B: Backend<T>> Backend<T> for CachingBackend<B> {
+    async fn storage_fetch_values(
+        &self,
+        keys: Vec<Vec<u8>>,
+        at: HashFor<T>,
+    ) -> Result<StreamOfResults<StorageResponse>, Error> {
+        let hash = at.as_ref();
+        if !self.cache.is_finalized(hash) {
+            return self.inner.storage_fetch_values(keys, at).await;
+        }
+
+        let mut values = Vec::with_capacity(keys.len());
+        let mut missing = Vec::new();
+        for key in keys {
+            let cache_key = CacheKey::Storage(hash.to_vec(), key.clone());
+            match self.cache.get(hash, &cache_key) {
+                Some(Some(CacheValue::Storage(value))) => values.push((key, value)),
+                _ => {
+                    missing.push(key.clone());
+                    values.push((key, None));
+                }
+            }
+        }
+
+        if !missing.is_empty() {
+            let fetched: Vec<StorageResponse> = self
+                .inner
+                .storage_fetch_values(missing.clone(), at)
+                .await?
+                .try_collect()
+                .await?;
+
+            // Keys which weren't handed back have no value, which we cache too.
+            for key in missing {
+                let value = fetched
+                    .iter()
+                    .find(|res| res.key == key)
+                    .map(|res| res.value.clone());
+                self.cache.put(
+                    CacheKey::Storage(hash.to_vec(), key.clone()),
+                    CacheValue::Storage(value.clone()),
+                );
+                if let Some(entry) = values.iter_mut().find(|(k, _)| *k == key) {
+                    entry.1 = value;
+                }
+            }
+        }
+
+        let responses = values
+            .into_iter()
+            .filter_map(|(key, value)| value.map(|value| Ok(StorageResponse { key, value })));
+        Ok(StreamOf::new(Box::pin(futures::stream::iter(responses))))
+    }
+
+    async fn storage_fetch_descendant_keys(
+        &self,
+        key: Vec<u8>,
+        at: HashFor<T>,
+    ) -> Result<StreamOfResults<Vec<u8>>, Error> {
+        self.inner.storage_fetch_descendant_keys(key, at).await
+    }
+
+    async fn storage_fetch_descendant_values(
+        &self,
+        key: Vec<u8>,
+        at: HashFor<T>,
+    ) -> Result<StreamOfResults<StorageResponse>, Error> {
+        self.inner.storage_fetch_descendant_values(key, at).await
+    }
+
+    async fn genesis_hash(&self) -> Result<HashFor<T>, Error> {
+        self.inner.genesis_hash().await
+    }
+
+    async fn block_header(&self, at: HashFor<T>) -> Result<Option<T::Header>, Error> {
+        let key = CacheKey::Header(at.as_ref().to_vec());
+        match self.cache.get(at.as_ref(), &key) {
+            Some(Some(CacheValue::Header(bytes))) => Ok(Some(T::Header::decode(&mut &*bytes)?)),
+            Some(_) => {
+                let header = self.inner.block_header(at).await?;
+                if let Some(header) = &header {
+                    self.cache.put(key, CacheValue::Header(header.encode()));
+                }
+                Ok(header)
+            }
+            None => self.inner.block_header(at).await,
+        }
+    }
+
+    async fn block_body(&self, at: HashFor<T>) -> Result<Option<Vec<Vec<u8>>>, Error> {
+        let key = CacheKey::Body(at.as_ref().to_vec());
+        match self.cache.get(at.as_ref(), &key) {
+            Some(Some(CacheValue::Body(body))) => Ok(Some(body)),
+            Some(_) => {
+                let body = self.inner.block_body(at).await?;
+                if let Some(body) = &body {
+                    self.cache.put(key, CacheValue::Body(body.clone()));
+                }
+                Ok(body)
+            }
+            None => self.inner.block_body(at).await,
+        }
+    }
+
+    async fn latest_finalized_block_ref(&self) -> Result<BlockRef<HashFor<T>>, Error> {
+        let block_ref = self.inner.latest_finalized_block_ref().await?;
+        self.cache.mark_finalized(block_ref.hash().as_ref());
+        Ok(block_ref)
+    }
+
+    async fn current_runtime_version(&self) -> Result<RuntimeVersion, Error> {
+        self.inner.current_runtime_version().await
+    }
+
+    async fn stream_runtime_version(&self) -> Result<StreamOfResults<RuntimeVersion>, Error> {
+        self.inner.stream_runtime_version().await
+    }
+
+    async fn stream_all_block_headers(
+        &self,
+        hasher: T::Hasher,
+    ) -> Result<StreamOfResults<(T::Header, BlockRef<HashFor<T>>)>, Error> {
+        self.inner.stream_all_block_headers(hasher).await
+    }
+
+    async fn stream_best_block_headers(
+        &self,
+        hasher: T::Hasher,
+    ) -> Result<StreamOfResults<(T::Header, BlockRef<HashFor<T>>)>, Error> {
+        self.inner.stream_best_block_headers(hasher).await
+    }
+
+    async fn stream_finalized_block_headers(
+        &self,
+        hasher: T::Hasher,
+    ) -> Result<StreamOfResults<(T::Header, BlockRef<HashFor<T>>)>, Error> {
+        let cache = self.cache.clone();
+        let stream = self
+            .inner
+            .stream_finalized_block_headers(hasher)
+            .await?
+            .inspect(move |res| {
+                if let Ok((_, block_ref)) = res {
+                    cache.mark_finalized(block_ref.hash().as_ref());
+                }
+            });
+        Ok(StreamOf::new(Box::pin(stream)))
+    }
+
+    async fn submit_transaction(
+        &self,
+        bytes: &[u8],
+    ) -> Result<StreamOfResults<TransactionStatus<HashFor<T>>>, Error> {
+        self.inner.submit_transaction(bytes).await
+    }
+
+    async fn call(
+        &self,
+        method: &str,
+        call_parameters: Option<&[u8]>,
+        at: HashFor<T>,
+    ) -> Result<Vec<u8>, Error> {
+        let key = CacheKey::Call(
+            at.as_ref().to_vec(),
+            method.to_owned(),
+            call_parameters.map(|p| p.to_vec()),
+        );
+        match self.cache.get(at.as_ref(), &key) {
+            Some(Some(CacheValue::Call(bytes))) => Ok(bytes),
+            Some(_) => {
+                let bytes = self.inner.call(method, call_parameters, at).await?;
+                self.cache.put(key, CacheValue::Call(bytes.clone()));
+                Ok(bytes)
+            }
+            None => self.inner.call(method, call_parameters, at).await,
+        }
+    }
+
+    async fn pending_transactions(&self) -> Result<Vec<Vec<u8>>, Error> {
+        self.inner.pending_transactions().await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::PolkadotConfig;
+    use crate::backend::legacy::LegacyBackend;
+    use primitive_types::H256;
+    use std::sync::atomic::AtomicUsize;
+    use subxt_rpcs::client::{MockRpcClient, mock_rpc_client::Json};
+
+    #[tokio::test]
+    async fn caches_finalized_block_data() {
+        let finalized = H256::random();
+        let not_finalized = H256::random();
+        let num_calls = Arc::new(AtomicUsize::new(0));
+        let num_storage_calls = Arc::new(AtomicUsize::new(0));
+
+        let calls = num_calls.clone();
+        let storage_calls = num_storage_calls.clone();
+        let rpc_client = MockRpcClient::builder()
+            .method_handler("chain_getFinalizedHead", move |_params| async move {
+                Json(finalized)
+            })
+            .method_handler("state_call", move |_params| {
+                calls.fetch_add(1, Ordering::Relaxed);
+                async move { Json("0x0102") }
+            })
+            .method_handler("state_getStorage", move |_params| {
+                storage_calls.fetch_add(1, Ordering::Relaxed);
+                async move { Json("0x03") }
+            })
+            .build();
+
+        let backend = CachingBackend::builder()
+            .build(LegacyBackend::<PolkadotConfig>::builder().build(rpc_client));
+
+        // Nothing is cached until we know that the block is finalized.
+        for _ in 0..2 {
+            let res = Backend::<PolkadotConfig>::call(&backend, "Foo", None, finalized).await;
+            assert_eq!(res.unwrap(), vec![1, 2]);
+        }
+        assert_eq!(num_calls.load(Ordering::Relaxed), 2);
+        assert_eq!(backend.stats(), CacheStats::default());
+
+        Backend::<PolkadotConfig>::latest_finalized_block_ref(&backend)
+            .await
+            .unwrap();
+        for _ in 0..3 {
+            let res = Backend::<PolkadotConfig>::call(&backend, "Foo", None, finalized).await;
+            assert_eq!(res.unwrap(), vec![1, 2]);
+        }
+        assert_eq!(num_calls.load(Ordering::Relaxed), 3);
+        assert_eq!(backend.stats(), CacheStats { hits: 2, misses: 1 });
+
+        // Other blocks still aren't cached.
+        Backend::<PolkadotConfig>::call(&backend, "Foo", None, not_finalized)
+            .await
+            .unwrap();
+        assert_eq!(num_calls.load(Ordering::Relaxed), 4);
+
+        // Storage values are cached per key.
+        for _ in 0..2 {
+            let values: Vec<_> = Backend::<PolkadotConfig>::storage_fetch_values(
+                &backend,
+                vec![vec![1], vec![2]],
+                finalized,
+            )
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+            assert_eq!(values.len(), 2);
+            assert_eq!(values[1].key, vec![2]);
+            assert_eq!(values[1].value, vec![3]);
+        }
+        assert_eq!(num_storage_calls.load(Ordering::Relaxed), 2);
+        assert_eq!(backend.stats(), CacheStats { hits: 4, misses: 3 });
+    }
+
+    #[tokio::test]
+    async fn evicts_entries_to_stay_within_byte_budget() {
+        let finalized = H256::random();
+        let num_calls = Arc::new(AtomicUsize::new(0));
+
+        let calls = num_calls.clone();
+        let rpc_client = MockRpcClient::builder()
+            .method_handler("chain_getFinalizedHead", move |_params| async move {
+                Json(finalized)
+            })
+            .method_handler("state_call", move |params| {
+                calls.fetch_add(1, Ordering::Relaxed);
+                // Return a large value for the "Big" method, and a small one otherwise.
+                let big = params.is_some_and(|p| p.get().contains("Big"));
+                async move {
+                    Json(if big {
+                        format!("0x{}", "00".repeat(64))
+                    } else {
+                        "0x0102".to_owned()
+                    })
+                }
+            })
+            .build();
+
+        // Each small call result takes up 32 (block hash) + 3 (method name) + 2 bytes.
+        let backend = CachingBackend::builder()
+            .max_bytes(NonZeroUsize::new(80).unwrap())
+            .build(LegacyBackend::<PolkadotConfig>::builder().build(rpc_client));
+        Backend::<PolkadotConfig>::latest_finalized_block_ref(&backend)
+            .await
+            .unwrap();
+        let call = |method| Backend::<PolkadotConfig>::call(&backend, method, None, finalized);
+
+        call("Foo").await.unwrap();
+        call("Bar").await.unwrap();
+        call("Foo").await.unwrap();
+        assert_eq!(num_calls.load(Ordering::Relaxed), 2);
+
+        // There isn't room for a third entry, so the least recently used one is evicted.
+        call("Baz").await.unwrap();
+        call("Foo").await.unwrap();
+        assert_eq!(num_calls.load(Ordering::Relaxed), 3);
+        call("Bar").await.unwrap();
+        assert_eq!(num_calls.load(Ordering::Relaxed), 4);
+
+        // Values larger than the budget are never cached.
+        call("Big").await.unwrap();
+        call("Big").await.unwrap();
+        assert_eq!(num_calls.load(Ordering::Relaxed), 6);
+
+        // Clearing the cache empties it, but blocks are still known to be finalized.
+        backend.clear();
+        call("Foo").await.unwrap();
+        call("Foo").await.unwrap();
+        assert_eq!(num_calls.load(Ordering::Relaxed), 7);
+    }
+}
@@ -2,6 +2,7 @@
 // This file is dual-licensed as Apache-2.0 or GPL-3.0.
 // see LICENSE for license details.
 
+use crate::backend::utils::RetryPolicy;
 use crate::config::{Config, HashFor};
 use crate::error::Error;
 use futures::{FutureExt, Stream, StreamExt, TryStreamExt};
@@ -98,14 +99,21 @@ impl<Hash> FollowStream<Hash> {
         }
     }
 
-    /// Create a new [`FollowStream`] given the RPC methods.
-    pub fn from_methods<T: Config>(methods: ChainHeadRpcMethods<T>) -> FollowStream<HashFor<T>> {
+    /// Create a new [`FollowStream`] given the RPC methods, and the policy to use when
+    /// retrying attempts to subscribe.
+    pub fn from_methods<T: Config>(
+        methods: ChainHeadRpcMethods<T>,
+        retry_policy: RetryPolicy,
+    ) -> FollowStream<HashFor<T>> {
         FollowStream {
             stream_getter: Box::new(move || {
                 let methods = methods.clone();
+                let retry_policy = retry_policy.clone();
                 Box::pin(async move {
                     // Make the RPC call:
-                    let stream = methods.chainhead_v1_follow(true).await?;
+                    let stream = retry_policy
+                        .retry(|| async { Ok(methods.chainhead_v1_follow(true).await?) })
+                        .await?;
                     // Extract the subscription ID:
                     let Some(sub_id) = stream.subscription_id().map(ToOwned::to_owned) else {
                         return Err(Error::Other(
@@ -56,6 +56,9 @@ impl<H: Hash> Stream for FollowStreamDriver<H> {
             Poll::Ready(Some(Ok(item))) => {
                 // Push item to any subscribers.
                 self.shared.push_item(item);
+                // Keep track of which blocks are now pinned.
+                let pinned_blocks = self.inner.pinned_blocks();
+                self.shared.set_pinned_blocks(pinned_blocks);
                 Poll::Ready(Some(Ok(())))
             }
         }
@@ -74,6 +77,11 @@ impl<H: Hash> FollowStreamDriverHandle<H> {
     pub fn subscribe(&self) -> FollowStreamDriverSubscription<H> {
         self.shared.subscribe()
     }
+
+    /// The hashes of the blocks that are currently pinned, oldest first.
+    pub fn pinned_blocks(&self) -> Vec<H> {
+        self.shared.0.lock().unwrap().pinned_blocks.clone()
+    }
 }
 
 /// A subscription to events from the [`FollowStreamDriver`]. All subscriptions
@@ -175,6 +183,8 @@ struct SharedState<H: Hash> {
     // Runtime events by block hash; we need to track these to know
     // whether the runtime has changed when we see a finalized block notification.
     seen_runtime_events: HashMap<H, RuntimeEvent>,
+    // The blocks that are currently pinned.
+    pinned_blocks: Vec<H>,
 }
 
 impl<H: Hash> Default for Shared<H> {
@@ -187,6 +197,7 @@ impl<H: Hash> Default for Shared<H> {
             current_subscription_id: None,
             seen_runtime_events: HashMap::new(),
             block_events_for_new_subscriptions: VecDeque::new(),
+            pinned_blocks: Vec::new(),
         })))
     }
 }
@@ -235,6 +246,11 @@ impl<H: Hash> Shared<H> {
         Some(items)
     }
 
+    /// Update the blocks that are currently pinned.
+    pub fn set_pinned_blocks(&self, pinned_blocks: Vec<H>) {
+        self.0.lock().unwrap().pinned_blocks = pinned_blocks;
+    }
+
     /// Push a new item out to subscribers.
     pub fn push_item(&self, item: FollowStreamMsg<BlockRef<H>>) {
         let mut shared = self.0.lock().unwrap();
@@ -23,9 +23,9 @@ pub use super::follow_stream::FollowStreamMsg;
 /// A `Stream` which builds on `FollowStream`, and handles pinning. It replaces any block hash seen in
 /// the follow events with a `BlockRef` which, when all clones are dropped, will lead to an "unpin" call
 /// for that block hash being queued. It will also automatically unpin any blocks that exceed a given max
-/// age, to try and prevent the underlying stream from ending (and _all_ blocks from being unpinned as a
-/// result). Put simply, it tries to keep every block pinned as long as possible until the block is no longer
-/// used anywhere.
+/// age or once more than a given number of blocks are pinned, to try and prevent the underlying stream from
+/// ending (and _all_ blocks from being unpinned as a result). Put simply, it tries to keep every block pinned
+/// as long as possible until the block is no longer used anywhere.
 #[derive(Debug)]
 pub struct FollowStreamUnpin<H: Hash> {
     // The underlying stream of events.
@@ -45,6 +45,8 @@ pub struct FollowStreamUnpin<H: Hash> {
     subscription_id: Option<Arc<str>>,
     // The longest period a block can be pinned for.
     max_block_life: usize,
+    // The most blocks that we'll keep pinned at once.
+    max_pinned_blocks: usize,
     // The currently seen and pinned blocks.
     pinned: HashMap<H, PinnedDetails<H>>,
     // Shared state about blocks we've flagged to unpin from elsewhere
@@ -110,6 +112,14 @@ impl<H: Hash> Stream for FollowStreamUnpin<H> {
             // React to any actual FollowEvent we get back.
             let ev = match ev {
                 FollowStreamMsg::Ready(subscription_id) => {
+                    tracing::debug!(
+                        target: "subxt",
+                        subscription_id = %subscription_id,
+                        "chainHead_v1_follow subscription started"
+                    );
+                    #[cfg(feature = "metrics")]
+                    metrics::counter!("subxt_chain_head_follow_subscriptions_total").increment(1);
+
                     // update the subscription ID we'll use to unpin things.
                     this.subscription_id = Some(subscription_id.clone().into());
 
@@ -212,6 +222,13 @@ impl<H: Hash> Stream for FollowStreamUnpin<H> {
                     }))
                 }
                 FollowStreamMsg::Event(FollowEvent::Stop) => {
+                    tracing::debug!(
+                        target: "subxt",
+                        subscription_id = this.subscription_id.as_deref(),
+                        pinned_blocks = this.pinned.len(),
+                        "chainHead_v1_follow subscription stopped; all blocks unpinned"
+                    );
+
                     // clear out "old" things that are no longer applicable since
                     // the subscription has ended (a new one will be created under the hood, at
                     // which point we'll get given a new subscription ID.
@@ -247,6 +264,13 @@ impl<H: Hash> Stream for FollowStreamUnpin<H> {
                 }
             };
 
+            // Pinning blocks above may have taken us over budget, so unpin some if so. The
+            // blocks in the event that we're about to return are never unpinned here.
+            this.unpin_blocks_over_budget(&event_block_hashes(&ev), cx.waker());
+
+            #[cfg(feature = "metrics")]
+            metrics::gauge!("subxt_chain_head_pinned_blocks").set(this.pinned.len() as f64);
+
             // Return our event.
             return Poll::Ready(Some(Ok(ev)));
         }
@@ -259,11 +283,13 @@ impl<H: Hash> FollowStreamUnpin<H> {
         follow_stream: FollowStream<H>,
         unpin_method: UnpinMethod<H>,
         max_block_life: usize,
+        max_pinned_blocks: usize,
     ) -> Self {
         Self {
             inner: follow_stream,
             unpin_method: UnpinMethodHolder(unpin_method),
             max_block_life,
+            max_pinned_blocks,
             pinned: Default::default(),
             subscription_id: None,
             next_rel_block_age: 0,
@@ -277,6 +303,7 @@ impl<H: Hash> FollowStreamUnpin<H> {
         follow_stream: FollowStream<HashFor<T>>,
         methods: ChainHeadRpcMethods<T>,
         max_block_life: usize,
+        max_pinned_blocks: usize,
     ) -> FollowStreamUnpin<HashFor<T>> {
         let unpin_method = Box::new(move |hash: HashFor<T>, sub_id: Arc<str>| {
             let methods = methods.clone();
@@ -287,7 +314,12 @@ impl<H: Hash> FollowStreamUnpin<H> {
             fut
         });
 
-        FollowStreamUnpin::new(follow_stream, unpin_method, max_block_life)
+        FollowStreamUnpin::new(
+            follow_stream,
+            unpin_method,
+            max_block_life,
+            max_pinned_blocks,
+        )
     }
 
     /// Is the block hash currently pinned.
@@ -295,6 +327,13 @@ impl<H: Hash> FollowStreamUnpin<H> {
         self.pinned.contains_key(hash)
     }
 
+    /// The hashes of all of the blocks that are currently pinned, oldest first.
+    pub fn pinned_blocks(&self) -> Vec<H> {
+        let mut pinned: Vec<_> = self.pinned.iter().collect();
+        pinned.sort_by_key(|(_, details)| details.rel_block_age);
+        pinned.into_iter().map(|(hash, _)| *hash).collect()
+    }
+
     /// Pin a block, or return the reference to an already-pinned block. If the block has been registered to
     /// be unpinned, we'll clear those flags, so that it won't be unpinned. If the unpin request has already
     /// been sent though, then the block will be unpinned.
@@ -316,15 +355,17 @@ impl<H: Hash> FollowStreamUnpin<H> {
         hash: H,
         can_be_unpinned: bool,
     ) -> BlockRef<H> {
+        // Clear any unpin flag for the block. Either the block is being handed out again, or
+        // the flag was left over from an earlier time that the block was pinned.
+        self.unpin_flags.lock().unwrap().remove(&hash);
+
         let entry = self
             .pinned
             .entry(hash)
-            // If there's already an entry, then clear any unpin_flags and update the
-            // can_be_unpinned status (this can become true but cannot become false again
-            // once true).
+            // If there's already an entry, then update the can_be_unpinned status (this can
+            // become true but cannot become false again once true).
             .and_modify(|entry| {
                 entry.can_be_unpinned = entry.can_be_unpinned || can_be_unpinned;
-                self.unpin_flags.lock().unwrap().remove(&hash);
             })
             // If there's not an entry already, make one and return it.
             .or_insert_with(|| PinnedDetails {
@@ -367,6 +408,10 @@ impl<H: Hash> FollowStreamUnpin<H> {
             }
         }
 
+        // Blocks which have already been unpinned can have been flagged if several references
+        // to them were handed out, so clear out any flags for blocks which aren't pinned.
+        unpin_flags.retain(|hash| self.pinned.contains_key(hash));
+
         // Release our lock on unpin_flags ASAP.
         drop(unpin_flags);
 
@@ -376,6 +421,7 @@ impl<H: Hash> FollowStreamUnpin<H> {
         }
 
         for hash in blocks_to_unpin {
+            tracing::trace!(target: "subxt", subscription_id = %sub_id, block_hash = ?hash, "unpinning block");
             self.pinned.remove(&hash);
             let fut = (self.unpin_method.0)(hash, sub_id.clone());
             self.unpin_futs.push(fut);
@@ -386,6 +432,96 @@ impl<H: Hash> FollowStreamUnpin<H> {
         // have it polled sooner, just in case it's slow to receive things.
         waker.wake_by_ref();
     }
+
+    /// If more blocks are pinned than `max_pinned_blocks` allows, unpin the oldest blocks that
+    /// are no longer referenced anywhere first, and then the oldest blocks that are still referenced
+    /// if necessary. This prevents the server from stopping the subscription, which would unpin
+    /// _every_ block.
+    ///
+    /// The blocks given in `keep_pinned` are about to be handed out, and so are never unpinned.
+    /// This means that more than `max_pinned_blocks` blocks can remain pinned if a single event
+    /// contains more blocks than this.
+    fn unpin_blocks_over_budget(&mut self, keep_pinned: &[H], waker: &Waker) {
+        let num_to_unpin = self.pinned.len().saturating_sub(self.max_pinned_blocks);
+        if num_to_unpin == 0 {
+            return;
+        }
+
+        // Nothing will need unpinning if there is no subscription.
+        let Some(sub_id) = &self.subscription_id else {
+            return;
+        };
+
+        // Unreferenced blocks sort before referenced ones, and older blocks before newer ones.
+        let mut candidates: Vec<_> = self
+            .pinned
+            .iter()
+            .filter(|(hash, _)| !keep_pinned.contains(hash))
+            .map(|(hash, details)| {
+                let is_referenced = Arc::strong_count(&details.block_ref.inner) > 1;
+                (is_referenced, details.rel_block_age, *hash)
+            })
+            .collect();
+        candidates.sort_unstable_by_key(|(is_referenced, rel_block_age, _)| {
+            (*is_referenced, *rel_block_age)
+        });
+
+        let blocks_to_unpin: Vec<H> = candidates
+            .into_iter()
+            .take(num_to_unpin)
+            .map(|(_, _, hash)| hash)
+            .collect();
+
+        if blocks_to_unpin.is_empty() {
+            return;
+        }
+
+        // Clear these from our unpin flags so that we don't try to unpin them again. The lock
+        // must be released before removing blocks, since dropping a BlockRef also takes it.
+        let mut unpin_flags = self.unpin_flags.lock().unwrap();
+        for hash in &blocks_to_unpin {
+            unpin_flags.remove(hash);
+        }
+        drop(unpin_flags);
+
+        for hash in blocks_to_unpin {
+            tracing::debug!(
+                target: "subxt",
+                subscription_id = %sub_id,
+                block_hash = ?hash,
+                "unpinning block to stay within the pinned block budget"
+            );
+            self.pinned.remove(&hash);
+            let fut = (self.unpin_method.0)(hash, sub_id.clone());
+            self.unpin_futs.push(fut);
+        }
+
+        waker.wake_by_ref();
+    }
+}
+
+// The hashes of the blocks that are handed out in some event.
+fn event_block_hashes<H: Hash>(ev: &FollowStreamMsg<BlockRef<H>>) -> Vec<H> {
+    match ev {
+        FollowStreamMsg::Event(FollowEvent::Initialized(details)) => details
+            .finalized_block_hashes
+            .iter()
+            .map(BlockRef::hash)
+            .collect(),
+        FollowStreamMsg::Event(FollowEvent::NewBlock(details)) => {
+            vec![details.block_hash.hash(), details.parent_block_hash.hash()]
+        }
+        FollowStreamMsg::Event(FollowEvent::BestBlockChanged(details)) => {
+            vec![details.best_block_hash.hash()]
+        }
+        FollowStreamMsg::Event(FollowEvent::Finalized(details)) => details
+            .finalized_block_hashes
+            .iter()
+            .chain(&details.pruned_block_hashes)
+            .map(BlockRef::hash)
+            .collect(),
+        _ => Vec::new(),
+    }
 }
 
 // The set of block hashes that can be unpinned when ready.
@@ -479,6 +615,20 @@ pub(super) mod test_utils {
         events: F,
         max_life: usize,
     ) -> (FollowStreamUnpin<H>, UnpinRx<H>)
+    where
+        H: Hash + 'static,
+        F: Fn() -> I + Send + 'static,
+        I: IntoIterator<Item = Result<FollowEvent<H>, Error>>,
+    {
+        test_unpin_stream_getter_with_max_pinned(events, max_life, usize::MAX)
+    }
+
+    /// Get a [`FollowStreamUnpin`] which pins at most `max_pinned` blocks from an iterator over events.
+    pub fn test_unpin_stream_getter_with_max_pinned<H, F, I>(
+        events: F,
+        max_life: usize,
+        max_pinned: usize,
+    ) -> (FollowStreamUnpin<H>, UnpinRx<H>)
     where
         H: Hash + 'static,
         F: Fn() -> I + Send + 'static,
@@ -493,7 +643,8 @@ pub(super) mod test_utils {
             Box::pin(std::future::ready(()))
         });
 
-        let follow_unpin = FollowStreamUnpin::new(follow_stream, unpin_method, max_life);
+        let follow_unpin =
+            FollowStreamUnpin::new(follow_stream, unpin_method, max_life, max_pinned);
         (follow_unpin, unpin_rx)
     }
 
@@ -555,7 +706,10 @@ mod test {
     use super::super::follow_stream::test_utils::{
         ev_best_block, ev_finalized, ev_initialized, ev_new_block,
     };
-    use super::test_utils::{assert_from_unpin_rx, ev_new_block_ref, test_unpin_stream_getter};
+    use super::test_utils::{
+        assert_from_unpin_rx, ev_new_block_ref, test_unpin_stream_getter,
+        test_unpin_stream_getter_with_max_pinned,
+    };
     use super::*;
     use crate::config::substrate::H256;
 
@@ -810,4 +964,112 @@ mod test {
         assert_from_unpin_rx(&unpin_rx, [H256::from_low_u64_le(1)]);
         unpin_rx.try_recv().expect_err("nothing unpinned yet");
     }
+
+    #[tokio::test]
+    async fn unpins_oldest_unreferenced_blocks_when_over_budget() {
+        let (mut follow_unpin, unpin_rx) = test_unpin_stream_getter_with_max_pinned(
+            || {
+                [
+                    Ok(ev_initialized(0)),
+                    Ok(ev_new_block(0, 1)),
+                    Ok(ev_new_block(1, 2)),
+                    Ok(ev_new_block(2, 3)),
+                    Err(Error::Other("ended".to_owned())),
+                ]
+            },
+            10,
+            2,
+        );
+
+        let _r = follow_unpin.next().await.unwrap().unwrap();
+
+        // Drop the references to block 0 (the initialized block and the parent of block 1).
+        let i0 = follow_unpin.next().await.unwrap().unwrap();
+        drop(i0);
+        let n1 = follow_unpin.next().await.unwrap().unwrap();
+        drop(n1);
+        unpin_rx.try_recv().expect_err("nothing unpinned yet");
+        assert_eq!(
+            follow_unpin.pinned_blocks(),
+            vec![H256::from_low_u64_le(0), H256::from_low_u64_le(1)]
+        );
+
+        // Block 2 takes us over budget, so the unreferenced block 0 is unpinned.
+        let _n2 = follow_unpin.next().await.unwrap().unwrap();
+        assert_from_unpin_rx(&unpin_rx, [H256::from_low_u64_le(0)]);
+        assert!(!follow_unpin.is_pinned(&H256::from_low_u64_le(0)));
+
+        // Block 3 takes us over budget again. Every block is still referenced, so the
+        // oldest block (1) is unpinned.
+        let _n3 = follow_unpin.next().await.unwrap().unwrap();
+        assert_from_unpin_rx(&unpin_rx, [H256::from_low_u64_le(1)]);
+        assert_eq!(
+            follow_unpin.pinned_blocks(),
+            vec![H256::from_low_u64_le(2), H256::from_low_u64_le(3)]
+        );
+    }
+
+    #[tokio::test]
+    async fn never_unpins_blocks_in_the_event_being_returned_when_over_budget() {
+        let (mut follow_unpin, unpin_rx) = test_unpin_stream_getter_with_max_pinned(
+            || {
+                [
+                    Ok(ev_initialized(0)),
+                    Ok(ev_finalized([1, 2, 3], [])),
+                    Ok(ev_new_block(3, 4)),
+                    Ok(ev_finalized([4], [])),
+                    Err(Error::Other("ended".to_owned())),
+                ]
+            },
+            10,
+            2,
+        );
+
+        let _r = follow_unpin.next().await.unwrap().unwrap();
+        let i0 = follow_unpin.next().await.unwrap().unwrap();
+        drop(i0);
+
+        // The finalized event has more blocks than the budget allows, but they are all
+        // handed back pinned, and only the unreferenced block 0 is unpinned.
+        let f = follow_unpin.next().await.unwrap().unwrap();
+        assert_from_unpin_rx(&unpin_rx, [H256::from_low_u64_le(0)]);
+        unpin_rx.try_recv().expect_err("nothing else unpinned");
+        let FollowStreamMsg::Event(FollowEvent::Finalized(details)) = &f else {
+            panic!("expected a finalized event");
+        };
+        for block_ref in &details.finalized_block_hashes {
+            assert!(follow_unpin.is_pinned(&block_ref.hash()));
+        }
+
+        // Keep a couple of references to block 1 around.
+        let f1 = details.finalized_block_hashes[0].clone();
+        let f1_clone = f1.clone();
+        drop(f);
+
+        // The next event brings us back within the budget by unpinning blocks 1 and 2,
+        // which are not in the event being returned.
+        let _n4 = follow_unpin.next().await.unwrap().unwrap();
+        assert_from_unpin_rx(
+            &unpin_rx,
+            [H256::from_low_u64_le(1), H256::from_low_u64_le(2)],
+        );
+        assert_eq!(
+            follow_unpin.pinned_blocks(),
+            vec![H256::from_low_u64_le(3), H256::from_low_u64_le(4)]
+        );
+
+        // Dropping one of the references to block 1 flags it to be unpinned, even though it
+        // no longer is. This flag is cleared on the next finalized event.
+        drop(f1_clone);
+        assert!(
+            follow_unpin
+                .unpin_flags
+                .lock()
+                .unwrap()
+                .contains(&f1.hash())
+        );
+        let _f4 = follow_unpin.next().await.unwrap().unwrap();
+        assert!(follow_unpin.unpin_flags.lock().unwrap().is_empty());
+        unpin_rx.try_recv().expect_err("nothing else unpinned");
+    }
 }
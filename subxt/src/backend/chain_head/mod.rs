@@ -10,30 +10,45 @@
 //!
 //! Everything in this module is **unstable**, meaning that it could change without
 //! warning at any time.
+//!
+//! # Metrics
+//!
+//! If the `metrics` feature is enabled, the backend records the following metrics using the
+//! [`metrics`](https://docs.rs/metrics) facade, in addition to those recorded for each RPC call
+//! (see [`subxt_rpcs::client`]):
+//!
+//! - `subxt_chain_head_pinned_blocks` (gauge): the number of blocks that are currently pinned.
+//! - `subxt_chain_head_follow_subscriptions_total` (counter): the number of `chainHead_v1_follow`
+//!   subscriptions that have been started, including those started after a `stop` event.
 
 mod follow_stream;
 mod follow_stream_driver;
 mod follow_stream_unpin;
+mod stop_operation;
 mod storage_items;
 
 use self::follow_stream_driver::FollowStreamFinalizedHeads;
 use crate::backend::{
     Backend, BlockRef, BlockRefT, RuntimeVersion, StorageResponse, StreamOf, StreamOfResults,
-    TransactionStatus, utils::retry,
+    TransactionStatus, utils::RetryPolicy,
 };
 use crate::config::{Config, Hash, HashFor};
 use crate::error::{Error, RpcError};
 use async_trait::async_trait;
 use follow_stream_driver::{FollowStreamDriver, FollowStreamDriverHandle};
+use follow_stream_unpin::FollowStreamMsg;
 use futures::future::Either;
 use futures::{Stream, StreamExt};
 use std::collections::HashMap;
 use std::task::Poll;
+use stop_operation::{OperationStopper, StopOperations};
 use storage_items::StorageItems;
 use subxt_rpcs::RpcClient;
+use subxt_rpcs::methods::LegacyRpcMethods;
 use subxt_rpcs::methods::chain_head::{
     FollowEvent, MethodResponse, RuntimeEvent, StorageQuery, StorageQueryType, StorageResultType,
 };
+use tracing::Instrument;
 
 /// Re-export RPC types and methods from [`subxt_rpcs::methods::chain_head`].
 pub mod rpc_methods {
@@ -43,9 +58,14 @@ pub mod rpc_methods {
 // Expose the RPC methods.
 pub use subxt_rpcs::methods::chain_head::ChainHeadRpcMethods;
 
+// The fewest blocks that we'll allow to be pinned at once; a new block and its parent.
+const MIN_PINNED_BLOCKS: usize = 2;
+
 /// Configure and build an [`ChainHeadBackend`].
 pub struct ChainHeadBackendBuilder<T> {
     max_block_life: usize,
+    max_pinned_blocks: usize,
+    retry_policy: RetryPolicy,
     transaction_timeout_secs: usize,
     submit_transactions_ignoring_follow_events: bool,
     _marker: std::marker::PhantomData<T>,
@@ -62,6 +82,8 @@ impl<T: Config> ChainHeadBackendBuilder<T> {
     pub fn new() -> Self {
         Self {
             max_block_life: usize::MAX,
+            max_pinned_blocks: usize::MAX,
+            retry_policy: RetryPolicy::default(),
             transaction_timeout_secs: 240,
             submit_transactions_ignoring_follow_events: false,
             _marker: std::marker::PhantomData,
@@ -74,12 +96,34 @@ impl<T: Config> ChainHeadBackendBuilder<T> {
     ///
     /// By default, we will never automatically unpin blocks, but if the number of pinned blocks that we
     /// keep hold of exceeds the number that the server can tolerate, then a `stop` event is generated and
-    /// we are forced to resubscribe, losing any pinned blocks.
+    /// we are forced to resubscribe, losing any pinned blocks. See also [`Self::max_pinned_blocks()`].
     pub fn max_block_life(mut self, max_block_life: usize) -> Self {
         self.max_block_life = max_block_life;
         self
     }
 
+    /// The maximum number of blocks that we'll keep pinned at any one time. If pinning a new block takes
+    /// us over this number, we unpin the oldest blocks that are no longer referenced anywhere first, and then
+    /// the oldest blocks that are still referenced if necessary.
+    ///
+    /// By default there is no limit. Setting this below the number of blocks that the server allows to be
+    /// pinned avoids the server generating a `stop` event, which unpins _every_ block. Use
+    /// [`ChainHeadBackend::pinned_blocks()`] to see which blocks are currently pinned.
+    ///
+    /// New blocks are reported along with their parent, and so at least 2 blocks are always allowed to be
+    /// pinned. Values lower than this are treated as 2.
+    pub fn max_pinned_blocks(mut self, max_pinned_blocks: usize) -> Self {
+        self.max_pinned_blocks = max_pinned_blocks.max(MIN_PINNED_BLOCKS);
+        self
+    }
+
+    /// Configure how requests made by the [`ChainHeadBackend`], and attempts to (re)subscribe to
+    /// `chainHead_v1_follow`, are retried when they fail. See [`RetryPolicy`] for the default behaviour.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
     /// When a transaction is submitted, we wait for events indicating it's successfully made it into a finalized
     /// block. If it takes too long for this to happen, we assume that something went wrong and that we should
     /// give up waiting.
@@ -127,27 +171,38 @@ impl<T: Config> ChainHeadBackendBuilder<T> {
         client: impl Into<RpcClient>,
     ) -> (ChainHeadBackend<T>, ChainHeadBackendDriver<T>) {
         // Construct the underlying follow_stream layers:
-        let rpc_methods = ChainHeadRpcMethods::new(client.into());
-        let follow_stream =
-            follow_stream::FollowStream::<HashFor<T>>::from_methods(rpc_methods.clone());
+        let client = client.into();
+        let legacy_methods = LegacyRpcMethods::new(client.clone());
+        let rpc_methods = ChainHeadRpcMethods::new(client);
+        let follow_stream = follow_stream::FollowStream::<HashFor<T>>::from_methods(
+            rpc_methods.clone(),
+            self.retry_policy.clone(),
+        );
         let follow_stream_unpin =
             follow_stream_unpin::FollowStreamUnpin::<HashFor<T>>::from_methods(
                 follow_stream,
                 rpc_methods.clone(),
                 self.max_block_life,
+                self.max_pinned_blocks,
             );
         let follow_stream_driver = FollowStreamDriver::new(follow_stream_unpin);
+        let (operation_stopper, stop_operations) =
+            stop_operation::stop_operations(rpc_methods.clone());
 
         // Wrap these into the backend and driver that we'll expose.
         let backend = ChainHeadBackend {
             methods: rpc_methods,
+            legacy_methods,
             follow_handle: follow_stream_driver.handle(),
+            operation_stopper,
+            retry_policy: self.retry_policy,
             transaction_timeout_secs: self.transaction_timeout_secs,
             submit_transactions_ignoring_follow_events: self
                 .submit_transactions_ignoring_follow_events,
         };
         let driver = ChainHeadBackendDriver {
             driver: follow_stream_driver,
+            stop_operations,
         };
 
         (backend, driver)
@@ -194,6 +249,7 @@ impl<T: Config> ChainHeadBackendBuilder<T> {
 #[derive(Debug)]
 pub struct ChainHeadBackendDriver<T: Config> {
     driver: FollowStreamDriver<HashFor<T>>,
+    stop_operations: StopOperations,
 }
 
 impl<T: Config> Stream for ChainHeadBackendDriver<T> {
@@ -202,17 +258,38 @@ impl<T: Config> Stream for ChainHeadBackendDriver<T> {
         mut self: std::pin::Pin<&mut Self>,
         cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Option<Self::Item>> {
+        self.stop_operations.poll(cx);
         self.driver.poll_next_unpin(cx)
     }
 }
 
+/// A change to the `chainHead_v1_follow` subscription that the [`ChainHeadBackend`] relies on.
+/// See [`ChainHeadBackend::follow_subscription_events()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FollowSubscriptionEvent {
+    /// A subscription has started.
+    Started {
+        /// The ID of the new subscription.
+        subscription_id: String,
+    },
+    /// The subscription was stopped by the server. Every block pinned up until this point has
+    /// been unpinned, and a new subscription will be started.
+    Stopped,
+}
+
 /// The chainHead backend.
 #[derive(Debug, Clone)]
 pub struct ChainHeadBackend<T: Config> {
     // RPC methods we'll want to call:
     methods: ChainHeadRpcMethods<T>,
+    // Legacy RPC methods, for things that the above can't do:
+    legacy_methods: LegacyRpcMethods<T>,
     // A handle to the chainHead_follow subscription:
     follow_handle: FollowStreamDriverHandle<HashFor<T>>,
+    // Stops operations that are no longer being waited on:
+    operation_stopper: OperationStopper,
+    // How to retry requests that fail:
+    retry_policy: RetryPolicy,
     // How long to wait until giving up on transactions:
     transaction_timeout_secs: usize,
     // Don't synchronise blocks with chainHead_follow when submitting txs:
@@ -225,6 +302,34 @@ impl<T: Config> ChainHeadBackend<T> {
         ChainHeadBackendBuilder::new()
     }
 
+    /// The hashes of the blocks that the backend currently has pinned, oldest first.
+    ///
+    /// A [`BlockRef`] for a block that is no longer pinned can no longer be used to query the node.
+    pub fn pinned_blocks(&self) -> Vec<HashFor<T>> {
+        self.follow_handle.pinned_blocks()
+    }
+
+    /// Subscribe to changes in the underlying `chainHead_v1_follow` subscription. The first event will
+    /// be [`FollowSubscriptionEvent::Started`] for the current subscription, if one is active.
+    ///
+    /// If the subscription is stopped by the server (for instance because too many blocks were pinned),
+    /// then every block is unpinned and a new subscription is started. Watching for this makes it possible
+    /// to tell why a [`BlockRef`] obtained before then can no longer be used.
+    pub fn follow_subscription_events(&self) -> StreamOf<FollowSubscriptionEvent> {
+        let events = self.follow_handle.subscribe().filter_map(|msg| {
+            let ev = match msg {
+                FollowStreamMsg::Ready(subscription_id) => {
+                    Some(FollowSubscriptionEvent::Started { subscription_id })
+                }
+                FollowStreamMsg::Event(FollowEvent::Stop) => Some(FollowSubscriptionEvent::Stopped),
+                _ => None,
+            };
+            std::future::ready(ev)
+        });
+
+        StreamOf(Box::pin(events))
+    }
+
     /// Stream block headers based on the provided filter fn
     async fn stream_headers<F>(
         &self,
@@ -282,8 +387,6 @@ impl<H: Hash + 'static> From<follow_stream_unpin::BlockRef<H>> for BlockRef<H> {
     }
 }
 
-impl<T: Config> super::sealed::Sealed for ChainHeadBackend<T> {}
-
 #[async_trait]
 impl<T: Config + Send + Sync + 'static> Backend<T> for ChainHeadBackend<T> {
     async fn storage_fetch_values(
@@ -291,34 +394,46 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for ChainHeadBackend<T> {
         keys: Vec<Vec<u8>>,
         at: HashFor<T>,
     ) -> Result<StreamOfResults<StorageResponse>, Error> {
-        retry(|| async {
-            let queries = keys.iter().map(|key| StorageQuery {
-                key: &**key,
-                query_type: StorageQueryType::Value,
-            });
+        self.retry_policy
+            .retry(|| async {
+                let queries = keys.iter().map(|key| StorageQuery {
+                    key: &**key,
+                    query_type: StorageQueryType::Value,
+                });
 
-            let storage_items =
-                StorageItems::from_methods(queries, at, &self.follow_handle, self.methods.clone())
-                    .await?;
+                let storage_items = StorageItems::from_methods(
+                    queries,
+                    at,
+                    &self.follow_handle,
+                    &self.operation_stopper,
+                    self.methods.clone(),
+                )
+                .await?;
 
-            let stream = storage_items.filter_map(async |val| {
-                let val = match val {
-                    Ok(val) => val,
-                    Err(e) => return Some(Err(e)),
-                };
+                let stream = storage_items.filter_map(async |val| {
+                    let val = match val {
+                        Ok(val) => val,
+                        Err(e) => return Some(Err(e)),
+                    };
 
-                let StorageResultType::Value(result) = val.result else {
-                    return None;
-                };
-                Some(Ok(StorageResponse {
-                    key: val.key.0,
-                    value: result.0,
-                }))
-            });
+                    let StorageResultType::Value(result) = val.result else {
+                        return None;
+                    };
+                    Some(Ok(StorageResponse {
+                        key: val.key.0,
+                        value: result.0,
+                    }))
+                });
 
-            Ok(StreamOf(Box::pin(stream)))
-        })
-        .await
+                Ok(StreamOf(Box::pin(stream)))
+            })
+            .instrument(tracing::debug_span!(
+                target: "subxt",
+                "chain_head_storage_fetch_values",
+                block_hash = ?at,
+                keys = keys.len(),
+            ))
+            .await
     }
 
     async fn storage_fetch_descendant_keys(
@@ -326,25 +441,32 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for ChainHeadBackend<T> {
         key: Vec<u8>,
         at: HashFor<T>,
     ) -> Result<StreamOfResults<Vec<u8>>, Error> {
-        retry(|| async {
-            // Ask for hashes, and then just ignore them and return the keys that come back.
-            let query = StorageQuery {
-                key: &*key,
-                query_type: StorageQueryType::DescendantsHashes,
-            };
+        self.retry_policy
+            .retry(|| async {
+                // Ask for hashes, and then just ignore them and return the keys that come back.
+                let query = StorageQuery {
+                    key: &*key,
+                    query_type: StorageQueryType::DescendantsHashes,
+                };
 
-            let storage_items = StorageItems::from_methods(
-                std::iter::once(query),
-                at,
-                &self.follow_handle,
-                self.methods.clone(),
-            )
-            .await?;
+                let storage_items = StorageItems::from_methods(
+                    std::iter::once(query),
+                    at,
+                    &self.follow_handle,
+                    &self.operation_stopper,
+                    self.methods.clone(),
+                )
+                .await?;
 
-            let storage_result_stream = storage_items.map(|val| val.map(|v| v.key.0));
-            Ok(StreamOf(Box::pin(storage_result_stream)))
-        })
-        .await
+                let storage_result_stream = storage_items.map(|val| val.map(|v| v.key.0));
+                Ok(StreamOf(Box::pin(storage_result_stream)))
+            })
+            .instrument(tracing::debug_span!(
+                target: "subxt",
+                "chain_head_storage_fetch_descendant_keys",
+                block_hash = ?at,
+            ))
+            .await
     }
 
     async fn storage_fetch_descendant_values(
@@ -352,84 +474,107 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for ChainHeadBackend<T> {
         key: Vec<u8>,
         at: HashFor<T>,
     ) -> Result<StreamOfResults<StorageResponse>, Error> {
-        retry(|| async {
-            let query = StorageQuery {
-                key: &*key,
-                query_type: StorageQueryType::DescendantsValues,
-            };
+        self.retry_policy
+            .retry(|| async {
+                let query = StorageQuery {
+                    key: &*key,
+                    query_type: StorageQueryType::DescendantsValues,
+                };
 
-            let storage_items = StorageItems::from_methods(
-                std::iter::once(query),
-                at,
-                &self.follow_handle,
-                self.methods.clone(),
-            )
-            .await?;
+                let storage_items = StorageItems::from_methods(
+                    std::iter::once(query),
+                    at,
+                    &self.follow_handle,
+                    &self.operation_stopper,
+                    self.methods.clone(),
+                )
+                .await?;
 
-            let storage_result_stream = storage_items.filter_map(async |val| {
-                let val = match val {
-                    Ok(val) => val,
-                    Err(e) => return Some(Err(e)),
-                };
+                let storage_result_stream = storage_items.filter_map(async |val| {
+                    let val = match val {
+                        Ok(val) => val,
+                        Err(e) => return Some(Err(e)),
+                    };
 
-                let StorageResultType::Value(result) = val.result else {
-                    return None;
-                };
-                Some(Ok(StorageResponse {
-                    key: val.key.0,
-                    value: result.0,
-                }))
-            });
+                    let StorageResultType::Value(result) = val.result else {
+                        return None;
+                    };
+                    Some(Ok(StorageResponse {
+                        key: val.key.0,
+                        value: result.0,
+                    }))
+                });
 
-            Ok(StreamOf(Box::pin(storage_result_stream)))
-        })
-        .await
+                Ok(StreamOf(Box::pin(storage_result_stream)))
+            })
+            .instrument(tracing::debug_span!(
+                target: "subxt",
+                "chain_head_storage_fetch_descendant_values",
+                block_hash = ?at,
+            ))
+            .await
     }
 
     async fn genesis_hash(&self) -> Result<HashFor<T>, Error> {
-        retry(|| async {
-            let genesis_hash = self.methods.chainspec_v1_genesis_hash().await?;
-            Ok(genesis_hash)
-        })
-        .await
+        self.retry_policy
+            .retry(|| async {
+                let genesis_hash = self.methods.chainspec_v1_genesis_hash().await?;
+                Ok(genesis_hash)
+            })
+            .await
     }
 
     async fn block_header(&self, at: HashFor<T>) -> Result<Option<T::Header>, Error> {
-        retry(|| async {
-            let sub_id = get_subscription_id(&self.follow_handle).await?;
-            let header = self.methods.chainhead_v1_header(&sub_id, at).await?;
-            Ok(header)
-        })
-        .await
+        self.retry_policy
+            .retry(|| async {
+                let sub_id = get_subscription_id(&self.follow_handle).await?;
+                let header = self.methods.chainhead_v1_header(&sub_id, at).await?;
+                Ok(header)
+            })
+            .instrument(tracing::debug_span!(
+                target: "subxt",
+                "chain_head_block_header",
+                block_hash = ?at,
+            ))
+            .await
     }
 
     async fn block_body(&self, at: HashFor<T>) -> Result<Option<Vec<Vec<u8>>>, Error> {
-        retry(|| async {
-            let sub_id = get_subscription_id(&self.follow_handle).await?;
-
-            // Subscribe to the body response and get our operationId back.
-            let follow_events = self.follow_handle.subscribe().events();
-            let status = self.methods.chainhead_v1_body(&sub_id, at).await?;
-            let operation_id = match status {
-                MethodResponse::LimitReached => return Err(RpcError::LimitReached.into()),
-                MethodResponse::Started(s) => s.operation_id,
-            };
-
-            // Wait for the response to come back with the correct operationId.
-            let mut exts_stream = follow_events.filter_map(|ev| {
-                let FollowEvent::OperationBodyDone(body) = ev else {
-                    return std::future::ready(None);
+        self.retry_policy
+            .retry(|| async {
+                let sub_id = get_subscription_id(&self.follow_handle).await?;
+
+                // Subscribe to the body response and get our operationId back.
+                let follow_events = self.follow_handle.subscribe().events();
+                let status = self.methods.chainhead_v1_body(&sub_id, at).await?;
+                let operation_id = match status {
+                    MethodResponse::LimitReached => return Err(RpcError::LimitReached.into()),
+                    MethodResponse::Started(s) => s.operation_id,
                 };
-                if body.operation_id != operation_id {
-                    return std::future::ready(None);
-                }
-                let exts: Vec<_> = body.value.into_iter().map(|ext| ext.0).collect();
-                std::future::ready(Some(exts))
-            });
+                let mut guard = self.operation_stopper.guard(&sub_id, &operation_id);
 
-            Ok(exts_stream.next().await)
-        })
-        .await
+                // Wait for the response to come back with the correct operationId.
+                let mut exts_stream = follow_events.filter_map(|ev| {
+                    let FollowEvent::OperationBodyDone(body) = ev else {
+                        return std::future::ready(None);
+                    };
+                    if body.operation_id != operation_id {
+                        return std::future::ready(None);
+                    }
+                    let exts: Vec<_> = body.value.into_iter().map(|ext| ext.0).collect();
+                    std::future::ready(Some(exts))
+                });
+
+                let exts = exts_stream.next().await;
+                guard.finish();
+                Ok(exts)
+            })
+            .instrument(tracing::debug_span!(
+                target: "subxt",
+                "chain_head_block_body",
+                block_hash = ?at,
+            ))
+            .await
     }
 
     async fn latest_finalized_block_ref(&self) -> Result<BlockRef<HashFor<T>>, Error> {
@@ -829,38 +974,54 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for ChainHeadBackend<T> {
         call_parameters: Option<&[u8]>,
         at: HashFor<T>,
     ) -> Result<Vec<u8>, Error> {
-        retry(|| async {
-            let sub_id = get_subscription_id(&self.follow_handle).await?;
-
-            // Subscribe to the body response and get our operationId back.
-            let follow_events = self.follow_handle.subscribe().events();
-            let call_parameters = call_parameters.unwrap_or(&[]);
-            let status = self
-                .methods
-                .chainhead_v1_call(&sub_id, at, method, call_parameters)
-                .await?;
-            let operation_id = match status {
-                MethodResponse::LimitReached => return Err(RpcError::LimitReached.into()),
-                MethodResponse::Started(s) => s.operation_id,
-            };
-
-            // Wait for the response to come back with the correct operationId.
-            let mut call_data_stream = follow_events.filter_map(|ev| {
-                let FollowEvent::OperationCallDone(body) = ev else {
-                    return std::future::ready(None);
+        self.retry_policy
+            .retry(|| async {
+                let sub_id = get_subscription_id(&self.follow_handle).await?;
+
+                // Subscribe to the body response and get our operationId back.
+                let follow_events = self.follow_handle.subscribe().events();
+                let call_parameters = call_parameters.unwrap_or(&[]);
+                let status = self
+                    .methods
+                    .chainhead_v1_call(&sub_id, at, method, call_parameters)
+                    .await?;
+                let operation_id = match status {
+                    MethodResponse::LimitReached => return Err(RpcError::LimitReached.into()),
+                    MethodResponse::Started(s) => s.operation_id,
                 };
-                if body.operation_id != operation_id {
-                    return std::future::ready(None);
-                }
-                std::future::ready(Some(body.output.0))
-            });
+                let mut guard = self.operation_stopper.guard(&sub_id, &operation_id);
 
-            call_data_stream
-                .next()
-                .await
-                .ok_or_else(|| RpcError::SubscriptionDropped.into())
-        })
-        .await
+                // Wait for the response to come back with the correct operationId.
+                let mut call_data_stream = follow_events.filter_map(|ev| {
+                    let FollowEvent::OperationCallDone(body) = ev else {
+                        return std::future::ready(None);
+                    };
+                    if body.operation_id != operation_id {
+                        return std::future::ready(None);
+                    }
+                    std::future::ready(Some(body.output.0))
+                });
+
+                let call_data = call_data_stream.next().await;
+                guard.finish();
+                call_data.ok_or_else(|| RpcError::SubscriptionDropped.into())
+            })
+            .instrument(tracing::debug_span!(
+                target: "subxt",
+                "chain_head_call",
+                block_hash = ?at,
+                method,
+            ))
+            .await
+    }
+    async fn pending_transactions(&self) -> Result<Vec<Vec<u8>>, Error> {
+        // The chainHead RPC methods offer no way to inspect the transaction pool.
+        self.retry_policy
+            .retry(|| async {
+                let res = self.legacy_methods.author_pending_extrinsics().await?;
+                Ok(res)
+            })
+            .await
     }
 }
 
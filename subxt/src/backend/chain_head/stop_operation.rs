@@ -0,0 +1,133 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Operations like `chainHead_v1_body` and `chainHead_v1_storage` keep running on the node
+//! until they are done or until `chainHead_v1_stopOperation` is called. If we stop waiting on
+//! an operation before it's done (for instance because the future waiting on it timed out or
+//! was dropped), we should tell the node to stop it, so that it's not counted towards the
+//! limit on the number of operations that can run at once.
+//!
+//! Since we can't make async calls when something is dropped, [`OperationGuard`]s send the
+//! operations to stop to the [`StopOperations`] held by the backend driver, which makes the calls.
+
+use crate::config::Config;
+use futures::channel::mpsc;
+use futures::stream::{FuturesUnordered, StreamExt};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use subxt_rpcs::methods::chain_head::ChainHeadRpcMethods;
+
+/// Create a connected [`OperationStopper`] and [`StopOperations`].
+pub fn stop_operations<T: Config>(
+    methods: ChainHeadRpcMethods<T>,
+) -> (OperationStopper, StopOperations) {
+    let stop_method: StopMethod = Box::new(move |request: StopRequest| {
+        let methods = methods.clone();
+        Box::pin(async move {
+            // The operation may have finished already, or the subscription may have
+            // stopped, so errors here aren't a problem.
+            if let Err(e) = methods
+                .chainhead_v1_stop_operation(&request.sub_id, &request.operation_id)
+                .await
+            {
+                tracing::debug!(
+                    target: "subxt",
+                    "Failed to stop chainHead operation {}: {e}",
+                    request.operation_id
+                );
+            }
+        })
+    });
+
+    let (tx, rx) = mpsc::unbounded();
+    let stop_operations = StopOperations {
+        stop_method,
+        rx,
+        stop_futs: FuturesUnordered::new(),
+    };
+    (OperationStopper(tx), stop_operations)
+}
+
+/// Hand out [`OperationGuard`]s for operations that have been started.
+#[derive(Debug, Clone)]
+pub struct OperationStopper(mpsc::UnboundedSender<StopRequest>);
+
+impl OperationStopper {
+    /// Return a guard which will stop the given operation when it's dropped, unless
+    /// [`OperationGuard::finish()`] was called first.
+    pub fn guard(&self, sub_id: &str, operation_id: &str) -> OperationGuard {
+        OperationGuard {
+            stopper: self.0.clone(),
+            request: Some(StopRequest {
+                sub_id: sub_id.to_owned(),
+                operation_id: operation_id.to_owned(),
+            }),
+        }
+    }
+}
+
+/// Stops an operation when dropped, unless it was marked as finished.
+#[derive(Debug)]
+pub struct OperationGuard {
+    stopper: mpsc::UnboundedSender<StopRequest>,
+    request: Option<StopRequest>,
+}
+
+impl OperationGuard {
+    /// The operation finished (or failed), so there is no need to stop it.
+    pub fn finish(&mut self) {
+        self.request = None;
+    }
+}
+
+impl Drop for OperationGuard {
+    fn drop(&mut self) {
+        if let Some(request) = self.request.take() {
+            tracing::debug!(
+                target: "subxt",
+                "Stopping chainHead operation {} which is no longer needed",
+                request.operation_id
+            );
+            // If the driver has gone away then there's nobody to stop the operation anyway.
+            let _ = self.stopper.unbounded_send(request);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct StopRequest {
+    sub_id: String,
+    operation_id: String,
+}
+
+type StopFut = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+type StopMethod = Box<dyn FnMut(StopRequest) -> StopFut + Send + 'static>;
+
+/// Receive operations to stop and make the `chainHead_v1_stopOperation` calls.
+pub struct StopOperations {
+    stop_method: StopMethod,
+    rx: mpsc::UnboundedReceiver<StopRequest>,
+    stop_futs: FuturesUnordered<StopFut>,
+}
+
+impl std::fmt::Debug for StopOperations {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StopOperations")
+            .field("stop_futs", &self.stop_futs.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl StopOperations {
+    /// Start any newly requested `chainHead_v1_stopOperation` calls and drive the
+    /// existing ones forwards. This never completes.
+    pub fn poll(&mut self, cx: &mut Context<'_>) {
+        while let Poll::Ready(Some(request)) = self.rx.poll_next_unpin(cx) {
+            self.stop_futs.push((self.stop_method)(request));
+        }
+
+        while let Poll::Ready(Some(())) = self.stop_futs.poll_next_unpin(cx) {}
+    }
+}
@@ -4,6 +4,7 @@
 
 use super::follow_stream_driver::FollowStreamDriverHandle;
 use super::follow_stream_unpin::BlockRef;
+use super::stop_operation::{OperationGuard, OperationStopper};
 use crate::config::{Config, HashFor};
 use crate::error::{Error, RpcError};
 use futures::{FutureExt, Stream, StreamExt};
@@ -21,6 +22,8 @@ use subxt_rpcs::methods::chain_head::{
 pub struct StorageItems<T: Config> {
     done: bool,
     operation_id: Arc<str>,
+    // Stops the operation if we're dropped before it's done.
+    operation_guard: Option<OperationGuard>,
     buffered_responses: VecDeque<StorageResult>,
     continue_call: ContinueFutGetter,
     continue_fut: Option<ContinueFut>,
@@ -35,6 +38,7 @@ impl<T: Config> StorageItems<T> {
         queries: impl Iterator<Item = StorageQuery<&[u8]>>,
         at: HashFor<T>,
         follow_handle: &FollowStreamDriverHandle<HashFor<T>>,
+        operation_stopper: &OperationStopper,
         methods: ChainHeadRpcMethods<T>,
     ) -> Result<Self, Error> {
         let sub_id = super::get_subscription_id(follow_handle).await?;
@@ -48,6 +52,7 @@ impl<T: Config> StorageItems<T> {
             MethodResponse::LimitReached => return Err(RpcError::LimitReached.into()),
             MethodResponse::Started(s) => s.operation_id.into(),
         };
+        let operation_guard = operation_stopper.guard(&sub_id, &operation_id);
 
         // A function which returns the call to continue the subscription:
         let continue_call: ContinueFutGetter = {
@@ -66,11 +71,10 @@ impl<T: Config> StorageItems<T> {
             })
         };
 
-        Ok(StorageItems::new(
-            operation_id,
-            continue_call,
-            Box::pin(follow_events),
-        ))
+        let mut storage_items =
+            StorageItems::new(operation_id, continue_call, Box::pin(follow_events));
+        storage_items.operation_guard = Some(operation_guard);
+        Ok(storage_items)
     }
 
     fn new(
@@ -82,11 +86,20 @@ impl<T: Config> StorageItems<T> {
             done: false,
             buffered_responses: VecDeque::new(),
             operation_id,
+            operation_guard: None,
             continue_call,
             continue_fut: None,
             follow_event_stream,
         }
     }
+
+    // No more items will be handed back, and the operation doesn't need stopping.
+    fn finish(&mut self) {
+        self.done = true;
+        if let Some(mut guard) = self.operation_guard.take() {
+            guard.finish();
+        }
+    }
 }
 
 pub type FollowEventStream<Hash> =
@@ -119,7 +132,7 @@ impl<T: Config> Stream for StorageItems<T> {
                             continue;
                         }
 
-                        self.done = true;
+                        self.finish();
                         return Poll::Ready(Some(Err(e)));
                     }
                     Poll::Ready(Ok(())) => {
@@ -130,7 +143,11 @@ impl<T: Config> Stream for StorageItems<T> {
 
             let ev = match self.follow_event_stream.poll_next_unpin(cx) {
                 Poll::Pending => return Poll::Pending,
-                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Ready(None) => {
+                    // The subscription has ended, and the operation along with it.
+                    self.finish();
+                    return Poll::Ready(None);
+                }
                 Poll::Ready(Some(ev)) => ev,
             };
 
@@ -144,7 +161,7 @@ impl<T: Config> Stream for StorageItems<T> {
                 }
                 FollowEvent::OperationStorageDone(id) if id.operation_id == *self.operation_id => {
                     // We're finished!
-                    self.done = true;
+                    self.finish();
                     return Poll::Ready(None);
                 }
                 FollowEvent::OperationStorageItems(items)
@@ -156,7 +173,7 @@ impl<T: Config> Stream for StorageItems<T> {
                 }
                 FollowEvent::OperationError(err) if err.operation_id == *self.operation_id => {
                     // Something went wrong obtaining storage items; mark as done and return the error.
-                    self.done = true;
+                    self.finish();
                     return Poll::Ready(Some(Err(Error::Other(err.error))));
                 }
                 _ => {
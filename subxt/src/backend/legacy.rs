@@ -6,7 +6,7 @@
 //! on the legacy RPC API methods.
 
 use self::rpc_methods::TransactionStatus as RpcTransactionStatus;
-use crate::backend::utils::{retry, retry_stream};
+use crate::backend::utils::RetryPolicy;
 use crate::backend::{
     Backend, BlockRef, RuntimeVersion, StorageResponse, StreamOf, StreamOfResults,
     TransactionStatus,
@@ -22,6 +22,7 @@ use std::collections::VecDeque;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use subxt_rpcs::RpcClient;
+use tracing::Instrument;
 
 /// Re-export legacy RPC types and methods from [`subxt_rpcs::methods::legacy`].
 pub mod rpc_methods {
@@ -34,6 +35,7 @@ pub use rpc_methods::LegacyRpcMethods;
 /// Configure and build an [`LegacyBackend`].
 pub struct LegacyBackendBuilder<T> {
     storage_page_size: u32,
+    retry_policy: RetryPolicy,
     _marker: std::marker::PhantomData<T>,
 }
 
@@ -48,6 +50,7 @@ impl<T: Config> LegacyBackendBuilder<T> {
     pub fn new() -> Self {
         Self {
             storage_page_size: 64,
+            retry_policy: RetryPolicy::default(),
             _marker: std::marker::PhantomData,
         }
     }
@@ -60,11 +63,19 @@ impl<T: Config> LegacyBackendBuilder<T> {
         self
     }
 
+    /// Configure how requests and subscriptions made by the [`LegacyBackend`] are retried
+    /// when they fail. See [`RetryPolicy`] for the default behaviour.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
     /// Given an [`RpcClient`] to use to make requests, this returns a [`LegacyBackend`],
     /// which implements the [`Backend`] trait.
     pub fn build(self, client: impl Into<RpcClient>) -> LegacyBackend<T> {
         LegacyBackend {
             storage_page_size: self.storage_page_size,
+            retry_policy: self.retry_policy,
             methods: LegacyRpcMethods::new(client.into()),
         }
     }
@@ -74,6 +85,7 @@ impl<T: Config> LegacyBackendBuilder<T> {
 #[derive(Debug)]
 pub struct LegacyBackend<T> {
     storage_page_size: u32,
+    retry_policy: RetryPolicy,
     methods: LegacyRpcMethods<T>,
 }
 
@@ -81,6 +93,7 @@ impl<T> Clone for LegacyBackend<T> {
     fn clone(&self) -> LegacyBackend<T> {
         LegacyBackend {
             storage_page_size: self.storage_page_size,
+            retry_policy: self.retry_policy.clone(),
             methods: self.methods.clone(),
         }
     }
@@ -93,8 +106,6 @@ impl<T: Config> LegacyBackend<T> {
     }
 }
 
-impl<T: Config> super::sealed::Sealed for LegacyBackend<T> {}
-
 #[async_trait]
 impl<T: Config + Send + Sync + 'static> Backend<T> for LegacyBackend<T> {
     async fn storage_fetch_values(
@@ -102,28 +113,37 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for LegacyBackend<T> {
         keys: Vec<Vec<u8>>,
         at: HashFor<T>,
     ) -> Result<StreamOfResults<StorageResponse>, Error> {
-        fn get_entry<T: Config>(
+        async fn get_entry<T: Config>(
             key: Vec<u8>,
             at: HashFor<T>,
             methods: LegacyRpcMethods<T>,
-        ) -> impl Future<Output = Result<Option<StorageResponse>, Error>> {
-            retry(move || {
-                let methods = methods.clone();
-                let key = key.clone();
-                async move {
-                    let res = methods.state_get_storage(&key, Some(at)).await?;
-                    Ok(res.map(move |value| StorageResponse { key, value }))
-                }
-            })
+            retry_policy: RetryPolicy,
+        ) -> Result<Option<StorageResponse>, Error> {
+            retry_policy
+                .retry(move || {
+                    let methods = methods.clone();
+                    let key = key.clone();
+                    async move {
+                        let res = methods.state_get_storage(&key, Some(at)).await?;
+                        Ok(res.map(move |value| StorageResponse { key, value }))
+                    }
+                })
+                .instrument(tracing::debug_span!(
+                    target: "subxt",
+                    "legacy_storage_fetch_value",
+                    block_hash = ?at,
+                ))
+                .await
         }
 
         let keys = keys.clone();
         let methods = self.methods.clone();
+        let retry_policy = self.retry_policy.clone();
 
         // For each key, return it + a future to get the result.
         let iter = keys
             .into_iter()
-            .map(move |key| get_entry(key, at, methods.clone()));
+            .map(move |key| get_entry(key, at, methods.clone(), retry_policy.clone()));
 
         let s = stream::iter(iter)
             // Resolve the future
@@ -143,6 +163,7 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for LegacyBackend<T> {
             at,
             key,
             storage_page_size: self.storage_page_size,
+            retry_policy: self.retry_policy.clone(),
             methods: self.methods.clone(),
             done: Default::default(),
             keys_fut: Default::default(),
@@ -174,6 +195,7 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for LegacyBackend<T> {
             at,
             key,
             storage_page_size: self.storage_page_size,
+            retry_policy: self.retry_policy.clone(),
             methods: self.methods.clone(),
             done: Default::default(),
             keys_fut: Default::default(),
@@ -188,70 +210,87 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for LegacyBackend<T> {
     }
 
     async fn genesis_hash(&self) -> Result<HashFor<T>, Error> {
-        retry(|| async {
-            let hash = self.methods.genesis_hash().await?;
-            Ok(hash)
-        })
-        .await
+        self.retry_policy
+            .retry(|| async {
+                let hash = self.methods.genesis_hash().await?;
+                Ok(hash)
+            })
+            .await
     }
 
     async fn block_header(&self, at: HashFor<T>) -> Result<Option<T::Header>, Error> {
-        retry(|| async {
-            let header = self.methods.chain_get_header(Some(at)).await?;
-            Ok(header)
-        })
-        .await
+        self.retry_policy
+            .retry(|| async {
+                let header = self.methods.chain_get_header(Some(at)).await?;
+                Ok(header)
+            })
+            .instrument(tracing::debug_span!(
+                target: "subxt",
+                "legacy_block_header",
+                block_hash = ?at,
+            ))
+            .await
     }
 
     async fn block_body(&self, at: HashFor<T>) -> Result<Option<Vec<Vec<u8>>>, Error> {
-        retry(|| async {
-            let Some(details) = self.methods.chain_get_block(Some(at)).await? else {
-                return Ok(None);
-            };
-            Ok(Some(
-                details.block.extrinsics.into_iter().map(|b| b.0).collect(),
+        self.retry_policy
+            .retry(|| async {
+                let Some(details) = self.methods.chain_get_block(Some(at)).await? else {
+                    return Ok(None);
+                };
+                Ok(Some(
+                    details.block.extrinsics.into_iter().map(|b| b.0).collect(),
+                ))
+            })
+            .instrument(tracing::debug_span!(
+                target: "subxt",
+                "legacy_block_body",
+                block_hash = ?at,
             ))
-        })
-        .await
+            .await
     }
 
     async fn latest_finalized_block_ref(&self) -> Result<BlockRef<HashFor<T>>, Error> {
-        retry(|| async {
-            let hash = self.methods.chain_get_finalized_head().await?;
-            Ok(BlockRef::from_hash(hash))
-        })
-        .await
+        self.retry_policy
+            .retry(|| async {
+                let hash = self.methods.chain_get_finalized_head().await?;
+                Ok(BlockRef::from_hash(hash))
+            })
+            .await
     }
 
     async fn current_runtime_version(&self) -> Result<RuntimeVersion, Error> {
-        retry(|| async {
-            let details = self.methods.state_get_runtime_version(None).await?;
-            Ok(RuntimeVersion {
-                spec_version: details.spec_version,
-                transaction_version: details.transaction_version,
+        self.retry_policy
+            .retry(|| async {
+                let details = self.methods.state_get_runtime_version(None).await?;
+                Ok(RuntimeVersion {
+                    spec_version: details.spec_version,
+                    transaction_version: details.transaction_version,
+                })
             })
-        })
-        .await
+            .await
     }
 
     async fn stream_runtime_version(&self) -> Result<StreamOfResults<RuntimeVersion>, Error> {
         let methods = self.methods.clone();
 
-        let retry_sub = retry_stream(move || {
-            let methods = methods.clone();
+        let retry_sub = self
+            .retry_policy
+            .retry_stream(move || {
+                let methods = methods.clone();
 
-            Box::pin(async move {
-                let sub = methods.state_subscribe_runtime_version().await?;
-                let sub = sub.map_err(|e| e.into()).map(|r| {
-                    r.map(|v| RuntimeVersion {
-                        spec_version: v.spec_version,
-                        transaction_version: v.transaction_version,
-                    })
-                });
-                Ok(StreamOf(Box::pin(sub)))
+                Box::pin(async move {
+                    let sub = methods.state_subscribe_runtime_version().await?;
+                    let sub = sub.map_err(|e| e.into()).map(|r| {
+                        r.map(|v| RuntimeVersion {
+                            spec_version: v.spec_version,
+                            transaction_version: v.transaction_version,
+                        })
+                    });
+                    Ok(StreamOf(Box::pin(sub)))
+                })
             })
-        })
-        .await?;
+            .await?;
 
         // For runtime version subscriptions we omit the `DisconnectedWillReconnect` error
         // because the once it resubscribes it will emit the latest runtime version.
@@ -276,20 +315,22 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for LegacyBackend<T> {
         hasher: T::Hasher,
     ) -> Result<StreamOfResults<(T::Header, BlockRef<HashFor<T>>)>, Error> {
         let methods = self.methods.clone();
-        let retry_sub = retry_stream(move || {
-            let methods = methods.clone();
-            Box::pin(async move {
-                let sub = methods.chain_subscribe_all_heads().await?;
-                let sub = sub.map_err(|e| e.into()).map(move |r| {
-                    r.map(|h| {
-                        let hash = h.hash_with(hasher);
-                        (h, BlockRef::from_hash(hash))
-                    })
-                });
-                Ok(StreamOf(Box::pin(sub)))
+        let retry_sub = self
+            .retry_policy
+            .retry_stream(move || {
+                let methods = methods.clone();
+                Box::pin(async move {
+                    let sub = methods.chain_subscribe_all_heads().await?;
+                    let sub = sub.map_err(|e| e.into()).map(move |r| {
+                        r.map(|h| {
+                            let hash = h.hash_with(hasher);
+                            (h, BlockRef::from_hash(hash))
+                        })
+                    });
+                    Ok(StreamOf(Box::pin(sub)))
+                })
             })
-        })
-        .await?;
+            .await?;
 
         Ok(retry_sub)
     }
@@ -300,20 +341,22 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for LegacyBackend<T> {
     ) -> Result<StreamOfResults<(T::Header, BlockRef<HashFor<T>>)>, Error> {
         let methods = self.methods.clone();
 
-        let retry_sub = retry_stream(move || {
-            let methods = methods.clone();
-            Box::pin(async move {
-                let sub = methods.chain_subscribe_new_heads().await?;
-                let sub = sub.map_err(|e| e.into()).map(move |r| {
-                    r.map(|h| {
-                        let hash = h.hash_with(hasher);
-                        (h, BlockRef::from_hash(hash))
-                    })
-                });
-                Ok(StreamOf(Box::pin(sub)))
+        let retry_sub = self
+            .retry_policy
+            .retry_stream(move || {
+                let methods = methods.clone();
+                Box::pin(async move {
+                    let sub = methods.chain_subscribe_new_heads().await?;
+                    let sub = sub.map_err(|e| e.into()).map(move |r| {
+                        r.map(|h| {
+                            let hash = h.hash_with(hasher);
+                            (h, BlockRef::from_hash(hash))
+                        })
+                    });
+                    Ok(StreamOf(Box::pin(sub)))
+                })
             })
-        })
-        .await?;
+            .await?;
 
         Ok(retry_sub)
     }
@@ -324,36 +367,38 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for LegacyBackend<T> {
     ) -> Result<StreamOfResults<(T::Header, BlockRef<HashFor<T>>)>, Error> {
         let this = self.clone();
 
-        let retry_sub = retry_stream(move || {
-            let this = this.clone();
-            Box::pin(async move {
-                let sub = this.methods.chain_subscribe_finalized_heads().await?;
-
-                // Get the last finalized block immediately so that the stream will emit every finalized block after this.
-                let last_finalized_block_ref = this.latest_finalized_block_ref().await?;
-                let last_finalized_block_num = this
-                    .block_header(last_finalized_block_ref.hash())
-                    .await?
-                    .map(|h| h.number().into());
-
-                // Fill in any missing blocks, because the backend may not emit every finalized block; just the latest ones which
-                // are finalized each time.
-                let sub = subscribe_to_block_headers_filling_in_gaps(
-                    this.methods.clone(),
-                    sub,
-                    last_finalized_block_num,
-                );
-                let sub = sub.map(move |r| {
-                    r.map(|h| {
-                        let hash = h.hash_with(hasher);
-                        (h, BlockRef::from_hash(hash))
-                    })
-                });
+        let retry_sub = self
+            .retry_policy
+            .retry_stream(move || {
+                let this = this.clone();
+                Box::pin(async move {
+                    let sub = this.methods.chain_subscribe_finalized_heads().await?;
+
+                    // Get the last finalized block immediately so that the stream will emit every finalized block after this.
+                    let last_finalized_block_ref = this.latest_finalized_block_ref().await?;
+                    let last_finalized_block_num = this
+                        .block_header(last_finalized_block_ref.hash())
+                        .await?
+                        .map(|h| h.number().into());
+
+                    // Fill in any missing blocks, because the backend may not emit every finalized block; just the latest ones which
+                    // are finalized each time.
+                    let sub = subscribe_to_block_headers_filling_in_gaps(
+                        this.methods.clone(),
+                        sub,
+                        last_finalized_block_num,
+                    );
+                    let sub = sub.map(move |r| {
+                        r.map(|h| {
+                            let hash = h.hash_with(hasher);
+                            (h, BlockRef::from_hash(hash))
+                        })
+                    });
 
-                Ok(StreamOf(Box::pin(sub)))
+                    Ok(StreamOf(Box::pin(sub)))
+                })
             })
-        })
-        .await?;
+            .await?;
 
         Ok(retry_sub)
     }
@@ -424,14 +469,29 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for LegacyBackend<T> {
         call_parameters: Option<&[u8]>,
         at: HashFor<T>,
     ) -> Result<Vec<u8>, Error> {
-        retry(|| async {
-            let res = self
-                .methods
-                .state_call(method, call_parameters, Some(at))
-                .await?;
-            Ok(res)
-        })
-        .await
+        self.retry_policy
+            .retry(|| async {
+                let res = self
+                    .methods
+                    .state_call(method, call_parameters, Some(at))
+                    .await?;
+                Ok(res)
+            })
+            .instrument(tracing::debug_span!(
+                target: "subxt",
+                "legacy_call",
+                block_hash = ?at,
+                method,
+            ))
+            .await
+    }
+    async fn pending_transactions(&self) -> Result<Vec<Vec<u8>>, Error> {
+        self.retry_policy
+            .retry(|| async {
+                let res = self.methods.author_pending_extrinsics().await?;
+                Ok(res)
+            })
+            .await
     }
 }
 
@@ -488,6 +548,7 @@ where
 #[allow(clippy::type_complexity)]
 pub struct StorageFetchDescendantKeysStream<T: Config> {
     methods: LegacyRpcMethods<T>,
+    retry_policy: RetryPolicy,
     key: Vec<u8>,
     at: HashFor<T>,
     // How many entries to ask for each time.
@@ -542,12 +603,7 @@ impl<T: Config> Stream for StorageFetchDescendantKeysStream<T> {
                         return Poll::Ready(Some(Ok(keys)));
                     }
                     Err(e) => {
-                        if e.is_disconnected_will_reconnect() {
-                            this.keys_fut = Some(keys_fut);
-                            continue;
-                        }
-
-                        // Error getting keys? Return it.
+                        // Error getting keys (that won't be retried)? Return it.
                         return Poll::Ready(Some(Err(e)));
                     }
                 }
@@ -559,16 +615,26 @@ impl<T: Config> Stream for StorageFetchDescendantKeysStream<T> {
             let at = this.at;
             let storage_page_size = this.storage_page_size;
             let pagination_start_key = this.pagination_start_key.clone();
+            let retry_policy = this.retry_policy.clone();
             let keys_fut = async move {
-                let keys = methods
-                    .state_get_keys_paged(
-                        &key,
-                        storage_page_size,
-                        pagination_start_key.as_deref(),
-                        Some(at),
-                    )
-                    .await?;
-                Ok(keys)
+                retry_policy
+                    .retry(|| async {
+                        let keys = methods
+                            .state_get_keys_paged(
+                                &key,
+                                storage_page_size,
+                                pagination_start_key.as_deref(),
+                                Some(at),
+                            )
+                            .await?;
+                        Ok(keys)
+                    })
+                    .instrument(tracing::debug_span!(
+                        target: "subxt",
+                        "legacy_storage_fetch_keys_page",
+                        block_hash = ?at,
+                    ))
+                    .await
             };
             this.keys_fut = Some(Box::pin(keys_fut));
         }
@@ -628,15 +694,22 @@ impl<T: Config> Stream for StorageFetchDescendantValuesStream<T> {
                 Poll::Ready(Some(Ok(keys))) => {
                     let methods = this.keys.methods.clone();
                     let at = this.keys.at;
+                    let retry_policy = this.keys.retry_policy.clone();
                     let results_fut = async move {
                         let keys = keys.iter().map(|k| &**k);
-                        let values = retry(|| async {
-                            let res = methods
-                                .state_query_storage_at(keys.clone(), Some(at))
-                                .await?;
-                            Ok(res)
-                        })
-                        .await?;
+                        let values = retry_policy
+                            .retry(|| async {
+                                let res = methods
+                                    .state_query_storage_at(keys.clone(), Some(at))
+                                    .await?;
+                                Ok(res)
+                            })
+                            .instrument(tracing::debug_span!(
+                                target: "subxt",
+                                "legacy_storage_fetch_values_page",
+                                block_hash = ?at,
+                            ))
+                            .await?;
                         let values: VecDeque<_> = values
                             .into_iter()
                             .flat_map(|v| {
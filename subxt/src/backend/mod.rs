@@ -6,8 +6,10 @@
 //! the necessary information (probably from a JSON-RPC API, but that's up to the
 //! implementation).
 
+pub mod caching;
 pub mod chain_head;
 pub mod legacy;
+pub mod timeout;
 pub mod utils;
 
 use crate::config::{Config, HashFor};
@@ -65,19 +67,50 @@ pub mod rpc {
         pub use subxt_rpcs::client::reconnecting_rpc_client;
     }
 
-    pub use subxt_rpcs::{RpcClient, RpcClientT};
-}
+    crate::macros::cfg_rate_limited_rpc_client! {
+        /// An RPC client which wraps another, limiting the rate of requests and the number of
+        /// requests and subscriptions in flight at once. Waiting transaction submissions are sent
+        /// ahead of other requests, and bulk storage reads are sent after them.
+        ///
+        /// # Example
+        ///
+        /// ```rust,no_run,standalone_crate
+        /// use std::time::Duration;
+        /// use subxt::backend::rpc::{RpcClient, rate_limited_rpc_client::RateLimitedRpcClient};
+        /// use subxt::{OnlineClient, PolkadotConfig};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let rpc = RpcClient::from_url("wss://rpc.polkadot.io").await.unwrap();
+        ///     let rpc = RateLimitedRpcClient::builder()
+        ///         .rate_limit(20, Duration::from_secs(1))
+        ///         .max_concurrent_requests(8)
+        ///         .build(rpc);
+        ///
+        ///     let api = OnlineClient::<PolkadotConfig>::from_rpc_client(rpc).await.unwrap();
+        /// }
+        /// ```
+        pub use subxt_rpcs::client::rate_limited_rpc_client;
+    }
 
-/// Prevent the backend trait being implemented externally.
-#[doc(hidden)]
-pub(crate) mod sealed {
-    pub trait Sealed {}
+    pub use subxt_rpcs::{RpcClient, RpcClientT};
 }
 
 /// This trait exposes the interface that Subxt will use to communicate with
 /// a backend. Its goal is to be as minimal as possible.
+///
+/// Subxt provides [`legacy::LegacyBackend`] and [`chain_head::ChainHeadBackend`], which talk to
+/// a node over RPC, and [`caching::CachingBackend`] and [`timeout::TimeoutBackend`], which wrap
+/// another backend. This trait can also be implemented in order to wrap other backends (for
+/// instance to log or route requests), or to provide test doubles, which can then be handed to
+/// [`crate::OnlineClient::from_backend()`].
+///
+/// # Stability
+///
+/// Methods may be added to this trait in new releases of Subxt as new functionality is needed,
+/// which will require external implementations to be updated.
 #[async_trait]
-pub trait Backend<T: Config>: sealed::Sealed + Send + Sync + 'static {
+pub trait Backend<T: Config>: Send + Sync + 'static {
     /// Fetch values from storage.
     async fn storage_fetch_values(
         &self,
@@ -151,6 +184,109 @@ pub trait Backend<T: Config>: sealed::Sealed + Send + Sync + 'static {
         call_parameters: Option<&[u8]>,
         at: HashFor<T>,
     ) -> Result<Vec<u8>, Error>;
+
+    /// Return the transactions that are currently waiting in the node's transaction pool.
+    /// Like [`Backend::block_body`], each transaction has _not_ been SCALE decoded.
+    ///
+    /// By default, this returns an error, since not every backend is able to inspect the
+    /// transaction pool.
+    async fn pending_transactions(&self) -> Result<Vec<Vec<u8>>, Error> {
+        Err(Error::Other(
+            "This backend does not support fetching pending transactions".into(),
+        ))
+    }
+}
+
+#[async_trait]
+impl<T: Config, B: Backend<T> + ?Sized> Backend<T> for Arc<B> {
+    async fn storage_fetch_values(
+        &self,
+        keys: Vec<Vec<u8>>,
+        at: HashFor<T>,
+    ) -> Result<StreamOfResults<StorageResponse>, Error> {
+        (**self).storage_fetch_values(keys, at).await
+    }
+
+    async fn storage_fetch_descendant_keys(
+        &self,
+        key: Vec<u8>,
+        at: HashFor<T>,
+    ) -> Result<StreamOfResults<Vec<u8>>, Error> {
+        (**self).storage_fetch_descendant_keys(key, at).await
+    }
+
+    async fn storage_fetch_descendant_values(
+        &self,
+        key: Vec<u8>,
+        at: HashFor<T>,
+    ) -> Result<StreamOfResults<StorageResponse>, Error> {
+        (**self).storage_fetch_descendant_values(key, at).await
+    }
+
+    async fn genesis_hash(&self) -> Result<HashFor<T>, Error> {
+        (**self).genesis_hash().await
+    }
+
+    async fn block_header(&self, at: HashFor<T>) -> Result<Option<T::Header>, Error> {
+        (**self).block_header(at).await
+    }
+
+    async fn block_body(&self, at: HashFor<T>) -> Result<Option<Vec<Vec<u8>>>, Error> {
+        (**self).block_body(at).await
+    }
+
+    async fn latest_finalized_block_ref(&self) -> Result<BlockRef<HashFor<T>>, Error> {
+        (**self).latest_finalized_block_ref().await
+    }
+
+    async fn current_runtime_version(&self) -> Result<RuntimeVersion, Error> {
+        (**self).current_runtime_version().await
+    }
+
+    async fn stream_runtime_version(&self) -> Result<StreamOfResults<RuntimeVersion>, Error> {
+        (**self).stream_runtime_version().await
+    }
+
+    async fn stream_all_block_headers(
+        &self,
+        hasher: T::Hasher,
+    ) -> Result<StreamOfResults<(T::Header, BlockRef<HashFor<T>>)>, Error> {
+        (**self).stream_all_block_headers(hasher).await
+    }
+
+    async fn stream_best_block_headers(
+        &self,
+        hasher: T::Hasher,
+    ) -> Result<StreamOfResults<(T::Header, BlockRef<HashFor<T>>)>, Error> {
+        (**self).stream_best_block_headers(hasher).await
+    }
+
+    async fn stream_finalized_block_headers(
+        &self,
+        hasher: T::Hasher,
+    ) -> Result<StreamOfResults<(T::Header, BlockRef<HashFor<T>>)>, Error> {
+        (**self).stream_finalized_block_headers(hasher).await
+    }
+
+    async fn submit_transaction(
+        &self,
+        bytes: &[u8],
+    ) -> Result<StreamOfResults<TransactionStatus<HashFor<T>>>, Error> {
+        (**self).submit_transaction(bytes).await
+    }
+
+    async fn call(
+        &self,
+        method: &str,
+        call_parameters: Option<&[u8]>,
+        at: HashFor<T>,
+    ) -> Result<Vec<u8>, Error> {
+        (**self).call(method, call_parameters, at).await
+    }
+
+    async fn pending_transactions(&self) -> Result<Vec<Vec<u8>>, Error> {
+        (**self).pending_transactions().await
+    }
 }
 
 /// helpful utility methods derived from those provided on [`Backend`]
@@ -424,6 +560,26 @@ mod test {
         type AssetId = u32;
     }
 
+    #[tokio::test]
+    async fn backends_can_be_shared_and_wrapped() {
+        let genesis_hash = random_hash();
+        let rpc_client = MockRpcClient::builder()
+            .method_handler("chain_getBlockHash", move |_params| async move {
+                Json(genesis_hash)
+            })
+            .build();
+
+        let backend: Arc<dyn Backend<Conf>> =
+            Arc::new(crate::backend::legacy::LegacyBackend::builder().build(rpc_client));
+        let wrapped = caching::CachingBackend::builder().build(backend.clone());
+
+        assert_eq!(backend.genesis_hash().await.unwrap(), genesis_hash);
+        assert_eq!(
+            Backend::<Conf>::genesis_hash(&wrapped).await.unwrap(),
+            genesis_hash
+        );
+    }
+
     mod legacy {
         use super::*;
         use crate::{
@@ -1062,5 +1218,88 @@ mod test {
                 .await;
             assert!(response.is_ok());
         }
+
+        #[tokio::test]
+        async fn follow_subscription_events_and_pinned_blocks() {
+            let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+            let rpc_client = mock_client_builder_with_ids(rx, [1, 2]).build();
+            // At least a new block and its parent can always be pinned.
+            let (backend, mut driver): (ChainHeadBackend<Conf>, _) = ChainHeadBackend::builder()
+                .max_pinned_blocks(0)
+                .build(rpc_client);
+            let mut events = backend.follow_subscription_events();
+            let started = |id| FollowSubscriptionEvent::Started {
+                subscription_id: subscription_id(id),
+            };
+
+            // Advance the driver until the subscription has started and the
+            // initialized block has been pinned.
+            let _ = driver.next().await.unwrap();
+            let _ = driver.next().await.unwrap();
+            assert_eq!(events.next().await, Some(started(1)));
+            let initialized = backend.pinned_blocks();
+            assert_eq!(initialized.len(), 1);
+
+            // New blocks are pinned, and listed after the older blocks.
+            let new_block = random_hash();
+            tx.send(FollowEvent::NewBlock(chain_head::NewBlock {
+                block_hash: new_block,
+                parent_block_hash: initialized[0],
+                new_runtime: None,
+            }))
+            .unwrap();
+            let _ = driver.next().await.unwrap();
+            assert_eq!(backend.pinned_blocks(), vec![initialized[0], new_block]);
+
+            // When the subscription is stopped, every block is unpinned.
+            tx.send(follow_event_stop()).unwrap();
+            let _ = driver.next().await.unwrap();
+            assert_eq!(events.next().await, Some(FollowSubscriptionEvent::Stopped));
+            assert!(backend.pinned_blocks().is_empty());
+
+            // A new subscription is then started.
+            let _ = driver.next().await.unwrap();
+            let _ = driver.next().await.unwrap();
+            assert_eq!(events.next().await, Some(started(2)));
+            assert_eq!(backend.pinned_blocks().len(), 1);
+        }
+
+        #[tokio::test]
+        async fn operations_are_stopped_when_requests_time_out() {
+            let (_tx, rx) = tokio::sync::mpsc::unbounded_channel();
+            let (stop_tx, mut stop_rx) = tokio::sync::mpsc::unbounded_channel();
+            let rpc_client = mock_client_builder(rx)
+                // Start the operation, but never send the result on chainHead_follow:
+                .method_handler("chainHead_v1_call", async move |_params| {
+                    Json(response_started("Id1"))
+                })
+                .method_handler("chainHead_v1_stopOperation", move |params| {
+                    let operation_id = {
+                        let params = params.as_ref().map(|p| p.get());
+                        let rpc_params = jsonrpsee::types::Params::new(params);
+                        let mut seq = rpc_params.sequence();
+                        let _sub_id = seq.next::<String>().unwrap();
+                        seq.next::<String>().unwrap()
+                    };
+                    stop_tx.send(operation_id).unwrap();
+                    async move { Json(()) }
+                })
+                .build();
+
+            let backend = timeout::TimeoutBackend::new(
+                build_backend_spawn_background(rpc_client),
+                std::time::Duration::from_millis(100),
+            );
+
+            let err = backend
+                .call("Foo_bar", None, random_hash())
+                .await
+                .unwrap_err();
+            assert!(err.is_request_timeout());
+
+            let stopped =
+                tokio::time::timeout(std::time::Duration::from_secs(5), stop_rx.recv()).await;
+            assert_eq!(stopped.unwrap().unwrap(), "Id1");
+        }
     }
 }
@@ -0,0 +1,331 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! This module exposes a [`TimeoutBackend`], which wraps another [`Backend`] and
+//! gives up on requests which take too long.
+
+use crate::backend::{
+    Backend, BlockRef, RuntimeVersion, StorageResponse, StreamOf, StreamOfResults,
+    TransactionStatus,
+};
+use crate::config::{Config, HashFor};
+use crate::error::{Error, RpcError};
+use async_trait::async_trait;
+use futures::future::{self, Either};
+use futures::{FutureExt, Stream, StreamExt};
+use futures_timer::Delay;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// A [`Backend`] which wraps another one, and returns an [`RpcError::RequestTimeout`] error
+/// for any request which takes longer than the configured timeout to complete.
+///
+/// - Requests which return a single value (for instance [`Backend::call()`], which powers runtime
+///   API calls) must complete within the timeout.
+/// - Storage requests must return a stream within the timeout, and then each item in the stream
+///   must arrive within the timeout of the last one. If an item doesn't arrive in time, the stream
+///   hands back an error and then ends.
+/// - Subscriptions (for instance [`Backend::stream_finalized_block_headers()`] and
+///   [`Backend::submit_transaction()`]) must be established within the timeout, but can then wait
+///   any length of time between items. Use [`crate::tx::TxProgress::next_with_timeout()`] to limit
+///   how long to wait for the next transaction status.
+///
+/// When a request times out, it is dropped. The [`chain_head::ChainHeadBackend`] will then tell the
+/// node to stop any operation that it had started for that request.
+///
+/// [`crate::OnlineClient::with_request_timeout()`] uses this to apply a timeout to every request
+/// made via a client.
+///
+/// [`chain_head::ChainHeadBackend`]: crate::backend::chain_head::ChainHeadBackend
+///
+/// # Example
+///
+/// ```rust,no_run,standalone_crate
+/// use std::sync::Arc;
+/// use std::time::Duration;
+/// use subxt::backend::{legacy::LegacyBackend, rpc::RpcClient, timeout::TimeoutBackend};
+/// use subxt::{OnlineClient, PolkadotConfig};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let rpc_client = RpcClient::from_url("ws://localhost:9944").await?;
+/// let backend = LegacyBackend::builder().build(rpc_client);
+/// let backend = TimeoutBackend::new(backend, Duration::from_secs(30));
+///
+/// let api = OnlineClient::<PolkadotConfig>::from_backend(Arc::new(backend)).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TimeoutBackend<B> {
+    inner: B,
+    timeout: Duration,
+}
+
+impl<B> TimeoutBackend<B> {
+    /// Wrap the given [`Backend`], giving up on requests that take longer than `timeout`.
+    pub fn new(backend: B, timeout: Duration) -> Self {
+        TimeoutBackend {
+            inner: backend,
+            timeout,
+        }
+    }
+
+    /// The timeout applied to requests.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Return a reference to the wrapped backend.
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    async fn with_timeout<R>(
+        &self,
+        fut: impl Future<Output = Result<R, Error>> + Send,
+    ) -> Result<R, Error> {
+        with_timeout(self.timeout, fut).await
+    }
+}
+
+/// Wait for the given future to complete, returning an [`RpcError::RequestTimeout`] error
+/// if it takes longer than `timeout`. The future is dropped if it times out.
+pub(crate) async fn with_timeout<R>(
+    timeout: Duration,
+    fut: impl Future<Output = Result<R, Error>>,
+) -> Result<R, Error> {
+    let fut = std::pin::pin!(fut);
+    match future::select(fut, Delay::new(timeout)).await {
+        Either::Left((res, _)) => res,
+        Either::Right(_) => Err(RpcError::RequestTimeout(timeout).into()),
+    }
+}
+
+#[async_trait]
+impl<T: Config, B: Backend<T>> Backend<T> for TimeoutBackend<B> {
+    async fn storage_fetch_values(
+        &self,
+        keys: Vec<Vec<u8>>,
+        at: HashFor<T>,
+    ) -> Result<StreamOfResults<StorageResponse>, Error> {
+        let stream = self
+            .with_timeout(self.inner.storage_fetch_values(keys, at))
+            .await?;
+        Ok(TimeoutStream::new(stream, self.timeout).into())
+    }
+
+    async fn storage_fetch_descendant_keys(
+        &self,
+        key: Vec<u8>,
+        at: HashFor<T>,
+    ) -> Result<StreamOfResults<Vec<u8>>, Error> {
+        let stream = self
+            .with_timeout(self.inner.storage_fetch_descendant_keys(key, at))
+            .await?;
+        Ok(TimeoutStream::new(stream, self.timeout).into())
+    }
+
+    async fn storage_fetch_descendant_values(
+        &self,
+        key: Vec<u8>,
+        at: HashFor<T>,
+    ) -> Result<StreamOfResults<StorageResponse>, Error> {
+        let stream = self
+            .with_timeout(self.inner.storage_fetch_descendant_values(key, at))
+            .await?;
+        Ok(TimeoutStream::new(stream, self.timeout).into())
+    }
+
+    async fn genesis_hash(&self) -> Result<HashFor<T>, Error> {
+        self.with_timeout(self.inner.genesis_hash()).await
+    }
+
+    async fn block_header(&self, at: HashFor<T>) -> Result<Option<T::Header>, Error> {
+        self.with_timeout(self.inner.block_header(at)).await
+    }
+
+    async fn block_body(&self, at: HashFor<T>) -> Result<Option<Vec<Vec<u8>>>, Error> {
+        self.with_timeout(self.inner.block_body(at)).await
+    }
+
+    async fn latest_finalized_block_ref(&self) -> Result<BlockRef<HashFor<T>>, Error> {
+        self.with_timeout(self.inner.latest_finalized_block_ref())
+            .await
+    }
+
+    async fn current_runtime_version(&self) -> Result<RuntimeVersion, Error> {
+        self.with_timeout(self.inner.current_runtime_version())
+            .await
+    }
+
+    async fn stream_runtime_version(&self) -> Result<StreamOfResults<RuntimeVersion>, Error> {
+        self.with_timeout(self.inner.stream_runtime_version()).await
+    }
+
+    async fn stream_all_block_headers(
+        &self,
+        hasher: T::Hasher,
+    ) -> Result<StreamOfResults<(T::Header, BlockRef<HashFor<T>>)>, Error> {
+        self.with_timeout(self.inner.stream_all_block_headers(hasher))
+            .await
+    }
+
+    async fn stream_best_block_headers(
+        &self,
+        hasher: T::Hasher,
+    ) -> Result<StreamOfResults<(T::Header, BlockRef<HashFor<T>>)>, Error> {
+        self.with_timeout(self.inner.stream_best_block_headers(hasher))
+            .await
+    }
+
+    async fn stream_finalized_block_headers(
+        &self,
+        hasher: T::Hasher,
+    ) -> Result<StreamOfResults<(T::Header, BlockRef<HashFor<T>>)>, Error> {
+        self.with_timeout(self.inner.stream_finalized_block_headers(hasher))
+            .await
+    }
+
+    async fn submit_transaction(
+        &self,
+        bytes: &[u8],
+    ) -> Result<StreamOfResults<TransactionStatus<HashFor<T>>>, Error> {
+        self.with_timeout(self.inner.submit_transaction(bytes))
+            .await
+    }
+
+    async fn call(
+        &self,
+        method: &str,
+        call_parameters: Option<&[u8]>,
+        at: HashFor<T>,
+    ) -> Result<Vec<u8>, Error> {
+        self.with_timeout(self.inner.call(method, call_parameters, at))
+            .await
+    }
+
+    async fn pending_transactions(&self) -> Result<Vec<Vec<u8>>, Error> {
+        self.with_timeout(self.inner.pending_transactions()).await
+    }
+}
+
+/// A stream which hands back an error and then ends if the next item
+/// takes longer than `timeout` to arrive.
+struct TimeoutStream<S> {
+    inner: Option<S>,
+    timeout: Duration,
+    delay: Option<Delay>,
+}
+
+impl<S> TimeoutStream<S> {
+    fn new(inner: S, timeout: Duration) -> Self {
+        TimeoutStream {
+            inner: Some(inner),
+            timeout,
+            delay: None,
+        }
+    }
+}
+
+impl<S, R> Stream for TimeoutStream<S>
+where
+    S: Stream<Item = Result<R, Error>> + Unpin,
+{
+    type Item = Result<R, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let Some(inner) = &mut this.inner else {
+            return Poll::Ready(None);
+        };
+
+        if let Poll::Ready(item) = inner.poll_next_unpin(cx) {
+            this.delay = None;
+            if item.is_none() {
+                this.inner = None;
+            }
+            return Poll::Ready(item);
+        }
+
+        let timeout = this.timeout;
+        let delay = this.delay.get_or_insert_with(|| Delay::new(timeout));
+        match delay.poll_unpin(cx) {
+            Poll::Ready(()) => {
+                // Drop the underlying stream so that anything it's waiting on is cancelled.
+                this.inner = None;
+                this.delay = None;
+                Poll::Ready(Some(Err(RpcError::RequestTimeout(timeout).into())))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<S, R> From<TimeoutStream<S>> for StreamOfResults<R>
+where
+    S: Stream<Item = Result<R, Error>> + Unpin + Send + 'static,
+    R: Send + 'static,
+{
+    fn from(stream: TimeoutStream<S>) -> Self {
+        StreamOf::new(Box::pin(stream))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::PolkadotConfig;
+    use crate::backend::legacy::LegacyBackend;
+    use primitive_types::H256;
+    use subxt_rpcs::client::{MockRpcClient, mock_rpc_client::Json};
+
+    fn never_responds() -> impl Future<Output = Json<()>> {
+        futures::future::pending()
+    }
+
+    #[tokio::test]
+    async fn requests_which_take_too_long_time_out() {
+        let rpc_client = MockRpcClient::builder()
+            .method_handler("state_call", |_params| never_responds())
+            .method_handler("chain_getBlockHash", |_params| async { Json(H256::zero()) })
+            .build();
+        let backend = TimeoutBackend::new(
+            LegacyBackend::<PolkadotConfig>::builder().build(rpc_client),
+            Duration::from_millis(50),
+        );
+
+        let err = backend
+            .call("Foo_bar", None, H256::zero())
+            .await
+            .unwrap_err();
+        assert!(err.is_request_timeout());
+
+        // Requests which complete in time are unaffected:
+        let genesis_hash = Backend::<PolkadotConfig>::genesis_hash(&backend)
+            .await
+            .unwrap();
+        assert_eq!(genesis_hash, H256::zero());
+    }
+
+    #[tokio::test]
+    async fn storage_streams_end_when_an_item_times_out() {
+        let items = futures::stream::iter([Ok(1), Ok(2)]).chain(futures::stream::pending());
+        let mut stream = TimeoutStream::new(items, Duration::from_millis(50));
+
+        assert_eq!(stream.next().await.unwrap().unwrap(), 1);
+        assert_eq!(stream.next().await.unwrap().unwrap(), 2);
+        assert!(
+            stream
+                .next()
+                .await
+                .unwrap()
+                .unwrap_err()
+                .is_request_timeout()
+        );
+        assert!(stream.next().await.is_none());
+    }
+}
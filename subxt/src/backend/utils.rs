@@ -4,7 +4,8 @@ use super::{StreamOf, StreamOfResults};
 use crate::error::Error;
 use futures::future::BoxFuture;
 use futures::{FutureExt, Stream, StreamExt};
-use std::{future::Future, pin::Pin, task::Poll};
+use std::{future::Future, pin::Pin, sync::Arc, task::Poll, time::Duration};
+use web_time::Instant;
 
 /// Resubscribe callback.
 type ResubscribeGetter<T> = Box<dyn FnMut() -> ResubscribeFuture<T> + Send>;
@@ -30,6 +31,7 @@ impl<T> std::fmt::Debug for PendingOrStream<T> {
 struct RetrySubscription<T> {
     resubscribe: ResubscribeGetter<T>,
     state: Option<PendingOrStream<T>>,
+    policy: RetryPolicy,
 }
 
 impl<T> std::marker::Unpin for RetrySubscription<T> {}
@@ -49,7 +51,7 @@ impl<T> Stream for RetrySubscription<T> {
             match this {
                 PendingOrStream::Stream(ref mut s) => match s.poll_next_unpin(cx) {
                     Poll::Ready(Some(Err(err))) => {
-                        if err.is_disconnected_will_reconnect() {
+                        if self.policy.should_resubscribe(&err) {
                             self.state = Some(PendingOrStream::Pending((self.resubscribe)()));
                         }
                         return Poll::Ready(Some(Err(err)));
@@ -70,7 +72,7 @@ impl<T> Stream for RetrySubscription<T> {
                         continue;
                     }
                     Poll::Ready(Err(err)) => {
-                        if err.is_disconnected_will_reconnect() {
+                        if self.policy.should_resubscribe(&err) {
                             self.state = Some(PendingOrStream::Pending((self.resubscribe)()));
                         }
                         return Poll::Ready(Some(Err(err)));
@@ -85,7 +87,240 @@ impl<T> Stream for RetrySubscription<T> {
     }
 }
 
-/// Retry a future until it doesn't return a disconnected error.
+/// Configures how a backend retries the operations that it makes (for instance fetching storage,
+/// making runtime API calls and subscribing to blocks) when they fail.
+///
+/// By default, operations are retried immediately and indefinitely when the RPC client reports that
+/// it has disconnected and will reconnect (see [`Error::is_disconnected_will_reconnect()`]), and up to
+/// 10 times when it reports that a limit was reached (see [`Error::is_rpc_limit_reached()`]). Other
+/// errors are not retried unless [`RetryPolicy::retry_if()`] is used.
+///
+/// # Example
+///
+/// ```rust,no_run,standalone_crate
+/// use std::time::Duration;
+/// use subxt::backend::{legacy::LegacyBackend, utils::RetryPolicy};
+/// use subxt::PolkadotConfig;
+///
+/// let policy = RetryPolicy::new()
+///     .max_attempts(5)
+///     .exponential_backoff(Duration::from_millis(100), Duration::from_secs(5))
+///     .with_jitter()
+///     .total_timeout(Duration::from_secs(30))
+///     .retry_if(|e| e.to_string().contains("Too many requests"));
+///
+/// let builder = LegacyBackend::<PolkadotConfig>::builder().retry_policy(policy);
+/// ```
+#[derive(Clone, Default)]
+pub struct RetryPolicy {
+    max_attempts: Option<usize>,
+    backoff: Option<(Duration, Duration)>,
+    jitter: bool,
+    total_timeout: Option<Duration>,
+    retry_if: Option<Arc<dyn Fn(&Error) -> bool + Send + Sync>>,
+}
+
+impl std::fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("backoff", &self.backoff)
+            .field("jitter", &self.jitter)
+            .field("total_timeout", &self.total_timeout)
+            .field(
+                "retry_if",
+                &self.retry_if.as_ref().map(|_| "Fn(&Error) -> bool"),
+            )
+            .finish()
+    }
+}
+
+impl RetryPolicy {
+    /// Create a new [`RetryPolicy`] with the default behaviour.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The maximum number of times that an operation will be attempted, including the
+    /// first attempt. Setting this to 1 disables retrying. By default there is no limit.
+    pub fn max_attempts(mut self, max_attempts: usize) -> Self {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    /// Wait before retrying an operation. We wait `initial` before the first retry, and then
+    /// double the wait each time, up to a maximum of `max`. By default, operations are retried
+    /// immediately.
+    pub fn exponential_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.backoff = Some((initial, max));
+        self
+    }
+
+    /// Randomize the time waited between attempts to somewhere between half of and the full
+    /// backoff time, so that many clients don't all retry at the same moment. This has no effect
+    /// unless [`RetryPolicy::exponential_backoff()`] is also used.
+    pub fn with_jitter(mut self) -> Self {
+        self.jitter = true;
+        self
+    }
+
+    /// Don't begin any new attempts once this much time has passed since the first attempt, and
+    /// instead return the last error. By default there is no limit.
+    pub fn total_timeout(mut self, total_timeout: Duration) -> Self {
+        self.total_timeout = Some(total_timeout);
+        self
+    }
+
+    /// Also retry operations which fail with errors that this function returns `true` for. For
+    /// subscriptions, this also leads to resubscribing if such an error is emitted by the stream.
+    pub fn retry_if<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&Error) -> bool + Send + Sync + 'static,
+    {
+        self.retry_if = Some(Arc::new(f));
+        self
+    }
+
+    /// Retry a future according to this policy.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run,standalone_crate
+    /// use subxt::backend::utils::RetryPolicy;
+    ///
+    /// async fn some_future() -> Result<(), subxt::error::Error> {
+    ///    Ok(())
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let result = RetryPolicy::new().max_attempts(3).retry(|| some_future()).await;
+    /// }
+    /// ```
+    pub async fn retry<T, F, R>(&self, mut retry_future: F) -> Result<R, Error>
+    where
+        F: FnMut() -> T,
+        T: Future<Output = Result<R, Error>>,
+    {
+        let started = Instant::now();
+        let mut attempts = 0;
+        let mut limit_reached_retries = 0;
+
+        loop {
+            attempts += 1;
+            let e = match retry_future().await {
+                Ok(v) => return Ok(v),
+                Err(e) => e,
+            };
+
+            if !self.is_retryable(&e, &mut limit_reached_retries) {
+                return Err(e);
+            }
+            if self.max_attempts.is_some_and(|max| attempts >= max) {
+                return Err(e);
+            }
+
+            let delay = self.backoff_delay(attempts);
+            if let Some(total_timeout) = self.total_timeout {
+                if started.elapsed() + delay >= total_timeout {
+                    return Err(e);
+                }
+            }
+            if !delay.is_zero() {
+                futures_timer::Delay::new(delay).await;
+            }
+        }
+    }
+
+    /// Create a stream according to this policy, resubscribing if the stream emits an error
+    /// that should be retried. The error is still handed back before resubscribing.
+    ///
+    /// It's important to note that this function is intended to work only for stateless subscriptions.
+    /// If the subscription takes input or modifies state, this function should not be used.
+    pub async fn retry_stream<F, R>(&self, sub_stream: F) -> Result<StreamOfResults<R>, Error>
+    where
+        F: FnMut() -> ResubscribeFuture<R> + Send + 'static + Clone,
+        R: Send + 'static,
+    {
+        let stream = self.retry(sub_stream.clone()).await?;
+
+        let policy = self.clone();
+        let resubscribe = Box::new(move || {
+            let sub_stream = sub_stream.clone();
+            let policy = policy.clone();
+            async move { policy.retry(sub_stream).await }.boxed()
+        });
+
+        // The extra Box is to encapsulate the retry subscription type
+        Ok(StreamOf::new(Box::pin(RetrySubscription {
+            state: Some(PendingOrStream::Stream(stream)),
+            resubscribe,
+            policy: self.clone(),
+        })))
+    }
+
+    fn is_retryable(&self, e: &Error, limit_reached_retries: &mut usize) -> bool {
+        const REJECTED_MAX_RETRIES: usize = 10;
+
+        if e.is_disconnected_will_reconnect() {
+            return true;
+        }
+
+        // TODO: https://github.com/paritytech/subxt/issues/1567
+        // This is a hack because, in the event of a disconnection,
+        // we may not get the correct subscription ID back on reconnecting.
+        //
+        // This is because we have a race between this future and the
+        // separate chainHead subscription, which runs in a different task.
+        // if this future is too quick, it'll be given back an old
+        // subscription ID from the chainHead subscription which has yet
+        // to reconnect and establish a new subscription ID.
+        //
+        // In the event of a wrong subscription Id being used, we happen to
+        // hand back an `RpcError::LimitReached`, and so can retry when we
+        // specifically hit that error to see if we get a new subscription ID
+        // eventually.
+        if e.is_rpc_limit_reached() && *limit_reached_retries < REJECTED_MAX_RETRIES {
+            *limit_reached_retries += 1;
+            return true;
+        }
+
+        self.retry_if.as_ref().is_some_and(|f| f(e))
+    }
+
+    fn should_resubscribe(&self, e: &Error) -> bool {
+        e.is_disconnected_will_reconnect() || self.retry_if.as_ref().is_some_and(|f| f(e))
+    }
+
+    /// How long to wait after the given number of failed attempts.
+    fn backoff_delay(&self, failed_attempts: usize) -> Duration {
+        let Some((initial, max)) = self.backoff else {
+            return Duration::ZERO;
+        };
+
+        let exponent = failed_attempts.saturating_sub(1).min(31) as u32;
+        let delay = initial.saturating_mul(2u32.pow(exponent)).min(max);
+
+        if self.jitter {
+            let half = delay / 2;
+            half + half.mul_f64(random_fraction())
+        } else {
+            delay
+        }
+    }
+}
+
+/// A pseudo-random number in the range [0, 1), which is good enough for adding jitter.
+fn random_fraction() -> f64 {
+    use std::hash::{BuildHasher, Hasher};
+    let n = std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish();
+    (n >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Retry a future until it doesn't return a disconnected error. This uses the
+/// default [`RetryPolicy`].
 ///
 /// # Example
 ///
@@ -101,48 +336,16 @@ impl<T> Stream for RetrySubscription<T> {
 ///    let result = retry(|| some_future()).await;
 /// }
 /// ```
-pub async fn retry<T, F, R>(mut retry_future: F) -> Result<R, Error>
+pub async fn retry<T, F, R>(retry_future: F) -> Result<R, Error>
 where
     F: FnMut() -> T,
     T: Future<Output = Result<R, Error>>,
 {
-    const REJECTED_MAX_RETRIES: usize = 10;
-    let mut rejected_retries = 0;
-
-    loop {
-        match retry_future().await {
-            Ok(v) => return Ok(v),
-            Err(e) => {
-                if e.is_disconnected_will_reconnect() {
-                    continue;
-                }
-
-                // TODO: https://github.com/paritytech/subxt/issues/1567
-                // This is a hack because, in the event of a disconnection,
-                // we may not get the correct subscription ID back on reconnecting.
-                //
-                // This is because we have a race between this future and the
-                // separate chainHead subscription, which runs in a different task.
-                // if this future is too quick, it'll be given back an old
-                // subscription ID from the chainHead subscription which has yet
-                // to reconnect and establish a new subscription ID.
-                //
-                // In the event of a wrong subscription Id being used, we happen to
-                // hand back an `RpcError::LimitReached`, and so can retry when we
-                // specifically hit that error to see if we get a new subscription ID
-                // eventually.
-                if e.is_rpc_limit_reached() && rejected_retries < REJECTED_MAX_RETRIES {
-                    rejected_retries += 1;
-                    continue;
-                }
-
-                return Err(e);
-            }
-        }
-    }
+    RetryPolicy::default().retry(retry_future).await
 }
 
-/// Create a retry stream that will resubscribe on disconnect.
+/// Create a retry stream that will resubscribe on disconnect. This uses the
+/// default [`RetryPolicy`].
 ///
 /// It's important to note that this function is intended to work only for stateless subscriptions.
 /// If the subscription takes input or modifies state, this function should not be used.
@@ -168,18 +371,7 @@ where
     F: FnMut() -> ResubscribeFuture<R> + Send + 'static + Clone,
     R: Send + 'static,
 {
-    let stream = retry(sub_stream.clone()).await?;
-
-    let resubscribe = Box::new(move || {
-        let sub_stream = sub_stream.clone();
-        async move { retry(sub_stream).await }.boxed()
-    });
-
-    // The extra Box is to encapsulate the retry subscription type
-    Ok(StreamOf::new(Box::pin(RetrySubscription {
-        state: Some(PendingOrStream::Stream(stream)),
-        resubscribe,
-    })))
+    RetryPolicy::default().retry_stream(sub_stream).await
 }
 
 #[cfg(test)]
@@ -234,6 +426,7 @@ mod tests {
         let retry_stream = RetrySubscription {
             state: Some(PendingOrStream::Stream(StreamOf::new(Box::pin(stream)))),
             resubscribe,
+            policy: RetryPolicy::default(),
         };
 
         let result: Vec<_> = retry_stream.collect().await;
@@ -251,6 +444,7 @@ mod tests {
         let retry_stream = RetrySubscription {
             state: Some(PendingOrStream::Stream(StreamOf::new(Box::pin(stream)))),
             resubscribe,
+            policy: RetryPolicy::default(),
         };
 
         assert_eq!(retry_stream.count().await, 1);
@@ -264,6 +458,7 @@ mod tests {
         let retry_stream = RetrySubscription {
             state: Some(PendingOrStream::Stream(StreamOf::new(Box::pin(stream)))),
             resubscribe,
+            policy: RetryPolicy::default(),
         };
 
         let result: Vec<_> = retry_stream.collect().await;
@@ -272,4 +467,89 @@ mod tests {
         assert!(matches!(result[1], Err(ref e) if e.is_disconnected_will_reconnect()));
         assert!(matches!(result[2], Err(ref e) if matches!(e, Error::Other(_))));
     }
+
+    #[tokio::test]
+    async fn retry_policy_respects_max_attempts() {
+        let mut attempts = 0;
+        let result: Result<(), Error> = RetryPolicy::new()
+            .max_attempts(3)
+            .retry(|| {
+                attempts += 1;
+                async { Err(disconnect_err()) }
+            })
+            .await;
+
+        assert!(matches!(result, Err(ref e) if e.is_disconnected_will_reconnect()));
+        assert_eq!(attempts, 3);
+    }
+
+    #[tokio::test]
+    async fn retry_policy_retries_errors_matching_predicate() {
+        let mut attempts = 0;
+        let result = RetryPolicy::new()
+            .retry_if(|e| matches!(e, Error::Other(_)))
+            .retry(|| {
+                attempts += 1;
+                let res = if attempts < 3 {
+                    Err(custom_err())
+                } else {
+                    Ok(attempts)
+                };
+                async move { res }
+            })
+            .await;
+
+        assert!(matches!(result, Ok(3)));
+
+        // Without the predicate, the error is returned immediately.
+        let mut attempts = 0;
+        let result: Result<(), Error> = RetryPolicy::new()
+            .retry(|| {
+                attempts += 1;
+                async { Err(custom_err()) }
+            })
+            .await;
+
+        assert!(matches!(result, Err(Error::Other(_))));
+        assert_eq!(attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn retry_policy_stops_after_total_timeout() {
+        let mut attempts = 0;
+        let result: Result<(), Error> = RetryPolicy::new()
+            .exponential_backoff(Duration::from_millis(20), Duration::from_millis(20))
+            .total_timeout(Duration::from_millis(50))
+            .retry(|| {
+                attempts += 1;
+                async { Err(disconnect_err()) }
+            })
+            .await;
+
+        // Attempts are made at roughly 0ms, 20ms and 40ms, after which
+        // there's not enough time left to wait before another one.
+        assert!(result.is_err());
+        assert!((2..=3).contains(&attempts));
+    }
+
+    #[test]
+    fn retry_policy_backoff_is_exponential_and_capped() {
+        let policy = RetryPolicy::new()
+            .exponential_backoff(Duration::from_millis(100), Duration::from_millis(500));
+
+        assert_eq!(policy.backoff_delay(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff_delay(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff_delay(3), Duration::from_millis(400));
+        assert_eq!(policy.backoff_delay(4), Duration::from_millis(500));
+        assert_eq!(policy.backoff_delay(100), Duration::from_millis(500));
+
+        let policy = policy.with_jitter();
+        for attempt in 1..10 {
+            let delay = policy.backoff_delay(attempt);
+            let max = RetryPolicy::new()
+                .exponential_backoff(Duration::from_millis(100), Duration::from_millis(500))
+                .backoff_delay(attempt);
+            assert!(delay >= max / 2 && delay <= max);
+        }
+    }
 }
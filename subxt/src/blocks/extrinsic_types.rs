@@ -6,18 +6,29 @@ use crate::{
     blocks::block_types::{CachedEvents, get_events},
     client::{OfflineClientT, OnlineClientT},
     config::{Config, HashFor},
-    error::Error,
+    error::{DispatchError, Error},
     events,
 };
 
+use codec::Decode;
 use derive_where::derive_where;
 use scale_decode::DecodeAsType;
 use subxt_core::blocks::{ExtrinsicDetails as CoreExtrinsicDetails, Extrinsics as CoreExtrinsics};
+#[cfg(feature = "signature-verification")]
+use {
+    super::VerifySignature,
+    crate::{
+        config::{Header, transaction_extensions::CheckMortality},
+        error::BlockError,
+        utils::Era,
+    },
+    subxt_metadata::RuntimeVersionPrefix,
+};
 
 // Re-export anything that's directly returned/used in the APIs below.
 pub use subxt_core::blocks::{
     ExtrinsicMetadataDetails, ExtrinsicTransactionExtension, ExtrinsicTransactionExtensions,
-    StaticExtrinsic,
+    SigningContext, StaticExtrinsic,
 };
 
 /// The body of a block.
@@ -241,6 +252,110 @@ where
     pub fn as_root_extrinsic<E: DecodeAsType>(&self) -> Result<E, Error> {
         self.inner.as_root_extrinsic::<E>().map_err(Into::into)
     }
+
+    /// See [`subxt_core::blocks::ExtrinsicDetails::signer_payload()`].
+    pub fn signer_payload(&self, context: &SigningContext<T>) -> Result<Vec<u8>, Error> {
+        self.inner.signer_payload(context).map_err(Into::into)
+    }
+}
+
+crate::macros::cfg_signature_verification! {
+    impl<T, C> ExtrinsicDetails<T, C>
+    where
+        T: Config,
+        T::Address: Decode,
+        T::Signature: VerifySignature<T::Address>,
+        C: OfflineClientT<T>,
+    {
+        /// Check that the signature of this extrinsic is valid, given the [`SigningContext`] that
+        /// it was signed in. Returns `Ok(false)` if the signature is not valid, and an error if the
+        /// extrinsic is not signed or the signer payload cannot be rebuilt.
+        ///
+        /// Use [`ExtrinsicDetails::verify_signature()`] to fetch the [`SigningContext`] from the node.
+        pub fn verify_signature_with(&self, context: &SigningContext<T>) -> Result<bool, Error> {
+            // This errors if the extrinsic is not signed.
+            let payload = self.signer_payload(context)?;
+            let address_bytes = self.address_bytes().expect("signed extrinsics have an address; qed");
+            let signature_bytes = self
+                .signature_bytes()
+                .expect("signed extrinsics have a signature; qed");
+            let address = T::Address::decode(&mut &*address_bytes)?;
+            let signature = T::Signature::decode(&mut &*signature_bytes)?;
+
+            Ok(signature.verify(&payload, &address))
+        }
+    }
+
+    impl<T, C> ExtrinsicDetails<T, C>
+    where
+        T: Config,
+        T::Address: Decode,
+        T::Signature: VerifySignature<T::Address>,
+        C: OnlineClientT<T>,
+    {
+        /// Check that the signature of this extrinsic is valid. The spec and transaction versions
+        /// of the runtime that executed this block, and the block that any mortal extrinsic was
+        /// signed at, are fetched from the node in order to rebuild the signer payload. Returns
+        /// `Ok(false)` if the signature is not valid.
+        ///
+        /// Extrinsics signed with metadata hash checking enabled are not supported here; use
+        /// [`ExtrinsicDetails::verify_signature_with()`] and provide the metadata hash instead.
+        pub async fn verify_signature(&self, genesis_hash: HashFor<T>) -> Result<bool, Error> {
+            // The runtime version at this block reflects any upgrade enacted by it, so we ask
+            // for the version at the parent block, which is the runtime that executed this one.
+            let backend = self.client.backend();
+            let header = backend
+                .block_header(self.block_hash)
+                .await?
+                .ok_or_else(|| Error::Block(BlockError::not_found(self.block_hash)))?;
+            let parent_hash = header.parent_hash().ok_or_else(|| {
+                Error::Other("The block header does not expose its parent hash".into())
+            })?;
+            let runtime_version = backend.call("Core_version", None, parent_hash).await?;
+            let runtime_version = RuntimeVersionPrefix::decode(&mut &*runtime_version)?;
+
+            let era = match self.transaction_extensions() {
+                Some(extensions) => extensions.find::<CheckMortality<T>>()?,
+                None => None,
+            };
+            let mortality_checkpoint = match era {
+                Some(era @ Era::Mortal { .. }) => Some(self.mortality_checkpoint(era).await?),
+                _ => None,
+            };
+
+            self.verify_signature_with(&SigningContext {
+                genesis_hash,
+                spec_version: runtime_version.spec_version,
+                transaction_version: runtime_version.transaction_version,
+                mortality_checkpoint,
+                metadata_hash: None,
+            })
+        }
+
+        // Walk back from this block to find the hash of the block that the mortality
+        // period of the extrinsic begins at.
+        async fn mortality_checkpoint(&self, era: Era) -> Result<HashFor<T>, Error> {
+            let backend = self.client.backend();
+            let not_found = |hash| Error::Block(BlockError::not_found(hash));
+
+            let mut hash = self.block_hash;
+            let mut header = backend
+                .block_header(hash)
+                .await?
+                .ok_or_else(|| not_found(hash))?;
+            let birth = era.birth(header.number().into());
+            while header.number().into() > birth {
+                hash = header.parent_hash().ok_or_else(|| {
+                    Error::Other("The block header does not expose its parent hash".into())
+                })?;
+                header = backend
+                    .block_header(hash)
+                    .await?
+                    .ok_or_else(|| not_found(hash))?;
+            }
+            Ok(hash)
+        }
+    }
 }
 
 impl<T, C> ExtrinsicDetails<T, C>
@@ -350,4 +465,77 @@ impl<T: Config> ExtrinsicEvents<T> {
     pub fn has<Ev: events::StaticEvent>(&self) -> Result<bool, Error> {
         Ok(self.find::<Ev>().next().transpose()?.is_some())
     }
+
+    /// Assuming that this extrinsic is a `Utility` pallet batch call containing `num_calls` calls
+    /// (for instance one built using [`crate::tx::Batch`]), use the `ItemCompleted`, `ItemFailed`
+    /// and `BatchInterrupted` events emitted by the `Utility` pallet to work out what happened to
+    /// each of the calls.
+    ///
+    /// **Note:** If a `batch_all` call fails, the whole extrinsic is reverted and none of these
+    /// events are emitted, so every call will be reported as [`BatchItemResult::NotExecuted`]. Use
+    /// [`crate::tx::TxInBlock::wait_for_success()`] to obtain the error in this case.
+    ///
+    /// **Note:** Batches which themselves contain batch calls can't be reliably decoded, because
+    /// the events from each batch are interleaved, and so an error is returned in this case.
+    pub fn batch_results(&self, num_calls: usize) -> Result<Vec<BatchItemResult>, Error> {
+        let metadata = self.events.metadata();
+        let mut results = Vec::with_capacity(num_calls);
+        let mut seen_batch_end = false;
+
+        for ev in self.iter() {
+            let ev = ev?;
+            if ev.pallet_name() != "Utility" {
+                continue;
+            }
+
+            match ev.variant_name() {
+                "ItemCompleted" => results.push(BatchItemResult::Completed),
+                "ItemFailed" => {
+                    let error = DispatchError::decode_from(ev.field_bytes(), metadata.clone())?;
+                    results.push(BatchItemResult::Failed(error));
+                }
+                "BatchInterrupted" => {
+                    let mut bytes = ev.field_bytes();
+                    let index = u32::decode(&mut bytes)? as usize;
+                    let error = DispatchError::decode_from(bytes, metadata.clone())?;
+                    results.resize_with(index, || BatchItemResult::NotExecuted);
+                    results.push(BatchItemResult::Failed(error));
+                }
+                _ => {}
+            }
+
+            if matches!(
+                ev.variant_name(),
+                "BatchInterrupted" | "BatchCompleted" | "BatchCompletedWithErrors"
+            ) {
+                if seen_batch_end {
+                    return Err(Error::Other(
+                        "Cannot decode the results of nested batch calls".into(),
+                    ));
+                }
+                seen_batch_end = true;
+            }
+        }
+
+        if results.len() > num_calls {
+            return Err(Error::Other(format!(
+                "Expected results for {num_calls} batched calls but found {}",
+                results.len()
+            )));
+        }
+
+        results.resize_with(num_calls, || BatchItemResult::NotExecuted);
+        Ok(results)
+    }
+}
+
+/// The outcome of a single call in a `Utility` pallet batch. See [`ExtrinsicEvents::batch_results()`].
+#[derive(Debug)]
+pub enum BatchItemResult {
+    /// The call was dispatched successfully.
+    Completed,
+    /// The call was dispatched but failed with the given error.
+    Failed(DispatchError),
+    /// The call was not dispatched, for instance because an earlier call in the batch failed.
+    NotExecuted,
 }
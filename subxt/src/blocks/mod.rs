@@ -8,14 +8,19 @@ mod block_types;
 mod blocks_client;
 mod extrinsic_types;
 
+crate::macros::cfg_signature_verification! {
+    mod verify_signature;
+    pub use verify_signature::VerifySignature;
+}
+
 /// A reference to a block.
 pub use crate::backend::BlockRef;
 
 pub use block_types::Block;
 pub use blocks_client::BlocksClient;
 pub use extrinsic_types::{
-    ExtrinsicDetails, ExtrinsicEvents, ExtrinsicTransactionExtension,
-    ExtrinsicTransactionExtensions, Extrinsics, FoundExtrinsic, StaticExtrinsic,
+    BatchItemResult, ExtrinsicDetails, ExtrinsicEvents, ExtrinsicTransactionExtension,
+    ExtrinsicTransactionExtensions, Extrinsics, FoundExtrinsic, SigningContext, StaticExtrinsic,
 };
 
 // We get account nonce info in tx_client, too, so re-use the logic:
@@ -0,0 +1,137 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use crate::utils::{AccountId32, MultiAddress, MultiSignature};
+use sp_crypto_hashing::blake2_256;
+use subxt_core::utils::AccountId20;
+use subxt_signer::{ecdsa, eth, sr25519};
+
+/// A signature which can be checked against the address of the account that created it.
+/// This is used by [`crate::blocks::ExtrinsicDetails::verify_signature()`], and is implemented
+/// for the signature and address types used by the built-in [`crate::config::Config`]s.
+pub trait VerifySignature<Address> {
+    /// Return true if this is a valid signature of `payload`, created by `address`.
+    fn verify(&self, payload: &[u8], address: &Address) -> bool;
+}
+
+impl<AccountIndex> VerifySignature<MultiAddress<AccountId32, AccountIndex>> for MultiSignature {
+    fn verify(&self, payload: &[u8], address: &MultiAddress<AccountId32, AccountIndex>) -> bool {
+        // Only account IDs can be checked without looking anything up.
+        let MultiAddress::Id(account_id) = address else {
+            return false;
+        };
+
+        match self {
+            MultiSignature::Ed25519(signature) => {
+                let Ok(public) = ed25519_zebra::VerificationKey::try_from(account_id.0) else {
+                    return false;
+                };
+                let signature = ed25519_zebra::Signature::from_bytes(signature);
+                public.verify(&signature, payload).is_ok()
+            }
+            MultiSignature::Sr25519(signature) => sr25519::verify(
+                &sr25519::Signature(*signature),
+                payload,
+                &sr25519::PublicKey(account_id.0),
+            ),
+            // ECDSA account IDs are the hash of the compressed public key.
+            MultiSignature::Ecdsa(signature) => {
+                ecdsa::recover(&ecdsa::Signature(*signature), payload)
+                    .is_some_and(|public| blake2_256(&public.0) == account_id.0)
+            }
+        }
+    }
+}
+
+impl VerifySignature<AccountId20> for eth::Signature {
+    fn verify(&self, payload: &[u8], address: &AccountId20) -> bool {
+        eth::recover(self, payload).is_some_and(|public| public.to_account_id() == *address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::SigningContext;
+    use crate::config::{DefaultExtrinsicParamsBuilder as Params, PolkadotConfig};
+    use crate::dynamic::Value;
+    use crate::test_utils::offline_client;
+    use crate::utils::H256;
+    use codec::Decode;
+
+    #[test]
+    fn verifies_signed_extrinsics() {
+        let client = offline_client();
+        let checkpoint = H256::repeat_byte(7);
+        let params = Params::new()
+            .nonce(1)
+            .mortal_from_unchecked(32, 100, checkpoint)
+            .build();
+        let remark = crate::dynamic::tx("System", "remark", vec![Value::from_bytes("hello")]);
+        let tx = client
+            .tx()
+            .create_v4_partial_offline(&remark, params)
+            .unwrap()
+            .sign(&sr25519::dev::alice());
+
+        let extrinsics = subxt_core::blocks::Extrinsics::<PolkadotConfig>::decode_from(
+            vec![tx.into_encoded()],
+            client.metadata(),
+        )
+        .unwrap();
+        let ext = extrinsics.iter().next().unwrap();
+        let address =
+            MultiAddress::<AccountId32, ()>::decode(&mut ext.address_bytes().unwrap()).unwrap();
+        let signature = MultiSignature::decode(&mut ext.signature_bytes().unwrap()).unwrap();
+
+        let context = SigningContext::<PolkadotConfig> {
+            genesis_hash: client.genesis_hash(),
+            spec_version: 1,
+            transaction_version: 1,
+            mortality_checkpoint: Some(checkpoint),
+            metadata_hash: None,
+        };
+        let payload = ext.signer_payload(&context).unwrap();
+        assert!(signature.verify(&payload, &address));
+
+        // Any difference in the implicit data leads to a different payload.
+        let context = SigningContext {
+            spec_version: 2,
+            ..context
+        };
+        let payload = ext.signer_payload(&context).unwrap();
+        assert!(!signature.verify(&payload, &address));
+    }
+
+    #[test]
+    fn verifies_each_signature_type() {
+        let payload = b"payload";
+        let other_payload = b"other payload";
+
+        let key = ed25519_zebra::SigningKey::from([1; 32]);
+        let public: [u8; 32] = ed25519_zebra::VerificationKey::from(&key).into();
+        let address = MultiAddress::<_, ()>::Id(AccountId32(public));
+        let signature = MultiSignature::Ed25519(key.sign(payload).to_bytes());
+        assert!(signature.verify(payload, &address));
+        assert!(!signature.verify(other_payload, &address));
+
+        let alice = sr25519::dev::alice();
+        let address = MultiAddress::<_, ()>::Id(alice.public_key().to_account_id());
+        let signature = MultiSignature::Sr25519(alice.sign(payload).0);
+        assert!(signature.verify(payload, &address));
+        assert!(!signature.verify(other_payload, &address));
+
+        let alice = ecdsa::dev::alice();
+        let address = MultiAddress::<_, ()>::Id(alice.public_key().to_account_id());
+        let signature = MultiSignature::Ecdsa(alice.sign(payload).0);
+        assert!(signature.verify(payload, &address));
+        assert!(!signature.verify(other_payload, &address));
+
+        let alith = eth::dev::alith();
+        let address = alith.public_key().to_account_id();
+        let signature = alith.sign(payload);
+        assert!(signature.verify(payload, &address));
+        assert!(!signature.verify(other_payload, &address));
+    }
+}
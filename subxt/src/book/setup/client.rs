@@ -25,9 +25,11 @@
 //! - [`crate::OnlineClient::from_url()`] to connect to a node at a specific URL. This uses the default Subxt
 //!   backend, and the default RPC client.
 //! - [`crate::OnlineClient::from_rpc_client()`] to instantiate the client with a [`crate::backend::rpc::RpcClient`].
-//! - [`crate::OnlineClient::from_backend()`] to instantiate Subxt using a custom backend. Currently there
-//!   is just one backend, [`crate::backend::legacy::LegacyBackend`]. This backend can be instantiated from
-//!   a [`crate::backend::rpc::RpcClient`].
+//! - [`crate::OnlineClient::from_backend()`] to instantiate Subxt using a custom backend. Subxt provides
+//!   [`crate::backend::legacy::LegacyBackend`] and [`crate::backend::chain_head::ChainHeadBackend`], which
+//!   can be instantiated from a [`crate::backend::rpc::RpcClient`], and
+//!   [`crate::backend::caching::CachingBackend`], which wraps another backend. You can also implement
+//!   [`crate::backend::Backend`] yourself, for instance to wrap one of these.
 //!
 //! [`crate::backend::rpc::RpcClient`] can itself be instantiated from anything that implements the low level
 //! [`crate::backend::rpc::RpcClientT`] trait; this allows you to decide how Subxt will attempt to talk to a node
@@ -44,6 +46,12 @@
 #![doc = include_str!("../../../examples/setup_client_custom_rpc.rs")]
 //! ```
 //!
+//! ### Writing a custom [`crate::backend::Backend`] implementation which wraps another:
+//!
+//! ```rust,ignore
+#![doc = include_str!("../../../examples/setup_client_custom_backend.rs")]
+//! ```
+//!
 //! ### Creating an [`crate::OfflineClient`]:
 //!
 //! ```rust,ignore
@@ -10,9 +10,11 @@
 
 mod offline_client;
 mod online_client;
+mod runtime_upgrade;
 
 pub use offline_client::{OfflineClient, OfflineClientT};
 pub use online_client::{
     ClientRuntimeUpdater, OnlineClient, OnlineClientT, RuntimeUpdaterStream, Update, UpgradeError,
 };
+pub use runtime_upgrade::{MetadataDiff, RuntimeUpgrade, RuntimeUpgradeHook, UpgradeDecision};
 pub use subxt_core::client::{ClientState, RuntimeVersion};
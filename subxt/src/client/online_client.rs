@@ -2,11 +2,15 @@
 // This file is dual-licensed as Apache-2.0 or GPL-3.0.
 // see LICENSE for license details.
 
+use super::runtime_upgrade::{RuntimeUpgrade, RuntimeUpgradeHook, UpgradeDecision};
 use super::{OfflineClient, OfflineClientT};
 use crate::custom_values::CustomValuesClient;
 use crate::{
     Metadata,
-    backend::{Backend, BackendExt, StreamOfResults, legacy::LegacyBackend, rpc::RpcClient},
+    backend::{
+        Backend, BackendExt, StreamOfResults, legacy::LegacyBackend, rpc::RpcClient,
+        timeout::TimeoutBackend,
+    },
     blocks::{BlockRef, BlocksClient},
     config::{Config, HashFor},
     constants::ConstantsClient,
@@ -20,6 +24,7 @@ use crate::{
 use derive_where::derive_where;
 use futures::future;
 use std::sync::{Arc, RwLock};
+use std::time::Duration;
 use subxt_core::client::{ClientState, RuntimeVersion};
 
 /// A trait representing a client that can perform
@@ -35,6 +40,8 @@ pub trait OnlineClientT<T: Config>: OfflineClientT<T> {
 pub struct OnlineClient<T: Config> {
     inner: Arc<RwLock<Inner<T>>>,
     backend: Arc<dyn Backend<T>>,
+    // If a request timeout is set, this wraps the above backend and is used instead of it.
+    timeout_backend: Option<Arc<TimeoutBackend<Arc<dyn Backend<T>>>>>,
 }
 
 #[derive_where(Debug)]
@@ -50,6 +57,7 @@ impl<T: Config> std::fmt::Debug for OnlineClient<T> {
         f.debug_struct("Client")
             .field("rpc", &"RpcClient")
             .field("inner", &self.inner)
+            .field("request_timeout", &self.request_timeout())
             .finish()
     }
 }
@@ -162,6 +170,7 @@ impl<T: Config> OnlineClient<T> {
                 hasher,
             })),
             backend,
+            timeout_backend: None,
         })
     }
 
@@ -229,6 +238,22 @@ impl<T: Config> OnlineClient<T> {
     /// });
     ///
     ///
+    /// // high level API, with a hook to observe (or delay or veto) upgrades.
+    ///
+    /// let update_task = client.updater().with_hook(|upgrade: subxt::client::RuntimeUpgrade| async move {
+    ///     println!(
+    ///         "Upgrading from spec version {} to {}; changed pallets: {:?}",
+    ///         upgrade.old_runtime_version().spec_version,
+    ///         upgrade.new_runtime_version().spec_version,
+    ///         upgrade.diff().pallets_changed(),
+    ///     );
+    ///     subxt::client::UpgradeDecision::Apply
+    /// });
+    /// tokio::spawn(async move {
+    ///     update_task.perform_runtime_updates().await;
+    /// });
+    ///
+    ///
     /// // low level API.
     ///
     /// let updater = client.updater();
@@ -251,7 +276,10 @@ impl<T: Config> OnlineClient<T> {
     /// # }
     /// ```
     pub fn updater(&self) -> ClientRuntimeUpdater<T> {
-        ClientRuntimeUpdater(self.clone())
+        ClientRuntimeUpdater {
+            client: self.clone(),
+            hooks: Vec::new(),
+        }
     }
 
     /// Return the hasher configured for hashing blocks and extrinsics.
@@ -310,9 +338,67 @@ impl<T: Config> OnlineClient<T> {
         inner.runtime_version = runtime_version;
     }
 
-    /// Return an RPC client to make raw requests with.
+    /// Return a copy of this client which gives up on any request that takes longer than
+    /// `timeout` to complete, returning an error for which [`Error::is_request_timeout()`]
+    /// is true. This replaces any timeout that was already set.
+    ///
+    /// This can be used to set a default timeout for every request made via a client, or to
+    /// override it for individual requests. The copy shares everything else with this client,
+    /// so it's cheap to create. See [`TimeoutBackend`] for details about how the timeout
+    /// applies to storage requests and subscriptions.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run,standalone_crate
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::time::Duration;
+    /// use subxt::{OnlineClient, PolkadotConfig};
+    ///
+    /// // Requests made via this client give up after 30 seconds:
+    /// let api = OnlineClient::<PolkadotConfig>::new()
+    ///     .await?
+    ///     .with_request_timeout(Duration::from_secs(30));
+    ///
+    /// // But this one gives up after 2 seconds:
+    /// let block = api
+    ///     .with_request_timeout(Duration::from_secs(2))
+    ///     .blocks()
+    ///     .at_latest()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_request_timeout(&self, timeout: Duration) -> OnlineClient<T> {
+        OnlineClient {
+            inner: self.inner.clone(),
+            backend: self.backend.clone(),
+            timeout_backend: Some(Arc::new(TimeoutBackend::new(self.backend.clone(), timeout))),
+        }
+    }
+
+    /// Return a copy of this client which doesn't apply any timeout to requests.
+    pub fn without_request_timeout(&self) -> OnlineClient<T> {
+        OnlineClient {
+            inner: self.inner.clone(),
+            backend: self.backend.clone(),
+            timeout_backend: None,
+        }
+    }
+
+    /// The timeout applied to requests made via this client, if any.
+    /// See [`OnlineClient::with_request_timeout()`].
+    pub fn request_timeout(&self) -> Option<Duration> {
+        self.timeout_backend.as_ref().map(|b| b.timeout())
+    }
+
+    /// Return an RPC client to make raw requests with. If a request timeout is set,
+    /// requests made via this will time out.
     pub fn backend(&self) -> &dyn Backend<T> {
-        &*self.backend
+        match &self.timeout_backend {
+            Some(backend) => &**backend,
+            None => &*self.backend,
+        }
     }
 
     /// Return an offline client with the same configuration as this.
@@ -395,27 +481,42 @@ impl<T: Config> OfflineClientT<T> for OnlineClient<T> {
 
 impl<T: Config> OnlineClientT<T> for OnlineClient<T> {
     fn backend(&self) -> &dyn Backend<T> {
-        &*self.backend
+        self.backend()
     }
 }
 
 /// Client wrapper for performing runtime updates. See [`OnlineClient::updater()`]
 /// for example usage.
-pub struct ClientRuntimeUpdater<T: Config>(OnlineClient<T>);
+pub struct ClientRuntimeUpdater<T: Config> {
+    client: OnlineClient<T>,
+    hooks: Vec<Box<dyn RuntimeUpgradeHook>>,
+}
 
 impl<T: Config> ClientRuntimeUpdater<T> {
+    /// Register a hook which will be consulted by [`ClientRuntimeUpdater::perform_runtime_updates()`]
+    /// before each runtime upgrade is applied, and notified after it has been applied. Hooks are
+    /// consulted in the order that they are registered. See [`RuntimeUpgradeHook`] for more.
+    pub fn with_hook(mut self, hook: impl RuntimeUpgradeHook) -> Self {
+        self.hooks.push(Box::new(hook));
+        self
+    }
+
     fn is_runtime_version_different(&self, new: &RuntimeVersion) -> bool {
-        let curr = self.0.inner.read().expect("shouldn't be poisoned");
+        let curr = self.client.inner.read().expect("shouldn't be poisoned");
         &curr.runtime_version != new
     }
 
     fn do_update(&self, update: Update) {
-        let mut writable = self.0.inner.write().expect("shouldn't be poisoned");
+        let mut writable = self.client.inner.write().expect("shouldn't be poisoned");
         writable.metadata = update.metadata;
         writable.runtime_version = update.runtime_version;
     }
 
     /// Tries to apply a new update.
+    ///
+    /// *Note:* This applies the update immediately. Any hooks registered via
+    /// [`ClientRuntimeUpdater::with_hook()`] are only consulted by
+    /// [`ClientRuntimeUpdater::perform_runtime_updates()`], and are bypassed here.
     pub fn apply_update(&self, update: Update) -> Result<(), UpgradeError> {
         if !self.is_runtime_version_different(&update.runtime_version) {
             return Err(UpgradeError::SameVersion);
@@ -426,7 +527,8 @@ impl<T: Config> ClientRuntimeUpdater<T> {
         Ok(())
     }
 
-    /// Performs runtime updates indefinitely unless encountering an error.
+    /// Performs runtime updates indefinitely unless encountering an error. Any hooks registered
+    /// via [`ClientRuntimeUpdater::with_hook()`] are consulted before each update is applied.
     ///
     /// *Note:* This will run indefinitely until it errors, so the typical usage
     /// would be to run it in a separate background task.
@@ -437,11 +539,49 @@ impl<T: Config> ClientRuntimeUpdater<T> {
         while let Some(update) = runtime_version_stream.next().await {
             let update = update?;
 
-            // This only fails if received the runtime version is the same the current runtime version
-            // which might occur because that runtime subscriptions in substrate sends out the initial
-            // value when they created and not only when runtime upgrades occurs.
-            // Thus, fine to ignore here as it strictly speaking isn't really an error
-            let _ = self.apply_update(update);
+            // The runtime version may be the same as the current runtime version, which
+            // might occur because runtime subscriptions in substrate send out the initial
+            // value when they are created and not only when runtime upgrades occur.
+            // Thus, fine to ignore here as it strictly speaking isn't really an upgrade.
+            if !self.is_runtime_version_different(&update.runtime_version) {
+                continue;
+            }
+
+            if self.hooks.is_empty() {
+                self.do_update(update);
+                continue;
+            }
+
+            let upgrade = {
+                let curr = self.client.inner.read().expect("shouldn't be poisoned");
+                RuntimeUpgrade::new(
+                    curr.runtime_version,
+                    update.runtime_version,
+                    curr.metadata.clone(),
+                    update.metadata.clone(),
+                )
+            };
+
+            let mut decision = UpgradeDecision::Apply;
+            for hook in &self.hooks {
+                decision = hook.before_upgrade(&upgrade).await;
+                if decision == UpgradeDecision::Skip {
+                    break;
+                }
+            }
+            if decision == UpgradeDecision::Skip {
+                tracing::debug!(
+                    target: "subxt",
+                    "Runtime upgrade to spec version {} skipped by hook",
+                    update.runtime_version.spec_version
+                );
+                continue;
+            }
+
+            self.do_update(update);
+            for hook in &self.hooks {
+                hook.after_upgrade(&upgrade);
+            }
         }
 
         Ok(())
@@ -454,8 +594,8 @@ impl<T: Config> ClientRuntimeUpdater<T> {
     /// to perform the actual updating.
     pub async fn runtime_updates(&self) -> Result<RuntimeUpdaterStream<T>, Error> {
         Ok(RuntimeUpdaterStream {
-            stream: self.0.backend().stream_runtime_version().await?,
-            client: self.0.clone(),
+            stream: self.client.backend().stream_runtime_version().await?,
+            client: self.client.clone(),
         })
     }
 }
@@ -0,0 +1,279 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Types describing a runtime upgrade, and hooks which can be registered on a
+//! [`super::ClientRuntimeUpdater`] to observe, delay or veto runtime upgrades.
+
+use crate::Metadata;
+use async_trait::async_trait;
+use std::future::Future;
+use subxt_core::client::RuntimeVersion;
+
+/// A runtime upgrade which is about to be (or has just been) applied to an
+/// [`super::OnlineClient`].
+#[derive(Debug, Clone)]
+pub struct RuntimeUpgrade {
+    old_runtime_version: RuntimeVersion,
+    new_runtime_version: RuntimeVersion,
+    old_metadata: Metadata,
+    new_metadata: Metadata,
+    diff: MetadataDiff,
+}
+
+impl RuntimeUpgrade {
+    pub(crate) fn new(
+        old_runtime_version: RuntimeVersion,
+        new_runtime_version: RuntimeVersion,
+        old_metadata: Metadata,
+        new_metadata: Metadata,
+    ) -> Self {
+        let diff = MetadataDiff::new(&old_metadata, &new_metadata);
+        RuntimeUpgrade {
+            old_runtime_version,
+            new_runtime_version,
+            old_metadata,
+            new_metadata,
+            diff,
+        }
+    }
+
+    /// The runtime version in use prior to the upgrade.
+    pub fn old_runtime_version(&self) -> &RuntimeVersion {
+        &self.old_runtime_version
+    }
+
+    /// The runtime version in use after the upgrade.
+    pub fn new_runtime_version(&self) -> &RuntimeVersion {
+        &self.new_runtime_version
+    }
+
+    /// The metadata in use prior to the upgrade.
+    pub fn old_metadata(&self) -> &Metadata {
+        &self.old_metadata
+    }
+
+    /// The metadata in use after the upgrade.
+    pub fn new_metadata(&self) -> &Metadata {
+        &self.new_metadata
+    }
+
+    /// The differences between the old and new metadata.
+    pub fn diff(&self) -> &MetadataDiff {
+        &self.diff
+    }
+}
+
+/// A summary of the pallets and runtime APIs which were added, removed or changed
+/// between two versions of the metadata.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MetadataDiff {
+    pallets_added: Vec<String>,
+    pallets_removed: Vec<String>,
+    pallets_changed: Vec<String>,
+    runtime_apis_added: Vec<String>,
+    runtime_apis_removed: Vec<String>,
+    runtime_apis_changed: Vec<String>,
+}
+
+impl MetadataDiff {
+    /// Compare two versions of the metadata.
+    pub fn new(old: &Metadata, new: &Metadata) -> Self {
+        let mut diff = MetadataDiff::default();
+
+        for pallet in new.pallets() {
+            match old.pallet_by_name(pallet.name()) {
+                None => diff.pallets_added.push(pallet.name().to_owned()),
+                Some(old_pallet) if old_pallet.hash() != pallet.hash() => {
+                    diff.pallets_changed.push(pallet.name().to_owned())
+                }
+                Some(_) => {}
+            }
+        }
+        for pallet in old.pallets() {
+            if new.pallet_by_name(pallet.name()).is_none() {
+                diff.pallets_removed.push(pallet.name().to_owned());
+            }
+        }
+
+        for api in new.runtime_api_traits() {
+            match old.runtime_api_trait_by_name(api.name()) {
+                None => diff.runtime_apis_added.push(api.name().to_owned()),
+                Some(old_api) if old_api.hash() != api.hash() => {
+                    diff.runtime_apis_changed.push(api.name().to_owned())
+                }
+                Some(_) => {}
+            }
+        }
+        for api in old.runtime_api_traits() {
+            if new.runtime_api_trait_by_name(api.name()).is_none() {
+                diff.runtime_apis_removed.push(api.name().to_owned());
+            }
+        }
+
+        diff
+    }
+
+    /// Returns true if no pallets or runtime APIs differ.
+    pub fn is_empty(&self) -> bool {
+        self.pallets_added.is_empty()
+            && self.pallets_removed.is_empty()
+            && self.pallets_changed.is_empty()
+            && self.runtime_apis_added.is_empty()
+            && self.runtime_apis_removed.is_empty()
+            && self.runtime_apis_changed.is_empty()
+    }
+
+    /// The names of pallets which exist only in the new metadata.
+    pub fn pallets_added(&self) -> &[String] {
+        &self.pallets_added
+    }
+
+    /// The names of pallets which exist only in the old metadata.
+    pub fn pallets_removed(&self) -> &[String] {
+        &self.pallets_removed
+    }
+
+    /// The names of pallets which exist in both, but whose calls, events, storage,
+    /// constants or associated types differ.
+    pub fn pallets_changed(&self) -> &[String] {
+        &self.pallets_changed
+    }
+
+    /// The names of runtime API traits which exist only in the new metadata.
+    pub fn runtime_apis_added(&self) -> &[String] {
+        &self.runtime_apis_added
+    }
+
+    /// The names of runtime API traits which exist only in the old metadata.
+    pub fn runtime_apis_removed(&self) -> &[String] {
+        &self.runtime_apis_removed
+    }
+
+    /// The names of runtime API traits which exist in both, but whose methods differ.
+    pub fn runtime_apis_changed(&self) -> &[String] {
+        &self.runtime_apis_changed
+    }
+}
+
+/// Should a runtime upgrade be applied? Returned from [`RuntimeUpgradeHook::before_upgrade()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpgradeDecision {
+    /// Apply the upgrade.
+    Apply,
+    /// Don't apply the upgrade. The client will continue to use the old runtime version
+    /// and metadata, which may leave it unable to submit valid transactions.
+    Skip,
+}
+
+/// A hook which is consulted whenever [`super::ClientRuntimeUpdater::perform_runtime_updates()`]
+/// is about to apply a runtime upgrade. Register hooks with
+/// [`super::ClientRuntimeUpdater::with_hook()`].
+///
+/// This is implemented for any `Fn(RuntimeUpgrade) -> impl Future<Output = UpgradeDecision>`.
+#[async_trait]
+pub trait RuntimeUpgradeHook: Send + Sync + 'static {
+    /// Called before the upgrade is applied. The upgrade is not applied until every hook
+    /// has returned, and so this can be used to delay it (for instance until in-flight
+    /// transactions signed against the old runtime version have been included). Return
+    /// [`UpgradeDecision::Skip`] to prevent the upgrade from being applied at all.
+    async fn before_upgrade(&self, upgrade: &RuntimeUpgrade) -> UpgradeDecision;
+
+    /// Called after the upgrade has been applied.
+    fn after_upgrade(&self, _upgrade: &RuntimeUpgrade) {}
+}
+
+#[async_trait]
+impl<F, Fut> RuntimeUpgradeHook for F
+where
+    F: Fn(RuntimeUpgrade) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = UpgradeDecision> + Send,
+{
+    async fn before_upgrade(&self, upgrade: &RuntimeUpgrade) -> UpgradeDecision {
+        self(upgrade.clone()).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use codec::{Decode, Encode};
+    use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed, v15::RuntimeMetadataV15};
+
+    fn metadata_v15() -> RuntimeMetadataV15 {
+        let bytes = include_bytes!("../../../artifacts/polkadot_metadata_full.scale");
+        let prefixed = RuntimeMetadataPrefixed::decode(&mut &bytes[..]).unwrap();
+        let RuntimeMetadata::V15(metadata) = prefixed.1 else {
+            panic!("expected V15 metadata");
+        };
+        metadata
+    }
+
+    fn to_metadata(metadata: RuntimeMetadataV15) -> Metadata {
+        let prefixed = RuntimeMetadataPrefixed::from(metadata);
+        Metadata::decode(&mut &prefixed.encode()[..]).unwrap()
+    }
+
+    #[test]
+    fn identical_metadata_has_empty_diff() {
+        let metadata = to_metadata(metadata_v15());
+        let diff = MetadataDiff::new(&metadata, &metadata);
+        assert!(diff.is_empty());
+        assert_eq!(diff, MetadataDiff::default());
+    }
+
+    #[test]
+    fn added_and_removed_pallets() {
+        let old = metadata_v15();
+        let mut new = old.clone();
+        let removed = new.pallets.pop().unwrap().name;
+        let (old, new) = (to_metadata(old), to_metadata(new));
+
+        let diff = MetadataDiff::new(&old, &new);
+        assert_eq!(diff.pallets_removed(), std::slice::from_ref(&removed));
+        assert!(diff.pallets_added().is_empty());
+        assert!(diff.pallets_changed().is_empty());
+        assert!(!diff.is_empty());
+
+        let diff = MetadataDiff::new(&new, &old);
+        assert_eq!(diff.pallets_added(), [removed]);
+        assert!(diff.pallets_removed().is_empty());
+        assert!(diff.pallets_changed().is_empty());
+    }
+
+    #[test]
+    fn changed_pallets() {
+        let old = metadata_v15();
+        let mut new = old.clone();
+        let system = new.pallets.iter_mut().find(|p| p.name == "System").unwrap();
+        system.storage.as_mut().unwrap().entries.pop();
+        let (old, new) = (to_metadata(old), to_metadata(new));
+
+        let diff = MetadataDiff::new(&old, &new);
+        assert_eq!(diff.pallets_changed(), ["System".to_owned()]);
+        assert!(diff.pallets_added().is_empty());
+        assert!(diff.pallets_removed().is_empty());
+        assert!(diff.runtime_apis_changed().is_empty());
+    }
+
+    #[test]
+    fn added_removed_and_changed_runtime_apis() {
+        let old = metadata_v15();
+        let mut new = old.clone();
+        let removed = new.apis.pop().unwrap().name;
+        let changed = new.apis[0].name.clone();
+        new.apis[0].methods.pop();
+        let (old, new) = (to_metadata(old), to_metadata(new));
+
+        let diff = MetadataDiff::new(&old, &new);
+        assert_eq!(diff.runtime_apis_removed(), std::slice::from_ref(&removed));
+        assert_eq!(diff.runtime_apis_changed(), std::slice::from_ref(&changed));
+        assert!(diff.runtime_apis_added().is_empty());
+        assert!(diff.pallets_changed().is_empty());
+
+        let diff = MetadataDiff::new(&new, &old);
+        assert_eq!(diff.runtime_apis_added(), [removed]);
+        assert_eq!(diff.runtime_apis_changed(), [changed]);
+        assert!(diff.runtime_apis_removed().is_empty());
+    }
+}
@@ -141,6 +141,11 @@ impl Error {
     pub fn is_rpc_limit_reached(&self) -> bool {
         matches!(self, Error::Rpc(RpcError::LimitReached))
     }
+
+    /// Checks whether the error was caused by a request timing out.
+    pub fn is_request_timeout(&self) -> bool {
+        matches!(self, Error::Rpc(RpcError::RequestTimeout(_)))
+    }
 }
 
 /// An RPC error. Since we are generic over the RPC client that is used,
@@ -160,6 +165,9 @@ pub enum RpcError {
     /// The RPC subscription dropped.
     #[error("RPC error: subscription dropped.")]
     SubscriptionDropped,
+    /// The request did not complete within the configured timeout.
+    #[error("RPC error: request timed out after {0:?}")]
+    RequestTimeout(std::time::Duration),
 }
 
 /// Block error
@@ -187,6 +195,9 @@ pub enum BlockError {
         /// The decode error.
         error: subxt_core::error::ExtrinsicDecodeError,
     },
+    /// The payload that the extrinsic signature was created from could not be rebuilt.
+    #[error("Cannot rebuild the signer payload of the extrinsic: {0}")]
+    SignerPayload(String),
 }
 
 impl From<CoreBlockError> for BlockError {
@@ -206,6 +217,7 @@ impl From<CoreBlockError> for BlockError {
                 extrinsic_index,
                 error,
             },
+            CoreBlockError::SignerPayload(reason) => BlockError::SignerPayload(reason),
         }
     }
 }
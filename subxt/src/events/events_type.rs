@@ -42,6 +42,11 @@ impl<T: Config> Events<T> {
         self.inner.bytes()
     }
 
+    /// Return the metadata used to decode these events.
+    pub fn metadata(&self) -> &Metadata {
+        self.inner.metadata()
+    }
+
     /// Iterate over all of the events, using metadata to dynamically
     /// decode them as we go, and returning the raw bytes and other associated
     /// details. If an error occurs, all subsequent iterations return `None`.
@@ -38,6 +38,10 @@ mod only_used_in_docs_or_tests {
 #[cfg(test)]
 use tracing_subscriber as _;
 
+// Helpers which are shared between unit tests.
+#[cfg(test)]
+mod test_utils;
+
 pub mod backend;
 pub mod blocks;
 pub mod client;
@@ -97,6 +101,7 @@ pub use crate::{
 
 /// Re-export external crates that are made use of in the subxt API.
 pub mod ext {
+    pub use async_trait;
     pub use codec;
     pub use frame_metadata;
     pub use futures;
@@ -24,6 +24,24 @@ macro_rules! cfg_reconnecting_rpc_client {
 	};
 }
 
+macro_rules! cfg_rate_limited_rpc_client {
+	($($item:item)*) => {
+		crate::macros::cfg_feature!("rate-limited-rpc-client", $($item)*);
+	};
+}
+
+macro_rules! cfg_runtime_wasm_path {
+	($($item:item)*) => {
+		crate::macros::cfg_feature!("runtime-wasm-path", $($item)*);
+	};
+}
+
+macro_rules! cfg_signature_verification {
+	($($item:item)*) => {
+		crate::macros::cfg_feature!("signature-verification", $($item)*);
+	};
+}
+
 macro_rules! cfg_jsonrpsee {
 	($($item:item)*) => {
 		crate::macros::cfg_feature!("jsonrpsee", $($item)*);
@@ -52,7 +70,10 @@ macro_rules! cfg_jsonrpsee_web {
 	}
 }
 
-pub(crate) use {cfg_feature, cfg_jsonrpsee, cfg_unstable_light_client};
+pub(crate) use {
+    cfg_feature, cfg_jsonrpsee, cfg_rate_limited_rpc_client, cfg_runtime_wasm_path,
+    cfg_signature_verification, cfg_unstable_light_client,
+};
 
 // Only used by light-client.
 #[allow(unused)]
@@ -0,0 +1,26 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Helpers which are shared between unit tests.
+
+use crate::{Metadata, OfflineClient, PolkadotConfig, client::RuntimeVersion};
+use codec::Decode;
+
+/// Metadata for a Polkadot node.
+pub(crate) fn metadata() -> Metadata {
+    let metadata_bytes = include_bytes!("../../artifacts/polkadot_metadata_full.scale");
+    Metadata::decode(&mut &metadata_bytes[..]).unwrap()
+}
+
+/// An offline client for a Polkadot node.
+pub(crate) fn offline_client() -> OfflineClient<PolkadotConfig> {
+    OfflineClient::new(
+        Default::default(),
+        RuntimeVersion {
+            spec_version: 1,
+            transaction_version: 1,
+        },
+        metadata(),
+    )
+}
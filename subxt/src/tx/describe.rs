@@ -0,0 +1,409 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Decode transactions into a human readable form, so that they can be checked before signing.
+
+use crate::{Config, Metadata, error::Error};
+use codec::Encode;
+use scale_info::{TypeDef, TypeDefPrimitive};
+use scale_value::{BitSequence, Composite, Primitive, Value, ValueDef, Variant};
+use std::fmt::Write;
+
+/// A decoded description of a transaction, so that it can be checked before it is signed.
+/// Create one with [`crate::tx::PartialTransaction::describe()`].
+///
+/// The [`std::fmt::Display`] implementation, and [`TransactionDescription::to_json()`], render
+/// this in a human readable way, where byte arrays are shown as hex strings.
+#[derive(Debug, Clone)]
+pub struct TransactionDescription {
+    call: Value<u32>,
+    transaction_extensions: Vec<(String, Value<u32>)>,
+    transaction_extensions_implicit: Vec<(String, Value<u32>)>,
+    metadata: Metadata,
+}
+
+impl TransactionDescription {
+    pub(crate) fn decode<T: Config>(
+        call_data: &[u8],
+        transaction_extension_values: &[u8],
+        transaction_extension_implicit: &[u8],
+        transaction_extension_version: u8,
+        metadata: Metadata,
+    ) -> Result<Self, Error> {
+        // Put the call and extension values together into a V5 general transaction, so that we
+        // can lean on the usual extrinsic decoding logic, which also complains if any bytes are
+        // left over once the call and each of the extensions has been decoded.
+        let mut bytes = vec![0b0100_0000 | 5, transaction_extension_version];
+        bytes.extend_from_slice(transaction_extension_values);
+        bytes.extend_from_slice(call_data);
+        let extrinsics =
+            subxt_core::blocks::decode_from::<T>(vec![bytes.encode()], metadata.clone())?;
+        let ext = extrinsics
+            .iter()
+            .next()
+            .expect("a single extrinsic was decoded above; qed");
+
+        // Calls nested in others (for instance in batches, proxies and multisigs) have the
+        // same outer call type, and so they are decoded along with the rest of the call.
+        let details = ext.extrinsic_metadata()?;
+        let pallet_call_ty = details.pallet.call_ty_id().ok_or_else(|| {
+            Error::Other(format!("Pallet {} has no calls", details.pallet.name()))
+        })?;
+        let call = Value {
+            value: ValueDef::Variant(Variant {
+                name: details.pallet.name().to_owned(),
+                values: Composite::Unnamed(vec![Value {
+                    value: ValueDef::Variant(Variant {
+                        name: details.variant.name.clone(),
+                        values: ext.field_values()?,
+                    }),
+                    context: pallet_call_ty,
+                }]),
+            }),
+            context: metadata.outer_enums().call_enum_ty(),
+        };
+
+        let transaction_extensions = ext
+            .transaction_extensions()
+            .into_iter()
+            .flat_map(|exts| exts.iter())
+            .map(|ext| Ok((ext.name().to_owned(), ext.value()?)))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        // The implicit data isn't part of the transaction, but it is signed, so it's
+        // decoded too in order that it can be checked.
+        let implicit = &mut &*transaction_extension_implicit;
+        let transaction_extensions_implicit = metadata
+            .extrinsic()
+            .transaction_extensions_by_version(transaction_extension_version)
+            .into_iter()
+            .flatten()
+            .map(|ext| {
+                let value = scale_value::scale::decode_as_type(
+                    implicit,
+                    ext.additional_ty(),
+                    metadata.types(),
+                )?;
+                Ok((ext.identifier().to_owned(), value))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        if !implicit.is_empty() {
+            return Err(Error::Other(format!(
+                "{} bytes were left over after decoding the implicit transaction extension data",
+                implicit.len()
+            )));
+        }
+
+        Ok(TransactionDescription {
+            call,
+            transaction_extensions,
+            transaction_extensions_implicit,
+            metadata,
+        })
+    }
+
+    /// The decoded call. The outermost variant is the pallet, and within that
+    /// is a variant representing the call and its arguments.
+    pub fn call(&self) -> &Value<u32> {
+        &self.call
+    }
+
+    /// The name of each transaction extension, and its decoded value.
+    pub fn transaction_extensions(&self) -> impl Iterator<Item = (&str, &Value<u32>)> {
+        self.transaction_extensions
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+    }
+
+    /// The name of each transaction extension, and its decoded implicit data. This data is not
+    /// included in the transaction, but it is part of what is signed.
+    pub fn transaction_extensions_implicit(&self) -> impl Iterator<Item = (&str, &Value<u32>)> {
+        self.transaction_extensions_implicit
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+    }
+
+    /// Render this description as JSON.
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "call": self.value_to_json(&self.call),
+            "transactionExtensions": self.extensions_to_json(&self.transaction_extensions),
+            "transactionExtensionsImplicit": self.extensions_to_json(&self.transaction_extensions_implicit),
+        })
+    }
+
+    fn extensions_to_json(&self, extensions: &[(String, Value<u32>)]) -> serde_json::Value {
+        serde_json::Value::Object(
+            extensions
+                .iter()
+                .map(|(name, value)| (name.clone(), self.value_to_json(value)))
+                .collect(),
+        )
+    }
+
+    fn value_to_json(&self, value: &Value<u32>) -> serde_json::Value {
+        use serde_json::Value as Json;
+
+        if let Some(inner) = self.newtype_inner(value) {
+            return self.value_to_json(inner);
+        }
+        if let Some(bytes) = self.as_bytes(value) {
+            return Json::String(format!("0x{}", hex::encode(bytes)));
+        }
+
+        match &value.value {
+            ValueDef::Composite(composite) => self.composite_to_json(composite),
+            ValueDef::Variant(variant) => {
+                let fields = match &variant.values {
+                    Composite::Unnamed(values) if values.is_empty() => {
+                        return Json::String(variant.name.clone());
+                    }
+                    Composite::Unnamed(values) if values.len() == 1 => {
+                        self.value_to_json(&values[0])
+                    }
+                    values => self.composite_to_json(values),
+                };
+                serde_json::json!({ &variant.name: fields })
+            }
+            ValueDef::Primitive(primitive) => match primitive {
+                Primitive::Bool(b) => Json::Bool(*b),
+                Primitive::Char(c) => Json::String(c.to_string()),
+                Primitive::String(s) => Json::String(s.clone()),
+                // Large numbers are given as strings to avoid losing precision.
+                Primitive::U128(n) => match u64::try_from(*n) {
+                    Ok(n) => Json::from(n),
+                    Err(_) => Json::String(n.to_string()),
+                },
+                Primitive::I128(n) => match i64::try_from(*n) {
+                    Ok(n) => Json::from(n),
+                    Err(_) => Json::String(n.to_string()),
+                },
+                Primitive::U256(_) | Primitive::I256(_) => Json::String(value.to_string()),
+            },
+            ValueDef::BitSequence(bits) => Json::String(bits_to_string(bits)),
+        }
+    }
+
+    fn composite_to_json(&self, composite: &Composite<u32>) -> serde_json::Value {
+        match composite {
+            Composite::Named(fields) => serde_json::Value::Object(
+                fields
+                    .iter()
+                    .map(|(name, value)| (name.clone(), self.value_to_json(value)))
+                    .collect(),
+            ),
+            Composite::Unnamed(fields) => {
+                serde_json::Value::Array(fields.iter().map(|v| self.value_to_json(v)).collect())
+            }
+        }
+    }
+
+    fn write_value(&self, f: &mut impl Write, value: &Value<u32>) -> std::fmt::Result {
+        if let Some(inner) = self.newtype_inner(value) {
+            return self.write_value(f, inner);
+        }
+        if let Some(bytes) = self.as_bytes(value) {
+            return write!(f, "0x{}", hex::encode(bytes));
+        }
+
+        match &value.value {
+            ValueDef::Composite(composite) => self.write_composite(f, composite),
+            ValueDef::Variant(variant) => {
+                write!(f, "{}", variant.name)?;
+                if !variant.values.is_empty() {
+                    if matches!(variant.values, Composite::Named(_)) {
+                        write!(f, " ")?;
+                    }
+                    self.write_composite(f, &variant.values)?;
+                }
+                Ok(())
+            }
+            ValueDef::Primitive(_) | ValueDef::BitSequence(_) => write!(f, "{value}"),
+        }
+    }
+
+    fn write_composite(&self, f: &mut impl Write, composite: &Composite<u32>) -> std::fmt::Result {
+        let (open, close) = match composite {
+            Composite::Named(_) => ("{ ", " }"),
+            Composite::Unnamed(_) => ("(", ")"),
+        };
+        write!(f, "{open}")?;
+        match composite {
+            Composite::Named(fields) => {
+                for (idx, (name, value)) in fields.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{name}: ")?;
+                    self.write_value(f, value)?;
+                }
+            }
+            Composite::Unnamed(fields) => {
+                for (idx, value) in fields.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ", ")?;
+                    }
+                    self.write_value(f, value)?;
+                }
+            }
+        }
+        write!(f, "{close}")
+    }
+
+    // Return the inner value if this is a struct with one unnamed field, so that
+    // these wrapper types don't clutter the output.
+    fn newtype_inner<'a>(&self, value: &'a Value<u32>) -> Option<&'a Value<u32>> {
+        let ValueDef::Composite(Composite::Unnamed(items)) = &value.value else {
+            return None;
+        };
+        let ty = self.metadata.types().resolve(value.context)?;
+        match (&ty.type_def, items.as_slice()) {
+            (TypeDef::Composite(_), [inner]) => Some(inner),
+            _ => None,
+        }
+    }
+
+    // Return the bytes if this value is a sequence or array of u8s.
+    fn as_bytes(&self, value: &Value<u32>) -> Option<Vec<u8>> {
+        let ValueDef::Composite(Composite::Unnamed(items)) = &value.value else {
+            return None;
+        };
+        let ty = self.metadata.types().resolve(value.context)?;
+        let item_ty = match &ty.type_def {
+            TypeDef::Sequence(seq) => seq.type_param.id,
+            TypeDef::Array(arr) => arr.type_param.id,
+            _ => return None,
+        };
+        let is_u8 = matches!(
+            self.metadata.types().resolve(item_ty)?.type_def,
+            TypeDef::Primitive(TypeDefPrimitive::U8)
+        );
+        if !is_u8 {
+            return None;
+        }
+        items
+            .iter()
+            .map(|item| item.as_u128().and_then(|n| u8::try_from(n).ok()))
+            .collect()
+    }
+}
+
+impl std::fmt::Display for TransactionDescription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Call: ")?;
+        self.write_value(f, &self.call)?;
+        writeln!(f)?;
+        write!(f, "Transaction extensions:")?;
+        for (name, value) in &self.transaction_extensions {
+            write!(f, "\n  {name}: ")?;
+            self.write_value(f, value)?;
+        }
+        writeln!(f)?;
+        write!(f, "Transaction extensions (implicit):")?;
+        for (name, value) in &self.transaction_extensions_implicit {
+            write!(f, "\n  {name}: ")?;
+            self.write_value(f, value)?;
+        }
+        Ok(())
+    }
+}
+
+fn bits_to_string(bits: &BitSequence) -> String {
+    bits.iter().map(|b| if b { '1' } else { '0' }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TransactionDescription;
+    use crate::config::{DefaultExtrinsicParamsBuilder as Params, PolkadotConfig};
+    use crate::dynamic::Value;
+    use crate::test_utils::offline_client;
+    use crate::tx::UnsignedTransactionEnvelope;
+    use crate::utils::AccountId32;
+
+    fn remark_envelope() -> UnsignedTransactionEnvelope {
+        let remark = crate::dynamic::tx("System", "remark", vec![Value::from_bytes("hi")]);
+        offline_client()
+            .tx()
+            .create_v4_partial_offline(&remark, Params::new().nonce(3).build())
+            .unwrap()
+            .to_envelope()
+            .unwrap()
+    }
+
+    fn describe(call: &[u8], values: &[u8], implicit: &[u8]) -> Result<(), crate::Error> {
+        let metadata = offline_client().metadata();
+        TransactionDescription::decode::<PolkadotConfig>(call, values, implicit, 0, metadata)
+            .map(|_| ())
+    }
+
+    #[test]
+    fn describes_nested_calls_and_extensions() {
+        let client = offline_client();
+        let remark = crate::dynamic::tx("System", "remark", vec![Value::from_bytes("hi")]);
+        let real = AccountId32([1; 32]);
+        let call = crate::tx::proxy::<PolkadotConfig>(real, remark);
+
+        let description = client
+            .tx()
+            .create_v4_partial_offline(&call, Params::new().nonce(3).tip(7).build())
+            .unwrap()
+            .describe()
+            .unwrap();
+
+        let json = description.to_json();
+        let proxy = &json["call"]["Proxy"]["proxy"];
+        assert_eq!(proxy["real"]["Id"], format!("0x{}", hex::encode([1; 32])));
+        assert_eq!(proxy["force_proxy_type"], "None");
+        assert_eq!(proxy["call"]["System"]["remark"]["remark"], "0x6869");
+        assert_eq!(json["transactionExtensions"]["CheckNonce"], 3);
+        assert_eq!(json["transactionExtensions"]["ChargeTransactionPayment"], 7);
+
+        let text = description.to_string();
+        assert!(text.starts_with("Call: Proxy(proxy { real: Id(0x0101"));
+        assert!(text.contains("call: System(remark { remark: 0x6869 })"));
+        assert!(text.contains("\n  CheckNonce: 3\n"));
+    }
+
+    #[test]
+    fn describes_implicit_data() {
+        let client = offline_client();
+        let remark = crate::dynamic::tx("System", "remark", vec![Value::from_bytes("hi")]);
+
+        let description = client
+            .tx()
+            .create_v4_partial_offline(&remark, Params::new().build())
+            .unwrap()
+            .describe()
+            .unwrap();
+
+        let json = description.to_json();
+        let implicit = &json["transactionExtensionsImplicit"];
+        assert_eq!(implicit["CheckSpecVersion"], 1);
+        assert_eq!(implicit["CheckTxVersion"], 1);
+        assert_eq!(
+            implicit["CheckGenesis"],
+            format!("0x{}", hex::encode(client.genesis_hash()))
+        );
+
+        let text = description.to_string();
+        assert!(text.contains("\nTransaction extensions (implicit):"));
+        assert!(text.contains("\n  CheckSpecVersion: 1"));
+    }
+
+    #[test]
+    fn rejects_leftover_bytes() {
+        let envelope = remark_envelope();
+        let call = &envelope.call_data;
+        let values = &envelope.transaction_extension_values;
+        let implicit = &envelope.transaction_extension_implicit;
+
+        describe(call, values, implicit).unwrap();
+
+        let with_byte = |bytes: &[u8]| [bytes, &[0]].concat();
+        assert!(describe(&with_byte(call), values, implicit).is_err());
+        assert!(describe(call, &with_byte(values), implicit).is_err());
+        assert!(describe(call, values, &with_byte(implicit)).is_err());
+    }
+}
@@ -0,0 +1,111 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! A portable format for handing unsigned transactions to an offline signer.
+
+use crate::utils::H256;
+use serde::{Deserialize, Serialize};
+use sp_crypto_hashing::blake2_256;
+
+/// An unsigned V4 transaction in a portable, serializable format. This can be handed to
+/// another (for instance, air-gapped) machine to be signed, and the resulting signature
+/// brought back and attached to it using [`crate::tx::PartialTransaction::from_envelope()`]
+/// and [`crate::tx::PartialTransaction::sign_with_account_and_signature()`].
+///
+/// Create one with [`crate::tx::PartialTransaction::to_envelope()`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnsignedTransactionEnvelope {
+    /// The version of this envelope format. See [`UnsignedTransactionEnvelope::VERSION`].
+    pub version: u32,
+    /// The SCALE encoded call data.
+    #[serde(with = "hex_bytes")]
+    pub call_data: Vec<u8>,
+    /// The SCALE encoded transaction extension values, which are included in the transaction.
+    #[serde(with = "hex_bytes")]
+    pub transaction_extension_values: Vec<u8>,
+    /// The SCALE encoded transaction extension values which are signed. These are usually the
+    /// same as `transaction_extension_values`, but extensions can sign something different from
+    /// what they include in the transaction. Added in version 2 of the envelope format.
+    #[serde(with = "hex_bytes")]
+    pub transaction_extension_signer_payload_values: Vec<u8>,
+    /// The SCALE encoded implicit transaction extension data, which is signed but not
+    /// included in the transaction.
+    #[serde(with = "hex_bytes")]
+    pub transaction_extension_implicit: Vec<u8>,
+    /// Subxt's own hash of the metadata that the transaction was created with, as given by
+    /// [`crate::Metadata::hasher()`], which is used to check that the transaction is rebuilt
+    /// using the same metadata. This is not the RFC-78 metadata hash which is signed when the
+    /// `CheckMetadataHash` transaction extension is enabled.
+    pub subxt_metadata_hash: H256,
+    /// A human readable description of the call and transaction extensions (see
+    /// [`crate::tx::TransactionDescription`]). This is not used when rebuilding the transaction,
+    /// and nothing ties it to the bytes that are signed, so signers should not trust it. Instead,
+    /// they should decode the transaction themselves, using
+    /// [`crate::tx::PartialTransaction::from_envelope()`] and
+    /// [`crate::tx::PartialTransaction::describe()`].
+    pub call: String,
+}
+
+impl UnsignedTransactionEnvelope {
+    /// The current version of the envelope format. Version 1 envelopes didn't record the signed
+    /// transaction extension values, and are no longer supported.
+    pub const VERSION: u32 = 2;
+
+    /// Return the V4 signer payload for this transaction. These are the bytes that must
+    /// be signed in order to produce a valid signature for it.
+    pub fn signer_payload(&self) -> Vec<u8> {
+        let mut bytes = self.call_data.clone();
+        bytes.extend(&self.transaction_extension_signer_payload_values);
+        bytes.extend(&self.transaction_extension_implicit);
+
+        if bytes.len() > 256 {
+            blake2_256(&bytes).to_vec()
+        } else {
+            bytes
+        }
+    }
+}
+
+mod hex_bytes {
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        let s = s.strip_prefix("0x").unwrap_or(&s);
+        hex::decode(s).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn envelope_json_roundtrip() {
+        let envelope = UnsignedTransactionEnvelope {
+            version: UnsignedTransactionEnvelope::VERSION,
+            call_data: vec![0, 1, 2],
+            transaction_extension_values: vec![3, 4],
+            transaction_extension_signer_payload_values: vec![4],
+            transaction_extension_implicit: vec![],
+            subxt_metadata_hash: H256::repeat_byte(5),
+            call: "System(remark(...))".into(),
+        };
+
+        let json = serde_json::to_value(&envelope).unwrap();
+        assert_eq!(json["callData"], "0x000102");
+        assert_eq!(json["transactionExtensionSignerPayloadValues"], "0x04");
+        assert_eq!(json["transactionExtensionImplicit"], "0x");
+
+        let decoded: UnsignedTransactionEnvelope = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, envelope);
+        // Only the signed values are part of the signer payload:
+        assert_eq!(envelope.signer_payload(), vec![0, 1, 2, 4]);
+    }
+}
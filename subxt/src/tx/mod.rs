@@ -9,13 +9,26 @@
 //! additional and signed extra parameters are used when constructing an extrinsic, and is a part
 //! of the chain configuration (see [`crate::config::Config`]).
 
+mod describe;
+mod envelope;
+mod multisig;
+mod proxy;
 mod tx_client;
 mod tx_progress;
+mod tx_resilient;
 
-pub use subxt_core::tx::payload::{DefaultPayload, DynamicPayload, Payload, dynamic};
+pub use crate::blocks::BatchItemResult;
+pub use describe::TransactionDescription;
+pub use envelope::UnsignedTransactionEnvelope;
+pub use multisig::{Multisig, MultisigApproval, Timepoint};
+pub use proxy::proxy;
+pub use subxt_core::tx::payload::{
+    Batch, BatchMode, DefaultPayload, DynamicPayload, NestedCallPayload, Payload, dynamic,
+};
 pub use subxt_core::tx::signer::{self, Signer};
 pub use tx_client::{
-    DefaultParams, PartialTransaction, SubmittableTransaction, TransactionInvalid,
-    TransactionUnknown, TxClient, ValidationResult,
+    DefaultParams, PartialTransaction, PendingFilter, PendingTransaction, SubmittableTransaction,
+    TransactionInvalid, TransactionUnknown, TxClient, ValidationResult,
 };
-pub use tx_progress::{TxInBlock, TxProgress, TxStatus};
+pub use tx_progress::{TipBumpOutcome, TxInBlock, TxOutcome, TxProgress, TxStatus};
+pub use tx_resilient::{ResilientTxProgress, ResilientTxStatus};
@@ -0,0 +1,349 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use crate::client::OnlineClientT;
+use crate::config::Config;
+use crate::error::{Error, MetadataError};
+use codec::{Decode, Encode};
+use scale_value::{At, Value};
+use sp_crypto_hashing::blake2_256;
+use subxt_core::tx::payload::{NestedCallPayload, Payload};
+
+/// The point at which a multisig operation was first approved, which every subsequent
+/// approval of the same call must refer to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timepoint {
+    /// The block number that the first approval was included in.
+    pub height: u64,
+    /// The index of the first approval in that block.
+    pub index: u32,
+}
+
+impl Timepoint {
+    fn to_value(self) -> Value {
+        Value::named_composite([
+            ("height", Value::u128(self.height as u128)),
+            ("index", Value::u128(self.index as u128)),
+        ])
+    }
+}
+
+/// A multisig account, made up of a set of signatories of which some `threshold` must
+/// approve a call before it is dispatched. Use [`Multisig::approve()`] to build the next
+/// approval for some call, based on the approvals already on chain.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use subxt::dynamic::Value;
+/// use subxt::{OnlineClient, PolkadotConfig, tx};
+/// use subxt_signer::sr25519::dev;
+///
+/// let api = OnlineClient::<PolkadotConfig>::new().await?;
+///
+/// let multisig = tx::Multisig::<PolkadotConfig>::new(
+///     2,
+///     vec![
+///         dev::alice().public_key().into(),
+///         dev::bob().public_key().into(),
+///         dev::charlie().public_key().into(),
+///     ],
+/// );
+///
+/// // Alice approves a call, and then Bob approves and executes it:
+/// for signer in [dev::alice(), dev::bob()] {
+///     let call = tx::dynamic("System", "remark", vec![Value::from_bytes("Hello")]);
+///     let approval = multisig
+///         .approve(&api, &signer.public_key().into(), call)
+///         .await?;
+///     api.tx()
+///         .sign_and_submit_then_watch_default(&approval.into_payload(), &signer)
+///         .await?
+///         .wait_for_finalized_success()
+///         .await?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Multisig<T: Config> {
+    threshold: u16,
+    signatories: Vec<T::AccountId>,
+}
+
+impl<T: Config> Multisig<T> {
+    /// Create a new multisig from a threshold and the set of signatories. The order of
+    /// signatories doesn't matter, and any duplicates are ignored.
+    pub fn new(threshold: u16, signatories: impl IntoIterator<Item = T::AccountId>) -> Self {
+        let mut signatories: Vec<(Vec<u8>, T::AccountId)> =
+            signatories.into_iter().map(|s| (s.encode(), s)).collect();
+        // The pallet expects signatories to be sorted and unique.
+        signatories.sort_by(|a, b| a.0.cmp(&b.0));
+        signatories.dedup_by(|a, b| a.0 == b.0);
+
+        Multisig {
+            threshold,
+            signatories: signatories.into_iter().map(|(_, s)| s).collect(),
+        }
+    }
+
+    /// The number of approvals needed to dispatch a call.
+    pub fn threshold(&self) -> u16 {
+        self.threshold
+    }
+
+    /// The signatories of this multisig, in the order that the pallet expects them.
+    pub fn signatories(&self) -> &[T::AccountId] {
+        &self.signatories
+    }
+
+    /// The account ID of this multisig, which calls will be dispatched from.
+    pub fn account_id(&self) -> T::AccountId {
+        let entropy =
+            (b"modlpy/utilisuba", &self.signatories, self.threshold).using_encoded(blake2_256);
+        // Account IDs longer than the hash are padded with zeroes, as the pallet does.
+        T::AccountId::decode(&mut TrailingZeroInput(&entropy))
+            .expect("infinite length input; no invalid inputs for type; qed")
+    }
+
+    /// Build the transaction that `signatory` should submit to approve the given call.
+    ///
+    /// This looks up any existing approvals of the call on chain to find the [`Timepoint`]
+    /// to refer to. If this approval will reach the threshold, the call is wrapped in
+    /// `Multisig::as_multi` so that it is dispatched. Otherwise, `Multisig::approve_as_multi`
+    /// is used and only the call hash is submitted. In either case, the maximum weight given
+    /// is an estimate of the call's weight obtained from the `TransactionPaymentCallApi`. A
+    /// threshold of 1 uses `Multisig::as_multi_threshold_1`.
+    ///
+    /// An error is returned if `signatory` is not one of the signatories, or if they have
+    /// already approved the call.
+    pub async fn approve<Client>(
+        &self,
+        client: &Client,
+        signatory: &T::AccountId,
+        call: impl Payload + Send + Sync + 'static,
+    ) -> Result<MultisigApproval, Error>
+    where
+        Client: OnlineClientT<T>,
+    {
+        let signatory_bytes = signatory.encode();
+        let other_signatories: Vec<&T::AccountId> = self
+            .signatories
+            .iter()
+            .filter(|s| s.encode() != signatory_bytes)
+            .collect();
+        if other_signatories.len() == self.signatories.len() {
+            return Err(Error::Other(
+                "The account approving a multisig call is not one of its signatories".into(),
+            ));
+        }
+        let other_signatories = other_signatories.encode();
+
+        if self.threshold <= 1 {
+            let payload = NestedCallPayload::new("Multisig", "as_multi_threshold_1")
+                .encoded_arg("other_signatories", other_signatories)
+                .call_arg("call", call);
+            return Ok(MultisigApproval {
+                payload,
+                timepoint: None,
+                is_final: true,
+            });
+        }
+
+        let metadata = client.metadata();
+        let call_data = call.encode_call_data(&metadata)?;
+        let call_hash = blake2_256(&call_data);
+
+        // Find any approvals of this call that are already on chain:
+        let address = crate::dynamic::storage(
+            "Multisig",
+            "Multisigs",
+            vec![
+                Value::from_bytes(self.account_id().encode()),
+                Value::from_bytes(call_hash),
+            ],
+        );
+        let existing = client.storage().at_latest().await?.fetch(&address).await?;
+
+        let (timepoint, approvals) = match existing {
+            None => (None, 0),
+            Some(existing) => {
+                let existing = existing.to_value()?;
+                let (timepoint, approvals) = decode_multisig_info(&existing, &metadata)
+                    .ok_or_else(|| {
+                        Error::Other("Cannot decode the existing multisig approvals".into())
+                    })?;
+                if approvals.contains(&signatory_bytes) {
+                    return Err(Error::Other(
+                        "The signatory has already approved this multisig call".into(),
+                    ));
+                }
+                (Some(timepoint), approvals.len())
+            }
+        };
+
+        let maybe_timepoint = match timepoint {
+            Some(timepoint) => Value::unnamed_variant("Some", [timepoint.to_value()]),
+            None => Value::unnamed_variant("None", []),
+        };
+        let is_final = approvals + 1 >= self.threshold as usize;
+        let max_weight = estimate_weight(client, &call_data).await?;
+
+        let payload = if is_final {
+            NestedCallPayload::new("Multisig", "as_multi")
+                .arg("threshold", Value::u128(self.threshold as u128))
+                .encoded_arg("other_signatories", other_signatories)
+                .arg("maybe_timepoint", maybe_timepoint)
+                .call_arg("call", call)
+                .arg("max_weight", max_weight)
+        } else {
+            NestedCallPayload::new("Multisig", "approve_as_multi")
+                .arg("threshold", Value::u128(self.threshold as u128))
+                .encoded_arg("other_signatories", other_signatories)
+                .arg("maybe_timepoint", maybe_timepoint)
+                .arg("call_hash", Value::from_bytes(call_hash))
+                .arg("max_weight", max_weight)
+        };
+
+        Ok(MultisigApproval {
+            payload,
+            timepoint,
+            is_final,
+        })
+    }
+}
+
+/// An approval of a multisig call, built by [`Multisig::approve()`].
+#[derive(Debug)]
+pub struct MultisigApproval {
+    payload: NestedCallPayload,
+    timepoint: Option<Timepoint>,
+    is_final: bool,
+}
+
+impl MultisigApproval {
+    /// The payload to sign and submit.
+    pub fn payload(&self) -> &NestedCallPayload {
+        &self.payload
+    }
+
+    /// Take the payload to sign and submit.
+    pub fn into_payload(self) -> NestedCallPayload {
+        self.payload
+    }
+
+    /// The timepoint of the first approval that this approval refers to. This is `None`
+    /// if this is the first approval.
+    pub fn timepoint(&self) -> Option<Timepoint> {
+        self.timepoint
+    }
+
+    /// Does this approval reach the threshold, and so dispatch the call?
+    pub fn is_final(&self) -> bool {
+        self.is_final
+    }
+}
+
+// Pull the timepoint and the encoded approving accounts out of a `pallet_multisig::Multisig`.
+fn decode_multisig_info(
+    info: &Value<u32>,
+    metadata: &crate::Metadata,
+) -> Option<(Timepoint, Vec<Vec<u8>>)> {
+    use scale_encode::EncodeAsType;
+
+    let when = info.at("when")?;
+    let timepoint = Timepoint {
+        height: when.at("height")?.as_u128()?.try_into().ok()?,
+        index: when.at("index")?.as_u128()?.try_into().ok()?,
+    };
+
+    let mut approvals = Vec::new();
+    let mut n = 0;
+    while let Some(approval) = info.at("approvals")?.at(n) {
+        approvals.push(
+            approval
+                .encode_as_type(approval.context, metadata.types())
+                .ok()?,
+        );
+        n += 1;
+    }
+
+    Some((timepoint, approvals))
+}
+
+// Estimate the weight of a call using `TransactionPaymentCallApi_query_call_info`.
+async fn estimate_weight<T, Client>(client: &Client, call_data: &[u8]) -> Result<Value, Error>
+where
+    T: Config,
+    Client: OnlineClientT<T>,
+{
+    const TRAIT_NAME: &str = "TransactionPaymentCallApi";
+    const METHOD_NAME: &str = "query_call_info";
+
+    let metadata = client.metadata();
+    let output_ty = metadata
+        .runtime_api_trait_by_name(TRAIT_NAME)
+        .ok_or_else(|| MetadataError::RuntimeTraitNotFound(TRAIT_NAME.into()))?
+        .method_by_name(METHOD_NAME)
+        .ok_or_else(|| MetadataError::RuntimeMethodNotFound(METHOD_NAME.into()))?
+        .output_ty();
+
+    let mut params = call_data.to_vec();
+    (call_data.len() as u32).encode_to(&mut params);
+
+    let bytes = client
+        .runtime_api()
+        .at_latest()
+        .await?
+        .call_raw(&format!("{TRAIT_NAME}_{METHOD_NAME}"), Some(&params))
+        .await?;
+    let info = scale_value::scale::decode_as_type(&mut &*bytes, output_ty, metadata.types())?;
+
+    info.at("weight")
+        .map(|weight| weight.clone().remove_context())
+        .ok_or_else(|| Error::Other("The call info returned has no weight".into()))
+}
+
+// Input which yields zeroes once the underlying bytes run out.
+struct TrailingZeroInput<'a>(&'a [u8]);
+
+impl codec::Input for TrailingZeroInput<'_> {
+    fn remaining_len(&mut self) -> Result<Option<usize>, codec::Error> {
+        Ok(None)
+    }
+
+    fn read(&mut self, into: &mut [u8]) -> Result<(), codec::Error> {
+        let len = into.len().min(self.0.len());
+        into[..len].copy_from_slice(&self.0[..len]);
+        into[len..].fill(0);
+        self.0 = &self.0[len..];
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PolkadotConfig;
+    use crate::utils::AccountId32;
+
+    #[test]
+    fn signatories_are_sorted_and_deduplicated() {
+        let a = AccountId32([1; 32]);
+        let b = AccountId32([2; 32]);
+        let c = AccountId32([3; 32]);
+
+        let m1 = Multisig::<PolkadotConfig>::new(2, [c.clone(), a.clone(), b.clone(), a.clone()]);
+        let m2 = Multisig::<PolkadotConfig>::new(2, [a.clone(), b.clone(), c.clone()]);
+
+        assert_eq!(m1.signatories(), &[a, b, c]);
+        assert_eq!(m1.account_id(), m2.account_id());
+
+        // The threshold is a part of the account ID:
+        let m3 = Multisig::<PolkadotConfig>::new(3, m1.signatories().to_vec());
+        assert_ne!(m1.account_id(), m3.account_id());
+    }
+}
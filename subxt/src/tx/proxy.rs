@@ -0,0 +1,35 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use crate::config::Config;
+use codec::Encode;
+use scale_value::Value;
+use subxt_core::tx::payload::{NestedCallPayload, Payload};
+
+/// Wrap a call in `Proxy::proxy`, so that it is dispatched on behalf of the `real`
+/// account by whoever signs the resulting transaction. The signer must be registered
+/// as a proxy of `real` for the call to succeed.
+///
+/// No `force_proxy_type` is given, so any matching proxy type registered for the signer
+/// will be used.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use subxt::dynamic::Value;
+/// use subxt::{PolkadotConfig, tx};
+/// use subxt_signer::sr25519::dev;
+///
+/// let remark = tx::dynamic("System", "remark", vec![Value::from_bytes("Hello")]);
+/// let proxied = tx::proxy::<PolkadotConfig>(dev::alice().public_key().into(), remark);
+/// ```
+pub fn proxy<T: Config>(
+    real: T::AccountId,
+    call: impl Payload + Send + Sync + 'static,
+) -> NestedCallPayload {
+    NestedCallPayload::new("Proxy", "proxy")
+        .encoded_arg("real", T::Address::from(real).encode())
+        .arg("force_proxy_type", Value::unnamed_variant("None", []))
+        .call_arg("call", call)
+}
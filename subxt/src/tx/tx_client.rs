@@ -4,15 +4,27 @@
 
 use crate::{
     backend::{BackendExt, BlockRef, TransactionStatus},
+    blocks::{ExtrinsicTransactionExtensions, StaticExtrinsic},
     client::{OfflineClientT, OnlineClientT},
+    config::transaction_extensions::{
+        ChargeAssetTxPayment, ChargeTransactionPayment, CheckMortality,
+    },
     config::{Config, ExtrinsicParams, HashFor, Header},
-    error::{BlockError, Error},
-    tx::{Payload, Signer as SignerT, TxProgress},
-    utils::PhantomDataSendSync,
+    error::{BlockError, Error, ExtrinsicError, RpcError},
+    tx::tx_progress::{
+        FinalizedBlockScanner, TipBumpOutcome, TxOutcome, TxStatus, latest_finalized_block_number,
+        transaction_death,
+    },
+    tx::{
+        Payload, ResilientTxProgress, Signer as SignerT, TransactionDescription, TxProgress,
+        UnsignedTransactionEnvelope,
+    },
+    utils::{Era, PhantomDataSendSync},
 };
 use codec::{Compact, Decode, Encode};
 use derive_where::derive_where;
-use futures::future::try_join;
+use either::Either;
+use futures::{StreamExt, future::try_join};
 use subxt_core::tx::TransactionVersion;
 
 /// A client for working with transactions.
@@ -68,6 +80,9 @@ impl<T: Config, C: OfflineClientT<T>> TxClient<T, C> {
         Ok(SubmittableTransaction {
             client: self.client.clone(),
             inner: tx,
+            era: None,
+            birth_block: None,
+            v4_parts: None,
         })
     }
 
@@ -88,6 +103,9 @@ impl<T: Config, C: OfflineClientT<T>> TxClient<T, C> {
         Ok(SubmittableTransaction {
             client: self.client.clone(),
             inner: tx,
+            era: None,
+            birth_block: None,
+            v4_parts: None,
         })
     }
 
@@ -105,6 +123,9 @@ impl<T: Config, C: OfflineClientT<T>> TxClient<T, C> {
         Ok(SubmittableTransaction {
             client: self.client.clone(),
             inner: tx,
+            era: None,
+            birth_block: None,
+            v4_parts: None,
         })
     }
 
@@ -122,6 +143,7 @@ impl<T: Config, C: OfflineClientT<T>> TxClient<T, C> {
         Call: Payload,
     {
         let metadata = self.client.metadata();
+        let era = era_from_params::<T>(&params, &metadata);
         let tx = match subxt_core::tx::suggested_version(&metadata)? {
             TransactionVersion::V4 => PartialTransactionInner::V4(
                 subxt_core::tx::create_v4_signed(call, &self.client.client_state(), params)?,
@@ -131,10 +153,7 @@ impl<T: Config, C: OfflineClientT<T>> TxClient<T, C> {
             ),
         };
 
-        Ok(PartialTransaction {
-            client: self.client.clone(),
-            inner: tx,
-        })
+        PartialTransaction::new(self.client.clone(), tx, era)
     }
 
     /// Create a v4 partial transaction, ready to sign.
@@ -152,16 +171,14 @@ impl<T: Config, C: OfflineClientT<T>> TxClient<T, C> {
     where
         Call: Payload,
     {
+        let era = era_from_params::<T>(&params, &self.client.metadata());
         let tx = PartialTransactionInner::V4(subxt_core::tx::create_v4_signed(
             call,
             &self.client.client_state(),
             params,
         )?);
 
-        Ok(PartialTransaction {
-            client: self.client.clone(),
-            inner: tx,
-        })
+        PartialTransaction::new(self.client.clone(), tx, era)
     }
 
     /// Create a v5 partial transaction, ready to sign.
@@ -179,16 +196,14 @@ impl<T: Config, C: OfflineClientT<T>> TxClient<T, C> {
     where
         Call: Payload,
     {
+        let era = era_from_params::<T>(&params, &self.client.metadata());
         let tx = PartialTransactionInner::V5(subxt_core::tx::create_v5_general(
             call,
             &self.client.client_state(),
             params,
         )?);
 
-        Ok(PartialTransaction {
-            client: self.client.clone(),
-            inner: tx,
-        })
+        PartialTransaction::new(self.client.clone(), tx, era)
     }
 }
 
@@ -203,6 +218,40 @@ where
         crate::blocks::get_account_nonce(&self.client, account_id, block_ref.hash()).await
     }
 
+    /// Fetch the transactions that are currently waiting in the node's transaction pool, and
+    /// decode them. The given [`PendingFilter`] can be used to only return transactions from
+    /// some signer, or with some nonce.
+    ///
+    /// This is useful for finding transactions which are stuck in the pool, so that they can be
+    /// replaced by submitting a new transaction with the same nonce and a higher tip.
+    ///
+    /// Each transaction is decoded separately. Any which can't be decoded (for instance because
+    /// they were created for a different runtime version) are returned as errors, since it's not
+    /// possible to tell whether they match the filter.
+    pub async fn pending(
+        &self,
+        filter: PendingFilter<T>,
+    ) -> Result<Vec<Result<PendingTransaction<T>, Error>>, Error> {
+        let pending = self.client.backend().pending_transactions().await?;
+        let metadata = self.client.metadata();
+
+        let decode = |tx_bytes: Vec<u8>| {
+            let extrinsics =
+                subxt_core::blocks::decode_from::<T>(vec![tx_bytes], metadata.clone())?;
+            Ok(extrinsics
+                .iter()
+                .next()
+                .expect("a single extrinsic was decoded above; qed"))
+        };
+
+        Ok(pending
+            .into_iter()
+            .map(decode)
+            .filter(|ext| ext.as_ref().map_or(true, |ext| filter.matches(ext)))
+            .map(|ext| ext.map(|inner| PendingTransaction { inner }))
+            .collect())
+    }
+
     /// Creates a partial transaction, without submitting it. This can then be signed and submitted.
     pub async fn create_partial<Call>(
         &self,
@@ -213,8 +262,11 @@ where
     where
         Call: Payload,
     {
-        inject_account_nonce_and_block(&self.client, account_id, &mut params).await?;
-        self.create_partial_offline(call, params)
+        let block_number =
+            inject_account_nonce_and_block(&self.client, account_id, &mut params).await?;
+        let mut partial_tx = self.create_partial_offline(call, params)?;
+        partial_tx.block_number = Some(block_number);
+        Ok(partial_tx)
     }
 
     /// Creates a partial V4 transaction, without submitting it. This can then be signed and submitted.
@@ -230,8 +282,11 @@ where
     where
         Call: Payload,
     {
-        inject_account_nonce_and_block(&self.client, account_id, &mut params).await?;
-        self.create_v4_partial_offline(call, params)
+        let block_number =
+            inject_account_nonce_and_block(&self.client, account_id, &mut params).await?;
+        let mut partial_tx = self.create_v4_partial_offline(call, params)?;
+        partial_tx.block_number = Some(block_number);
+        Ok(partial_tx)
     }
 
     /// Creates a partial V5 transaction, without submitting it. This can then be signed and submitted.
@@ -247,8 +302,11 @@ where
     where
         Call: Payload,
     {
-        inject_account_nonce_and_block(&self.client, account_id, &mut params).await?;
-        self.create_v5_partial_offline(call, params)
+        let block_number =
+            inject_account_nonce_and_block(&self.client, account_id, &mut params).await?;
+        let mut partial_tx = self.create_v5_partial_offline(call, params)?;
+        partial_tx.block_number = Some(block_number);
+        Ok(partial_tx)
     }
 
     /// Creates a signed transaction, without submitting it.
@@ -361,6 +419,10 @@ where
 pub struct PartialTransaction<T: Config, C> {
     client: C,
     inner: PartialTransactionInner<T>,
+    // The mortality of this transaction, if it has one.
+    era: Option<Era>,
+    // The block number that this transaction was created at, if known.
+    block_number: Option<u64>,
 }
 
 enum PartialTransactionInner<T: Config> {
@@ -373,6 +435,35 @@ where
     T: Config,
     C: OfflineClientT<T>,
 {
+    // The era is taken from the params that the transaction was created with where possible,
+    // and otherwise decoded from the transaction extension values.
+    fn new(
+        client: C,
+        inner: PartialTransactionInner<T>,
+        era: Option<Option<Era>>,
+    ) -> Result<Self, Error> {
+        let era = match era {
+            Some(era) => era,
+            None => match &inner {
+                PartialTransactionInner::V4(tx) => decode_era_from_parts::<T>(
+                    tx.call_data(),
+                    tx.transaction_extension_values(),
+                    client.metadata(),
+                )?,
+                PartialTransactionInner::V5(tx) => {
+                    decode_era::<T>(tx.to_transaction().encoded(), client.metadata())?
+                }
+            },
+        };
+
+        Ok(PartialTransaction {
+            client,
+            inner,
+            era,
+            block_number: None,
+        })
+    }
+
     /// Return the signer payload for this transaction. These are the bytes that must
     /// be signed in order to produce a valid signature for the transaction.
     pub fn signer_payload(&self) -> Vec<u8> {
@@ -391,6 +482,97 @@ where
         }
     }
 
+    /// Return the SCALE encoded RFC-78 metadata proof for this transaction. This contains the
+    /// type information needed to decode the transaction, and is what offline signers which
+    /// check the metadata hash (for instance hardware wallets) need alongside the signer payload.
+    ///
+    /// This is only supported for V4 transactions.
+    #[cfg(feature = "merkleized-metadata")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "merkleized-metadata")))]
+    pub fn metadata_proof(&self) -> Result<Vec<u8>, Error> {
+        match &self.inner {
+            PartialTransactionInner::V4(tx) => Ok(tx.metadata_proof(&self.client.metadata())?),
+            PartialTransactionInner::V5(_) => Err(ExtrinsicError::UnsupportedVersion.into()),
+        }
+    }
+
+    /// Decode this transaction into a [`TransactionDescription`], which contains the call (including
+    /// any calls nested inside it, for instance in batches, proxies and multisigs), each of
+    /// the transaction extension values and the implicit data that is signed along with them.
+    /// This can be displayed or rendered as JSON in order to check exactly what is being signed.
+    ///
+    /// This is only supported for V4 transactions.
+    pub fn describe(&self) -> Result<TransactionDescription, Error> {
+        let PartialTransactionInner::V4(tx) = &self.inner else {
+            return Err(ExtrinsicError::UnsupportedVersion.into());
+        };
+
+        // V4 transactions always use version 0 of the transaction extensions.
+        TransactionDescription::decode::<T>(
+            tx.call_data(),
+            tx.transaction_extension_values(),
+            tx.transaction_extension_implicit(),
+            0,
+            self.client.metadata(),
+        )
+    }
+
+    /// Convert this transaction into an [`UnsignedTransactionEnvelope`], which can be serialized
+    /// and handed to another machine to be signed. Use [`PartialTransaction::from_envelope()`]
+    /// to rebuild the transaction from it once a signature has been obtained.
+    ///
+    /// This is only supported for V4 transactions.
+    pub fn to_envelope(&self) -> Result<UnsignedTransactionEnvelope, Error> {
+        let PartialTransactionInner::V4(tx) = &self.inner else {
+            return Err(ExtrinsicError::UnsupportedVersion.into());
+        };
+
+        let metadata = self.client.metadata();
+        let description = self.describe()?;
+
+        Ok(UnsignedTransactionEnvelope {
+            version: UnsignedTransactionEnvelope::VERSION,
+            call_data: tx.call_data().to_vec(),
+            transaction_extension_values: tx.transaction_extension_values().to_vec(),
+            transaction_extension_signer_payload_values: tx
+                .transaction_extension_signer_payload_values()
+                .to_vec(),
+            transaction_extension_implicit: tx.transaction_extension_implicit().to_vec(),
+            subxt_metadata_hash: metadata.hasher().hash().into(),
+            call: description.to_string(),
+        })
+    }
+
+    /// Rebuild a transaction from an [`UnsignedTransactionEnvelope`], for instance so that a
+    /// signature obtained elsewhere can be attached with
+    /// [`PartialTransaction::sign_with_account_and_signature()`].
+    ///
+    /// An error is returned if the envelope version is not supported, or if the envelope was
+    /// created using different metadata from the client.
+    pub fn from_envelope(client: C, envelope: UnsignedTransactionEnvelope) -> Result<Self, Error> {
+        if envelope.version != UnsignedTransactionEnvelope::VERSION {
+            return Err(Error::Other(format!(
+                "Unsupported transaction envelope version {}; expected version {}",
+                envelope.version,
+                UnsignedTransactionEnvelope::VERSION
+            )));
+        }
+        if envelope.subxt_metadata_hash.0 != client.metadata().hasher().hash() {
+            return Err(Error::Other(
+                "The transaction envelope was created using different metadata from the client"
+                    .into(),
+            ));
+        }
+
+        let tx = subxt_core::tx::PartialTransactionV4::from_parts(
+            envelope.call_data,
+            envelope.transaction_extension_values,
+            envelope.transaction_extension_signer_payload_values,
+            envelope.transaction_extension_implicit,
+        );
+        PartialTransaction::new(client, PartialTransactionInner::V4(tx), None)
+    }
+
     /// Convert this [`PartialTransaction`] into a [`SubmittableTransaction`], ready to submit.
     /// The provided `signer` is responsible for providing the "from" address for the transaction,
     /// as well as providing a signature to attach to it.
@@ -403,10 +585,8 @@ where
             PartialTransactionInner::V5(tx) => tx.sign(signer),
         };
 
-        SubmittableTransaction {
-            client: self.client.clone(),
-            inner: tx,
-        }
+        SubmittableTransaction::from_signed(self.client.clone(), tx, self.era, self.block_number)
+            .with_v4_parts(self.v4_parts())
     }
 
     /// Convert this [`PartialTransaction`] into a [`SubmittableTransaction`], ready to submit.
@@ -427,17 +607,154 @@ where
             }
         };
 
-        SubmittableTransaction {
-            client: self.client.clone(),
-            inner: tx,
+        SubmittableTransaction::from_signed(self.client.clone(), tx, self.era, self.block_number)
+            .with_v4_parts(self.v4_parts())
+    }
+
+    // V4 transactions can be re-signed given the signer payload parts they were signed with.
+    fn v4_parts(&self) -> Option<V4SignerPayloadParts> {
+        match &self.inner {
+            PartialTransactionInner::V4(tx) => Some(V4SignerPayloadParts {
+                signer_payload_values: tx.transaction_extension_signer_payload_values().to_vec(),
+                implicit: tx.transaction_extension_implicit().to_vec(),
+            }),
+            PartialTransactionInner::V5(_) => None,
         }
     }
 }
 
+/// A filter for the transactions returned from [`TxClient::pending()`].
+#[derive_where(Clone, Debug, Default)]
+pub struct PendingFilter<T: Config> {
+    signer: Option<Vec<u8>>,
+    nonce: Option<u64>,
+    _marker: PhantomDataSendSync<T>,
+}
+
+impl<T: Config> PendingFilter<T> {
+    /// Create a filter which matches every pending transaction.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only match transactions signed by the given address.
+    pub fn signer(mut self, address: impl Into<T::Address>) -> Self {
+        self.signer = Some(address.into().encode());
+        self
+    }
+
+    /// Only match transactions with the given account nonce.
+    pub fn nonce(mut self, nonce: u64) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    fn matches(&self, ext: &subxt_core::blocks::ExtrinsicDetails<T>) -> bool {
+        if let Some(signer) = &self.signer {
+            if ext.address_bytes() != Some(&**signer) {
+                return false;
+            }
+        }
+        if let Some(nonce) = self.nonce {
+            let ext_nonce = ext.transaction_extensions().and_then(|e| e.nonce());
+            if ext_nonce != Some(nonce) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// A transaction which is waiting in the node's transaction pool, as returned from
+/// [`TxClient::pending()`]. This is not in any block yet, and so unlike
+/// [`crate::blocks::ExtrinsicDetails`], it has no block hash or events.
+pub struct PendingTransaction<T: Config> {
+    inner: subxt_core::blocks::ExtrinsicDetails<T>,
+}
+
+impl<T: Config> PendingTransaction<T> {
+    /// See [`subxt_core::blocks::ExtrinsicDetails::hash()`].
+    pub fn hash(&self) -> HashFor<T> {
+        self.inner.hash()
+    }
+
+    /// See [`subxt_core::blocks::ExtrinsicDetails::is_signed()`].
+    pub fn is_signed(&self) -> bool {
+        self.inner.is_signed()
+    }
+
+    /// See [`subxt_core::blocks::ExtrinsicDetails::bytes()`].
+    pub fn bytes(&self) -> &[u8] {
+        self.inner.bytes()
+    }
+
+    /// See [`subxt_core::blocks::ExtrinsicDetails::call_bytes()`].
+    pub fn call_bytes(&self) -> &[u8] {
+        self.inner.call_bytes()
+    }
+
+    /// See [`subxt_core::blocks::ExtrinsicDetails::field_bytes()`].
+    pub fn field_bytes(&self) -> &[u8] {
+        self.inner.field_bytes()
+    }
+
+    /// See [`subxt_core::blocks::ExtrinsicDetails::address_bytes()`].
+    pub fn address_bytes(&self) -> Option<&[u8]> {
+        self.inner.address_bytes()
+    }
+
+    /// See [`subxt_core::blocks::ExtrinsicDetails::signature_bytes()`].
+    pub fn signature_bytes(&self) -> Option<&[u8]> {
+        self.inner.signature_bytes()
+    }
+
+    /// See [`subxt_core::blocks::ExtrinsicDetails::transaction_extensions_bytes()`].
+    pub fn transaction_extensions_bytes(&self) -> Option<&[u8]> {
+        self.inner.transaction_extensions_bytes()
+    }
+
+    /// See [`subxt_core::blocks::ExtrinsicDetails::transaction_extensions()`].
+    pub fn transaction_extensions(&self) -> Option<ExtrinsicTransactionExtensions<'_, T>> {
+        self.inner.transaction_extensions()
+    }
+
+    /// See [`subxt_core::blocks::ExtrinsicDetails::pallet_name()`].
+    pub fn pallet_name(&self) -> Result<&str, Error> {
+        self.inner.pallet_name().map_err(Into::into)
+    }
+
+    /// See [`subxt_core::blocks::ExtrinsicDetails::variant_name()`].
+    pub fn variant_name(&self) -> Result<&str, Error> {
+        self.inner.variant_name().map_err(Into::into)
+    }
+
+    /// See [`subxt_core::blocks::ExtrinsicDetails::field_values()`].
+    pub fn field_values(&self) -> Result<scale_value::Composite<u32>, Error> {
+        self.inner.field_values().map_err(Into::into)
+    }
+
+    /// See [`subxt_core::blocks::ExtrinsicDetails::as_extrinsic()`].
+    pub fn as_extrinsic<E: StaticExtrinsic>(&self) -> Result<Option<E>, Error> {
+        self.inner.as_extrinsic::<E>().map_err(Into::into)
+    }
+}
+
 /// This represents an transaction that has been signed and is ready to submit.
 pub struct SubmittableTransaction<T, C> {
     client: C,
     inner: subxt_core::tx::Transaction<T>,
+    era: Option<Era>,
+    birth_block: Option<u64>,
+    // The parts of the signer payload that a V4 transaction was signed with, if known.
+    v4_parts: Option<V4SignerPayloadParts>,
+}
+
+/// The parts of a V4 signer payload, besides the call data, which are needed to sign a
+/// transaction again.
+#[derive(Clone)]
+struct V4SignerPayloadParts {
+    signer_payload_values: Vec<u8>,
+    implicit: Vec<u8>,
 }
 
 impl<T, C> SubmittableTransaction<T, C>
@@ -452,18 +769,186 @@ where
     /// Prefer to use [`TxClient`] to create and sign transactions. This is simply
     /// exposed in case you want to skip this process and submit something you've
     /// already created.
-    pub fn from_bytes(client: C, tx_bytes: Vec<u8>) -> Self {
+    ///
+    /// The transaction is decoded in order to find its mortality, and an error is
+    /// returned if this fails.
+    pub fn from_bytes(client: C, tx_bytes: Vec<u8>) -> Result<Self, Error> {
+        let inner = subxt_core::tx::Transaction::from_bytes(tx_bytes);
+        let era = decode_era::<T>(inner.encoded(), client.metadata())?;
+        Ok(Self::from_signed(client, inner, era, None))
+    }
+
+    // Work out when the mortality period of the transaction began, given
+    // some block number at which the transaction was created.
+    fn from_signed(
+        client: C,
+        inner: subxt_core::tx::Transaction<T>,
+        era: Option<Era>,
+        block_number: Option<u64>,
+    ) -> Self {
+        let birth_block = match era {
+            Some(era @ Era::Mortal { .. }) => block_number.map(|n| era.birth(n)),
+            _ => None,
+        };
         Self {
             client,
-            inner: subxt_core::tx::Transaction::from_bytes(tx_bytes),
+            inner,
+            era,
+            birth_block,
+            v4_parts: None,
         }
     }
 
+    fn with_v4_parts(mut self, v4_parts: Option<V4SignerPayloadParts>) -> Self {
+        self.v4_parts = v4_parts;
+        self
+    }
+
+    /// Set the number of a block that falls within the mortality period of this transaction
+    /// (for instance, the block whose hash was used to construct it). This is used to work out
+    /// when the transaction's mortality period begins and ends, and is only needed for
+    /// transactions created offline or with [`SubmittableTransaction::from_bytes()`].
+    pub fn with_birth_block(mut self, block_number: u64) -> Self {
+        if let Some(era @ Era::Mortal { .. }) = self.era {
+            self.birth_block = Some(era.birth(block_number));
+        }
+        self
+    }
+
+    /// The mortality of this transaction, or `None` if it has no `CheckMortality`
+    /// transaction extension (for instance because it is unsigned).
+    pub fn era(&self) -> Option<Era> {
+        self.era
+    }
+
+    /// The number of the block that the mortality period of this transaction begins at.
+    /// This is `None` for immortal transactions, and for mortal transactions whose birth
+    /// block is not known (see [`SubmittableTransaction::with_birth_block()`]).
+    pub fn birth_block(&self) -> Option<u64> {
+        self.birth_block
+    }
+
+    /// The number of the first block that this transaction can no longer be included in.
+    /// This is `None` for immortal transactions, and for mortal transactions whose birth
+    /// block is not known (see [`SubmittableTransaction::with_birth_block()`]).
+    pub fn death_block(&self) -> Option<u64> {
+        Some(self.era?.death(self.birth_block?))
+    }
+
     /// Calculate and return the hash of the transaction, based on the configured hasher.
     pub fn hash(&self) -> HashFor<T> {
         self.inner.hash_with(self.client.hasher())
     }
 
+    /// Sign this transaction again with a different tip, keeping the call, nonce, mortality
+    /// and every other transaction extension value the same. The tip is set in either the
+    /// `ChargeTransactionPayment` or the `ChargeAssetTxPayment` transaction extension.
+    ///
+    /// Since the nonce is unchanged, at most one of this and the returned transaction can be
+    /// included in a block. A transaction pool will generally replace the original with the
+    /// returned transaction if the tip is higher. See [`SubmittableTransaction::bump_tip()`]
+    /// to also submit the new transaction and wait for one of them to be included.
+    ///
+    /// This is only possible for V4 transactions which were signed from a [`PartialTransaction`],
+    /// since the signer payload that was signed is needed, and `signer` must be the account
+    /// which signed the original transaction.
+    pub fn with_tip<Signer>(&self, tip: u128, signer: &Signer) -> Result<Self, Error>
+    where
+        Signer: SignerT<T>,
+    {
+        let parts = self.v4_parts.clone().ok_or_else(|| {
+            Error::Other(
+                "Only V4 transactions signed from a PartialTransaction can be re-signed".into(),
+            )
+        })?;
+
+        let extrinsics = subxt_core::blocks::decode_from::<T>(
+            vec![self.encoded().to_vec()],
+            self.client.metadata(),
+        )?;
+        let ext = extrinsics
+            .iter()
+            .next()
+            .expect("one extrinsic was decoded; qed");
+
+        let address: T::Address = signer.account_id().into();
+        if ext.address_bytes() != Some(&*address.encode()) {
+            return Err(Error::Other(
+                "The signer must be the account which signed the original transaction".into(),
+            ));
+        }
+
+        // Swap the tip (which is the first field of both extensions) and keep everything else.
+        let mut extension_values = Vec::new();
+        let mut tip_range = None;
+        for extension in ext
+            .transaction_extensions()
+            .into_iter()
+            .flat_map(|e| e.iter())
+        {
+            let bytes = extension.bytes();
+            if matches!(
+                extension.name(),
+                "ChargeTransactionPayment" | "ChargeAssetTxPayment"
+            ) {
+                let mut rest = bytes;
+                Compact::<u128>::decode(&mut rest)?;
+                let start = extension_values.len();
+                Compact(tip).encode_to(&mut extension_values);
+                extension_values.extend_from_slice(rest);
+                tip_range = Some((start, start + bytes.len()));
+            } else {
+                extension_values.extend_from_slice(bytes);
+            }
+        }
+        let Some((tip_start, tip_end)) = tip_range else {
+            return Err(Error::Other(
+                "The transaction has no transaction extension which sets a tip".into(),
+            ));
+        };
+
+        // Extensions can sign something other than their values. Those which ship with Subxt
+        // (like `VerifySignature`) only ever drop the values of the extensions before them, so
+        // the new tip is signed as long as the signer payload values are a suffix of the values.
+        let signer_payload_values = &parts.signer_payload_values;
+        let original_values = ext.transaction_extensions_bytes().unwrap_or_default();
+        let Some(payload_start) = original_values
+            .ends_with(signer_payload_values)
+            .then(|| original_values.len() - signer_payload_values.len())
+        else {
+            return Err(Error::Other(
+                "The tip can't be changed because the transaction extensions sign values which aren't in the transaction".into(),
+            ));
+        };
+        let signer_payload_values = if payload_start <= tip_start {
+            extension_values[payload_start..].to_vec()
+        } else if payload_start >= tip_end {
+            signer_payload_values.clone()
+        } else {
+            return Err(Error::Other(
+                "The tip can't be changed because only part of it is signed".into(),
+            ));
+        };
+
+        let partial = subxt_core::tx::PartialTransactionV4::<T>::from_parts(
+            ext.call_bytes().to_vec(),
+            extension_values,
+            signer_payload_values.clone(),
+            parts.implicit.clone(),
+        );
+
+        Ok(Self {
+            client: self.client.clone(),
+            inner: partial.sign(signer),
+            era: self.era,
+            birth_block: self.birth_block,
+            v4_parts: Some(V4SignerPayloadParts {
+                signer_payload_values,
+                implicit: parts.implicit,
+            }),
+        })
+    }
+
     /// Returns the SCALE encoded transaction bytes.
     pub fn encoded(&self) -> &[u8] {
         self.inner.encoded()
@@ -496,7 +981,102 @@ where
             .submit_transaction(self.encoded())
             .await?;
 
-        Ok(TxProgress::new(sub, self.client.clone(), ext_hash))
+        Ok(TxProgress::new(sub, self.client.clone(), ext_hash)
+            .with_mortality(self.era, self.birth_block))
+    }
+
+    /// Submits the transaction to the chain, returning a [`ResilientTxProgress`] which
+    /// keeps track of the transaction even if the connection to the node drops. This is
+    /// most useful alongside a client which reconnects, such as one built on the
+    /// reconnecting RPC client.
+    ///
+    /// If the connection drops, the transaction is submitted again. If the node no longer
+    /// recognises it, then new finalized blocks are checked for the transaction until it
+    /// is found, or until its mortality period has ended, at which point it is reported as
+    /// expired. Immortal transactions never expire.
+    pub async fn submit_and_watch_resilient(&self) -> Result<ResilientTxProgress<T, C>, Error> {
+        // The transaction can't already be in any block that's finalized now.
+        let block_number = latest_finalized_block_number(&self.client).await?;
+        let death = transaction_death(&self.client, self.era, self.birth_block).await?;
+        let progress = self.submit_and_watch().await?;
+
+        Ok(ResilientTxProgress::new(
+            progress,
+            self.client.clone(),
+            self.encoded().to_vec(),
+            death,
+            block_number,
+        ))
+    }
+
+    /// Sign this transaction again with a higher tip, using [`SubmittableTransaction::with_tip()`],
+    /// and submit the new transaction to replace this one in the transaction pool. Both
+    /// transactions have the same nonce, so this then waits until one of them is included in a
+    /// finalized block, or until their (shared) mortality period has ended.
+    ///
+    /// This transaction is expected to have been submitted already. An error is returned if its
+    /// tip cannot be decoded, if `new_tip` is not higher than it, or if the node refuses to accept
+    /// the new transaction when it is submitted. Once the new transaction has been accepted, it
+    /// becoming invalid or being dropped does not end the wait, since that is also what happens
+    /// when the original transaction is included in a block.
+    pub async fn bump_tip<Signer>(
+        &self,
+        new_tip: u128,
+        signer: &Signer,
+    ) -> Result<TipBumpOutcome<T, C>, Error>
+    where
+        Signer: SignerT<T>,
+    {
+        let current_tip = decode_tip::<T>(self.encoded(), self.client.metadata())?;
+        if new_tip <= current_tip {
+            return Err(Error::Other(format!(
+                "The new tip ({new_tip}) must be higher than the current tip ({current_tip})"
+            )));
+        }
+
+        let replacement = self.with_tip(new_tip, signer)?;
+        let original_hash = self.hash();
+
+        // Blocks from the start of the mortality period may contain the original transaction.
+        let checked_up_to = match self.birth_block {
+            Some(birth_block) => birth_block.saturating_sub(1),
+            None => latest_finalized_block_number(&self.client).await?,
+        };
+        let death = transaction_death(&self.client, self.era, self.birth_block).await?;
+        let mut scanner = FinalizedBlockScanner::new(
+            vec![original_hash, replacement.hash()],
+            death,
+            checked_up_to,
+        );
+
+        let progress = replacement.submit_and_watch().await?;
+        let blocks = self.client.blocks().subscribe_finalized().await?;
+        let mut updates =
+            futures::stream::select(progress.map(Either::Left), blocks.map(Either::Right));
+
+        while let Some(update) = updates.next().await {
+            let outcome = match update {
+                Either::Left(Ok(TxStatus::InFinalizedBlock(tx_in_block))) => {
+                    TxOutcome::Included(tx_in_block)
+                }
+                // The replacement becomes invalid if the original is included, and the original
+                // may yet be included even if the replacement is dropped, so only the finalized
+                // blocks are conclusive.
+                Either::Left(_) => continue,
+                Either::Right(block) => match scanner.check(&self.client, block?).await? {
+                    Some(outcome) => outcome,
+                    None => continue,
+                },
+            };
+            return Ok(match outcome {
+                TxOutcome::Included(b) if b.extrinsic_hash() == original_hash => {
+                    TipBumpOutcome::Original(b)
+                }
+                TxOutcome::Included(b) => TipBumpOutcome::Replacement(b),
+                TxOutcome::Expired => TipBumpOutcome::Expired,
+            });
+        }
+        Err(RpcError::SubscriptionDropped.into())
     }
 
     /// Submits the transaction to the chain for block inclusion.
@@ -597,12 +1177,88 @@ where
     }
 }
 
+/// Decode the tip of a transaction, which is set in either the `ChargeTransactionPayment` or
+/// the `ChargeAssetTxPayment` transaction extension.
+fn decode_tip<T: Config>(tx_bytes: &[u8], metadata: crate::Metadata) -> Result<u128, Error> {
+    let extrinsics = subxt_core::blocks::decode_from::<T>(vec![tx_bytes.to_vec()], metadata)?;
+    let extrinsic = extrinsics
+        .iter()
+        .next()
+        .expect("one extrinsic was decoded; qed");
+    let extensions = extrinsic
+        .transaction_extensions()
+        .ok_or_else(|| Error::Other("The transaction is not signed".into()))?;
+
+    if let Some(payment) = extensions.find::<ChargeTransactionPayment>()? {
+        return Ok(payment.tip());
+    }
+    if let Some(payment) = extensions.find::<ChargeAssetTxPayment<T>>()? {
+        return Ok(payment.tip());
+    }
+    Err(Error::Other(
+        "The transaction has no transaction extension which sets a tip".into(),
+    ))
+}
+
+/// Work out the mortality of a transaction from the params used to create it. `None` is
+/// returned if the params don't determine it, for instance because a dynamic value was
+/// given for the `CheckMortality` transaction extension.
+fn era_from_params<T: Config>(
+    params: &<T::ExtrinsicParams as ExtrinsicParams<T>>::Params,
+    metadata: &crate::Metadata,
+) -> Option<Option<Era>> {
+    use subxt_core::config::transaction_extensions::Params;
+
+    let has_mortality = metadata
+        .extrinsic()
+        .transaction_extensions_to_use_for_encoding()
+        .any(|e| e.identifier() == "CheckMortality");
+    if !has_mortality {
+        return Some(None);
+    }
+    if params
+        .transaction_extension_values("CheckMortality")
+        .is_some()
+    {
+        return None;
+    }
+    params.mortality().map(Some)
+}
+
+/// Decode the mortality of a transaction, if it has one.
+fn decode_era<T: Config>(tx_bytes: &[u8], metadata: crate::Metadata) -> Result<Option<Era>, Error> {
+    let extrinsics = subxt_core::blocks::decode_from::<T>(vec![tx_bytes.to_vec()], metadata)?;
+    let extrinsic = extrinsics
+        .iter()
+        .next()
+        .expect("a single extrinsic was decoded above; qed");
+    match extrinsic.transaction_extensions() {
+        Some(extensions) => Ok(extensions.find::<CheckMortality<T>>()?),
+        None => Ok(None),
+    }
+}
+
+/// Decode the mortality of a transaction given its call data and transaction extension values.
+fn decode_era_from_parts<T: Config>(
+    call_data: &[u8],
+    transaction_extension_values: &[u8],
+    metadata: crate::Metadata,
+) -> Result<Option<Era>, Error> {
+    // As in `TransactionDescription::decode()`, put these together into a V5 general
+    // transaction (V4 transactions always use version 0 of the transaction extensions).
+    let mut bytes = vec![0b0100_0000 | 5, 0];
+    bytes.extend_from_slice(transaction_extension_values);
+    bytes.extend_from_slice(call_data);
+    decode_era::<T>(&bytes.encode(), metadata)
+}
+
 /// Fetch the latest block header and account nonce from the backend and use them to refine [`ExtrinsicParams::Params`].
+/// Returns the number of the block that was used.
 async fn inject_account_nonce_and_block<T: Config, Client: OnlineClientT<T>>(
     client: &Client,
     account_id: &T::AccountId,
     params: &mut <T::ExtrinsicParams as ExtrinsicParams<T>>::Params,
-) -> Result<(), Error> {
+) -> Result<u64, Error> {
     use subxt_core::config::transaction_extensions::Params;
 
     let block_ref = client.backend().latest_finalized_block_ref().await?;
@@ -616,10 +1272,11 @@ async fn inject_account_nonce_and_block<T: Config, Client: OnlineClientT<T>>(
     let block_header =
         block_header.ok_or_else(|| Error::Block(BlockError::not_found(block_ref.hash())))?;
 
+    let block_number = block_header.number().into();
     params.inject_account_nonce(account_nonce);
-    params.inject_block(block_header.number().into(), block_ref.hash());
+    params.inject_block(block_number, block_ref.hash());
 
-    Ok(())
+    Ok(block_number)
 }
 
 impl ValidationResult {
@@ -822,6 +1479,7 @@ const _: () = {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::test_utils::offline_client;
 
     #[test]
     fn transaction_validity_decoding_empty_bytes() {
@@ -942,4 +1600,341 @@ mod test {
             assert_eq!(decoded, validation_result);
         }
     }
+
+    fn remark() -> crate::tx::DynamicPayload {
+        crate::dynamic::tx(
+            "System",
+            "remark",
+            vec![scale_value::Value::from_bytes("Hello")],
+        )
+    }
+
+    #[test]
+    fn with_tip_changes_only_the_tip() {
+        use crate::config::{DefaultExtrinsicParamsBuilder as Params, PolkadotConfig};
+        use subxt_signer::sr25519::{self, dev};
+
+        let client = offline_client();
+        let metadata = client.metadata();
+        let call = remark();
+        let partial = |tip| {
+            client
+                .tx()
+                .create_v4_partial_offline(&call, Params::new().nonce(5).tip(tip).build())
+                .unwrap()
+        };
+        let decode = |tx: &SubmittableTransaction<_, _>| {
+            subxt_core::blocks::decode_from::<PolkadotConfig>(
+                vec![tx.encoded().to_vec()],
+                metadata.clone(),
+            )
+            .unwrap()
+            .iter()
+            .next()
+            .unwrap()
+        };
+
+        let tx = partial(1).sign(&dev::alice());
+        let bumped = tx.with_tip(100, &dev::alice()).unwrap();
+        let ext = decode(&bumped);
+
+        // The same as if it had been created with the higher tip in the first place:
+        let expected = partial(100);
+        assert_eq!(ext.call_bytes(), expected.call_data());
+        let expected_ext = decode(&partial(100).sign(&dev::alice()));
+        assert_eq!(
+            ext.transaction_extensions_bytes(),
+            expected_ext.transaction_extensions_bytes()
+        );
+        assert_eq!(ext.transaction_extensions().unwrap().tip(), Some(100));
+        assert_eq!(ext.transaction_extensions().unwrap().nonce(), Some(5));
+
+        // With a valid signature:
+        let signature: [u8; 64] = ext.signature_bytes().unwrap()[1..].try_into().unwrap();
+        assert!(sr25519::verify(
+            &sr25519::Signature(signature),
+            expected.signer_payload(),
+            &dev::alice().public_key()
+        ));
+
+        // Only the original signer can re-sign it:
+        assert!(tx.with_tip(100, &dev::bob()).is_err());
+    }
+
+    /// Extrinsic params which sign all but the first byte of the transaction extension values,
+    /// as an extension like `VerifySignature` does with the values before it.
+    struct SkipFirstSignedByte<P>(P);
+
+    impl<T: Config, P: ExtrinsicParams<T>> ExtrinsicParams<T> for SkipFirstSignedByte<P> {
+        type Params = P::Params;
+
+        fn new(
+            client: &subxt_core::client::ClientState<T>,
+            params: Self::Params,
+        ) -> Result<Self, subxt_core::error::ExtrinsicParamsError> {
+            Ok(SkipFirstSignedByte(P::new(client, params)?))
+        }
+    }
+
+    impl<P: crate::config::ExtrinsicParamsEncoder> crate::config::ExtrinsicParamsEncoder
+        for SkipFirstSignedByte<P>
+    {
+        fn encode_value_to(&self, v: &mut Vec<u8>) {
+            self.0.encode_value_to(v);
+        }
+        fn encode_signer_payload_value_to(&self, v: &mut Vec<u8>) {
+            let mut values = Vec::new();
+            self.0.encode_signer_payload_value_to(&mut values);
+            v.extend_from_slice(&values[1..]);
+        }
+        fn encode_implicit_to(&self, v: &mut Vec<u8>) {
+            self.0.encode_implicit_to(v);
+        }
+    }
+
+    enum SkipFirstSignedByteConfig {}
+
+    impl Config for SkipFirstSignedByteConfig {
+        type AccountId = <crate::PolkadotConfig as Config>::AccountId;
+        type Address = <crate::PolkadotConfig as Config>::Address;
+        type Signature = <crate::PolkadotConfig as Config>::Signature;
+        type Hasher = <crate::PolkadotConfig as Config>::Hasher;
+        type Header = <crate::PolkadotConfig as Config>::Header;
+        type AssetId = <crate::PolkadotConfig as Config>::AssetId;
+        type ExtrinsicParams =
+            SkipFirstSignedByte<crate::config::PolkadotExtrinsicParams<SkipFirstSignedByteConfig>>;
+    }
+
+    fn skip_first_signed_byte_client() -> crate::OfflineClient<SkipFirstSignedByteConfig> {
+        crate::OfflineClient::new(
+            Default::default(),
+            crate::client::RuntimeVersion {
+                spec_version: 1,
+                transaction_version: 1,
+            },
+            crate::test_utils::metadata(),
+        )
+    }
+
+    #[test]
+    fn with_tip_signs_the_signer_payload_values() {
+        use crate::config::DefaultExtrinsicParamsBuilder as Params;
+        use subxt_signer::sr25519::{self, dev};
+
+        let client = skip_first_signed_byte_client();
+        let partial = |tip| {
+            client
+                .tx()
+                .create_v4_partial_offline(&remark(), Params::new().nonce(5).tip(tip).build())
+                .unwrap()
+        };
+
+        // The signer payload doesn't contain the values as they appear in the transaction:
+        let PartialTransactionInner::V4(inner) = &partial(1).inner else {
+            panic!("expected a V4 transaction");
+        };
+        assert_ne!(
+            inner.transaction_extension_signer_payload_values(),
+            inner.transaction_extension_values()
+        );
+
+        let tx = partial(1).sign(&dev::alice());
+        let bumped = tx.with_tip(100, &dev::alice()).unwrap();
+        let ext = subxt_core::blocks::decode_from::<SkipFirstSignedByteConfig>(
+            vec![bumped.encoded().to_vec()],
+            client.metadata(),
+        )
+        .unwrap()
+        .iter()
+        .next()
+        .unwrap();
+        assert_eq!(ext.transaction_extensions().unwrap().tip(), Some(100));
+
+        let signature: [u8; 64] = ext.signature_bytes().unwrap()[1..].try_into().unwrap();
+        assert!(sr25519::verify(
+            &sr25519::Signature(signature),
+            partial(100).signer_payload(),
+            &dev::alice().public_key()
+        ));
+    }
+
+    #[test]
+    fn envelope_signs_the_signer_payload_values() {
+        use crate::config::DefaultExtrinsicParamsBuilder as Params;
+
+        let client = skip_first_signed_byte_client();
+        let partial = client
+            .tx()
+            .create_v4_partial_offline(&remark(), Params::new().nonce(1).build())
+            .unwrap();
+
+        let envelope = partial.to_envelope().unwrap();
+        assert_ne!(
+            envelope.transaction_extension_signer_payload_values,
+            envelope.transaction_extension_values
+        );
+        assert_eq!(envelope.signer_payload(), partial.signer_payload());
+
+        let rebuilt = PartialTransaction::from_envelope(client, envelope).unwrap();
+        assert_eq!(rebuilt.signer_payload(), partial.signer_payload());
+    }
+
+    #[test]
+    fn era_is_taken_from_params_or_decoded() {
+        use crate::config::DefaultExtrinsicParamsBuilder as Params;
+        use crate::utils::H256;
+        use subxt_signer::sr25519::dev;
+
+        let client = offline_client();
+        let mut partial = client
+            .tx()
+            .create_v4_partial_offline(
+                &remark(),
+                Params::new()
+                    .mortal_from_unchecked(32, 100, H256::zero())
+                    .build(),
+            )
+            .unwrap();
+        let tx = partial.sign(&dev::alice());
+        assert_eq!(tx.era(), Some(Era::mortal(32, 100)));
+
+        // The era is decoded from transaction bytes and envelopes:
+        let from_bytes =
+            SubmittableTransaction::from_bytes(client.clone(), tx.encoded().to_vec()).unwrap();
+        assert_eq!(from_bytes.era(), tx.era());
+        let envelope = partial.to_envelope().unwrap();
+        let from_envelope = PartialTransaction::from_envelope(client.clone(), envelope)
+            .unwrap()
+            .sign(&dev::alice());
+        assert_eq!(from_envelope.era(), tx.era());
+
+        // Transactions created offline without a block are immortal:
+        let immortal = client
+            .tx()
+            .create_v4_partial_offline(&remark(), Params::new().build())
+            .unwrap()
+            .sign(&dev::alice());
+        assert_eq!(immortal.era(), Some(Era::Immortal));
+
+        // Unsigned transactions have no mortality:
+        let unsigned = client.tx().create_unsigned(&remark()).unwrap();
+        let unsigned =
+            SubmittableTransaction::from_bytes(client.clone(), unsigned.into_encoded()).unwrap();
+        assert_eq!(unsigned.era(), None);
+
+        // Bytes which aren't a transaction can't be decoded:
+        assert!(SubmittableTransaction::from_bytes(client, vec![1, 2, 3]).is_err());
+    }
+
+    #[tokio::test]
+    async fn pending_reports_undecodable_transactions() {
+        use crate::backend::legacy::LegacyBackend;
+        use crate::config::{DefaultExtrinsicParamsBuilder as Params, PolkadotConfig};
+        use crate::{OnlineClient, tx::PendingFilter};
+        use subxt_rpcs::client::{MockRpcClient, mock_rpc_client::Json};
+        use subxt_signer::sr25519::dev;
+
+        let offline = offline_client();
+        let tx = offline
+            .tx()
+            .create_v4_partial_offline(&remark(), Params::new().nonce(3).build())
+            .unwrap()
+            .sign(&dev::alice());
+        let tx_hex = format!("0x{}", hex::encode(tx.encoded()));
+
+        let rpc_client = MockRpcClient::builder()
+            .method_handler("author_pendingExtrinsics", move |_params| {
+                let pending = vec![tx_hex.clone(), "0x010203".to_owned()];
+                async move { Json(pending) }
+            })
+            .build();
+        let backend: LegacyBackend<PolkadotConfig> = LegacyBackend::builder().build(rpc_client);
+        let client = OnlineClient::from_backend_with(
+            offline.genesis_hash(),
+            offline.runtime_version(),
+            offline.metadata(),
+            std::sync::Arc::new(backend),
+        )
+        .unwrap();
+
+        // The transaction which can't be decoded is reported alongside the one which can:
+        let pending = client.tx().pending(PendingFilter::new()).await.unwrap();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].as_ref().unwrap().hash(), tx.hash());
+        assert!(pending[1].is_err());
+
+        // It's not possible to tell whether it matches the filter, so it's reported either way:
+        let pending = client
+            .tx()
+            .pending(PendingFilter::new().nonce(4))
+            .await
+            .unwrap();
+        assert_eq!(pending.len(), 1);
+        assert!(pending[0].is_err());
+    }
+
+    #[test]
+    fn decode_tip_returns_tip_or_error() {
+        use crate::config::{DefaultExtrinsicParamsBuilder as Params, PolkadotConfig};
+        use subxt_signer::sr25519::dev;
+
+        let client = offline_client();
+        let tx = client
+            .tx()
+            .create_v4_partial_offline(&remark(), Params::new().tip(123).build())
+            .unwrap()
+            .sign(&dev::alice());
+        assert_eq!(
+            decode_tip::<PolkadotConfig>(tx.encoded(), client.metadata()).unwrap(),
+            123
+        );
+
+        // Unsigned transactions have no tip to bump:
+        let unsigned = client.tx().create_unsigned(&remark()).unwrap();
+        assert!(decode_tip::<PolkadotConfig>(unsigned.encoded(), client.metadata()).is_err());
+
+        // Bytes which aren't a transaction can't be decoded:
+        assert!(decode_tip::<PolkadotConfig>(&[1, 2, 3], client.metadata()).is_err());
+    }
+
+    #[test]
+    fn envelope_roundtrip() {
+        use crate::config::DefaultExtrinsicParamsBuilder as Params;
+        use crate::utils::MultiSignature;
+        use subxt_signer::sr25519::dev;
+
+        let client = offline_client();
+        let mut partial = client
+            .tx()
+            .create_v4_partial_offline(&remark(), Params::new().nonce(1).build())
+            .unwrap();
+
+        let envelope = partial.to_envelope().unwrap();
+        assert_eq!(envelope.signer_payload(), partial.signer_payload());
+        assert!(envelope.call.contains("remark"));
+
+        // Send it somewhere else and back again:
+        let json = serde_json::to_string(&envelope).unwrap();
+        let envelope: UnsignedTransactionEnvelope = serde_json::from_str(&json).unwrap();
+        let mut rebuilt =
+            PartialTransaction::from_envelope(client.clone(), envelope.clone()).unwrap();
+
+        let account_id = dev::alice().public_key().to_account_id();
+        let signature = MultiSignature::Sr25519([1; 64]);
+        assert_eq!(
+            rebuilt
+                .sign_with_account_and_signature(&account_id, &signature)
+                .encoded(),
+            partial
+                .sign_with_account_and_signature(&account_id, &signature)
+                .encoded()
+        );
+
+        // Envelopes from other metadata are rejected:
+        let other_metadata = UnsignedTransactionEnvelope {
+            subxt_metadata_hash: Default::default(),
+            ..envelope
+        };
+        assert!(PartialTransaction::from_envelope(client, other_metadata).is_err());
+    }
 }
@@ -5,16 +5,21 @@
 //! Types representing extrinsics/transactions that have been submitted to a node.
 
 use std::task::Poll;
+use std::time::Duration;
 
 use crate::{
-    backend::{BlockRef, StreamOfResults, TransactionStatus as BackendTxStatus},
+    backend::{
+        BlockRef, StreamOfResults, TransactionStatus as BackendTxStatus, timeout::with_timeout,
+    },
+    blocks::Block,
     client::OnlineClientT,
-    config::{Config, HashFor},
-    error::{DispatchError, Error, RpcError, TransactionError},
+    config::{Config, HashFor, Header},
+    error::{BlockError, DispatchError, Error, RpcError, TransactionError},
     events::EventsClient,
-    utils::strip_compact_prefix,
+    utils::{Era, strip_compact_prefix},
 };
 use derive_where::derive_where;
+use either::Either;
 use futures::{Stream, StreamExt};
 
 /// This struct represents a subscription to the progress of some transaction.
@@ -22,6 +27,8 @@ pub struct TxProgress<T: Config, C> {
     sub: Option<StreamOfResults<BackendTxStatus<HashFor<T>>>>,
     ext_hash: HashFor<T>,
     client: C,
+    era: Option<Era>,
+    birth_block: Option<u64>,
 }
 
 impl<T: Config, C> std::fmt::Debug for TxProgress<T, C> {
@@ -29,6 +36,8 @@ impl<T: Config, C> std::fmt::Debug for TxProgress<T, C> {
         f.debug_struct("TxProgress")
             .field("sub", &"<subscription>")
             .field("ext_hash", &self.ext_hash)
+            .field("era", &self.era)
+            .field("birth_block", &self.birth_block)
             .field("client", &"<client>")
             .finish()
     }
@@ -50,9 +59,19 @@ impl<T: Config, C> TxProgress<T, C> {
             sub: Some(sub),
             client,
             ext_hash,
+            era: None,
+            birth_block: None,
         }
     }
 
+    /// Provide the mortality of the transaction, and the block number that its mortality period
+    /// began at if known. This is used by [`TxProgress::wait_until_included_or_expired()`].
+    pub fn with_mortality(mut self, era: Option<Era>, birth_block: Option<u64>) -> Self {
+        self.era = era;
+        self.birth_block = birth_block;
+        self
+    }
+
     /// Return the hash of the extrinsic.
     pub fn extrinsic_hash(&self) -> HashFor<T> {
         self.ext_hash
@@ -71,6 +90,19 @@ where
         StreamExt::next(self).await
     }
 
+    /// Like [`TxProgress::next()`], but returns an [`RpcError::RequestTimeout`] error if the next
+    /// transaction status doesn't arrive within `timeout`. No status is lost if this times out;
+    /// it can be called again to carry on waiting.
+    pub async fn next_with_timeout(
+        &mut self,
+        timeout: Duration,
+    ) -> Option<Result<TxStatus<T, C>, Error>> {
+        match with_timeout(timeout, async { Ok(self.next().await) }).await {
+            Ok(status) => status,
+            Err(e) => Some(Err(e)),
+        }
+    }
+
     /// Wait for the transaction to be finalized, and return a [`TxInBlock`]
     /// instance when it is, or an error if there was a problem waiting for finalization.
     ///
@@ -118,6 +150,187 @@ where
         let evs = self.wait_for_finalized().await?.wait_for_success().await?;
         Ok(evs)
     }
+
+    /// Wait until the transaction is included in a finalized block, or until its mortality
+    /// period has ended, in which case [`TxOutcome::Expired`] is returned and it is safe to
+    /// assume that the transaction will never be included.
+    ///
+    /// Unlike [`TxProgress::wait_for_finalized()`], the finalized blocks themselves are checked
+    /// for the transaction, and so statuses like `Invalid` or `Dropped` do not end the wait for
+    /// mortal transactions; they may still make it into a block via some other node. Immortal
+    /// transactions never expire, and so for those this behaves like
+    /// [`TxProgress::wait_for_finalized()`].
+    ///
+    /// If the block that the mortality period began at is not known (see
+    /// [`TxProgress::with_mortality()`]), then it is assumed that the transaction was not
+    /// in any block finalized before this is called, and that it may have been constructed
+    /// at any block up to the current best block, so it is only reported as expired once a
+    /// full mortality period has been finalized beyond that.
+    ///
+    /// **Note:** consumes `self`.
+    pub async fn wait_until_included_or_expired(self) -> Result<TxOutcome<T, C>, Error> {
+        if !matches!(self.era, Some(Era::Mortal { .. })) {
+            return Ok(TxOutcome::Included(self.wait_for_finalized().await?));
+        }
+
+        let client = self.client.clone();
+        let checked_up_to = match self.birth_block {
+            Some(birth_block) => birth_block.saturating_sub(1),
+            None => latest_finalized_block_number(&client).await?,
+        };
+        let death = transaction_death(&client, self.era, self.birth_block).await?;
+        let mut scanner = FinalizedBlockScanner::new(vec![self.ext_hash], death, checked_up_to);
+
+        let blocks = client.blocks().subscribe_finalized().await?;
+        let mut updates =
+            futures::stream::select(self.map(Either::Left), blocks.map(Either::Right));
+
+        while let Some(update) = updates.next().await {
+            match update {
+                Either::Left(Ok(TxStatus::InFinalizedBlock(tx_in_block))) => {
+                    return Ok(TxOutcome::Included(tx_in_block));
+                }
+                // Other statuses (or the status subscription failing) aren't conclusive;
+                // the finalized blocks tell us whether the transaction made it in or not.
+                Either::Left(_) => continue,
+                Either::Right(block) => {
+                    if let Some(outcome) = scanner.check(&client, block?).await? {
+                        return Ok(outcome);
+                    }
+                }
+            }
+        }
+        Err(RpcError::SubscriptionDropped.into())
+    }
+}
+
+/// The outcome of [`TxProgress::wait_until_included_or_expired()`].
+#[derive_where(Debug; C)]
+pub enum TxOutcome<T: Config, C> {
+    /// The transaction has been included in a finalized block.
+    Included(TxInBlock<T, C>),
+    /// The mortality period of the transaction ended without it being included in a
+    /// finalized block, and so it can never be included.
+    Expired,
+}
+
+/// The outcome of [`crate::tx::SubmittableTransaction::bump_tip()`].
+#[derive_where(Debug; C)]
+pub enum TipBumpOutcome<T: Config, C> {
+    /// The original transaction was included in a finalized block.
+    Original(TxInBlock<T, C>),
+    /// The replacement transaction, with the higher tip, was included in a finalized block.
+    Replacement(TxInBlock<T, C>),
+    /// The mortality period of the transactions ended without either being included in a
+    /// finalized block.
+    Expired,
+}
+
+/// Checks each new finalized block, and any blocks between it and the last one checked,
+/// for some transactions, until one of them is found or the transactions have expired.
+pub(crate) struct FinalizedBlockScanner<T: Config> {
+    ext_hashes: Vec<HashFor<T>>,
+    // The first block number that the transaction can no longer be included in, if mortal.
+    death: Option<u64>,
+    // The last finalized block number that we know the transaction isn't in.
+    checked_up_to: u64,
+}
+
+impl<T: Config> FinalizedBlockScanner<T> {
+    pub(crate) fn new(ext_hashes: Vec<HashFor<T>>, death: Option<u64>, checked_up_to: u64) -> Self {
+        Self {
+            ext_hashes,
+            death,
+            checked_up_to,
+        }
+    }
+
+    pub(crate) async fn check<C: OnlineClientT<T>>(
+        &mut self,
+        client: &C,
+        block: Block<T, C>,
+    ) -> Result<Option<TxOutcome<T, C>>, Error> {
+        let block_number: u64 = block.number().into();
+        if block_number <= self.checked_up_to {
+            return Ok(None);
+        }
+
+        // Find any blocks that we missed, for instance while we were disconnected. Stop
+        // if the backend doesn't know about them any more.
+        let mut to_check = vec![block.reference()];
+        let mut next_parent_hash = block.header().parent_hash();
+        for _ in self.checked_up_to + 1..block_number {
+            let Some(hash) = next_parent_hash else {
+                break;
+            };
+            match client.backend().block_header(hash).await {
+                Ok(Some(header)) => {
+                    to_check.push(BlockRef::from_hash(hash));
+                    next_parent_hash = header.parent_hash();
+                }
+                _ => break,
+            }
+        }
+
+        for block_ref in to_check.into_iter().rev() {
+            let Some(body) = client.backend().block_body(block_ref.hash()).await? else {
+                continue;
+            };
+            let found = self.ext_hashes.iter().find(|&&ext_hash| {
+                find_extrinsic_index::<T>(&body, client.hasher(), ext_hash).is_some()
+            });
+            if let Some(&ext_hash) = found {
+                let tx_in_block = TxInBlock::new(block_ref, ext_hash, client.clone());
+                return Ok(Some(TxOutcome::Included(tx_in_block)));
+            }
+        }
+
+        self.checked_up_to = block_number;
+        if self.death.is_some_and(|death| block_number + 1 >= death) {
+            return Ok(Some(TxOutcome::Expired));
+        }
+        Ok(None)
+    }
+}
+
+/// The first block number that a transaction with the given mortality can no longer be
+/// included in, or `None` if it's immortal. If the block that its mortality period began at
+/// isn't known, then the transaction may have been constructed at any block up to the current
+/// best block, and so the latest block number that it could possibly live until is returned.
+pub(crate) async fn transaction_death<T: Config, C: OnlineClientT<T>>(
+    client: &C,
+    era: Option<Era>,
+    birth_block: Option<u64>,
+) -> Result<Option<u64>, Error> {
+    let Some(era @ Era::Mortal { period, .. }) = era else {
+        return Ok(None);
+    };
+    if let Some(birth_block) = birth_block {
+        return Ok(Some(era.death(birth_block)));
+    }
+
+    let mut best_blocks = client
+        .backend()
+        .stream_best_block_headers(client.hasher())
+        .await?;
+    let (header, _) = best_blocks
+        .next()
+        .await
+        .ok_or(RpcError::SubscriptionDropped)??;
+    let best_block_number: u64 = header.number().into();
+    Ok(Some(best_block_number + period))
+}
+
+pub(crate) async fn latest_finalized_block_number<T: Config, C: OnlineClientT<T>>(
+    client: &C,
+) -> Result<u64, Error> {
+    let block_ref = client.backend().latest_finalized_block_ref().await?;
+    let header = client
+        .backend()
+        .block_header(block_ref.hash())
+        .await?
+        .ok_or_else(|| Error::Block(BlockError::not_found(block_ref.hash())))?;
+    Ok(header.number().into())
 }
 
 impl<T: Config, C: Clone> Stream for TxProgress<T, C> {
@@ -290,16 +503,7 @@ impl<T: Config, C: OnlineClientT<T>> TxInBlock<T, C> {
             .await?
             .ok_or(Error::Transaction(TransactionError::BlockNotFound))?;
 
-        let extrinsic_idx = block_body
-            .iter()
-            .position(|ext| {
-                use crate::config::Hasher;
-                let Ok((_, stripped)) = strip_compact_prefix(ext) else {
-                    return false;
-                };
-                let hash = hasher.hash_of(&stripped);
-                hash == self.ext_hash
-            })
+        let extrinsic_idx = find_extrinsic_index::<T>(&block_body, hasher, self.ext_hash)
             // If we successfully obtain the block hash we think contains our
             // extrinsic, the extrinsic should be in there somewhere..
             .ok_or(Error::Transaction(TransactionError::BlockNotFound))?;
@@ -316,16 +520,41 @@ impl<T: Config, C: OnlineClientT<T>> TxInBlock<T, C> {
     }
 }
 
+/// Find the index of the extrinsic with the given hash in some block body.
+pub(crate) fn find_extrinsic_index<T: Config>(
+    block_body: &[Vec<u8>],
+    hasher: T::Hasher,
+    ext_hash: HashFor<T>,
+) -> Option<usize> {
+    use crate::config::Hasher;
+    block_body.iter().position(|ext| {
+        let Ok((_, stripped)) = strip_compact_prefix(ext) else {
+            return false;
+        };
+        hasher.hash_of(&stripped) == ext_hash
+    })
+}
+
 #[cfg(test)]
 mod test {
+    use std::sync::Arc;
+
+    use codec::Encode;
+    use futures::StreamExt;
+    use serde_json::value::RawValue;
     use subxt_core::client::RuntimeVersion;
+    use subxt_rpcs::client::{MockRpcClient, mock_rpc_client::Json};
 
+    use super::FinalizedBlockScanner;
     use crate::{
-        Error, SubstrateConfig,
-        backend::{StreamOfResults, TransactionStatus},
+        Error, OnlineClient, SubstrateConfig,
+        backend::{BlockRef, StreamOfResults, TransactionStatus},
+        blocks::Block,
         client::{OfflineClientT, OnlineClientT},
-        config::{Config, HashFor},
-        tx::TxProgress,
+        config::{Config, HashFor, Hasher, Header, substrate::SubstrateHeader},
+        test_utils::metadata,
+        tx::{ResilientTxProgress, ResilientTxStatus, TxOutcome, TxProgress, TxStatus},
+        utils::{Era, to_hex},
     };
 
     type MockTxProgress = TxProgress<SubstrateConfig, MockClient>;
@@ -409,6 +638,313 @@ mod test {
         ));
     }
 
+    #[tokio::test]
+    async fn wait_until_included_or_expired_when_immortal() {
+        let tx_progress = mock_tx_progress(vec![
+            MockSubstrateTxStatus::Broadcasted,
+            MockSubstrateTxStatus::InFinalizedBlock {
+                hash: BlockRef::from_hash(MockHash::repeat_byte(1)),
+            },
+        ])
+        .with_mortality(Some(Era::Immortal), None);
+
+        assert!(matches!(
+            tx_progress.wait_until_included_or_expired().await,
+            Ok(TxOutcome::Included(b)) if b.block_hash() == MockHash::repeat_byte(1)
+        ));
+    }
+
+    #[tokio::test]
+    async fn resilient_progress_returns_finalized() {
+        let tx_progress = mock_tx_progress(vec![
+            MockSubstrateTxStatus::Broadcasted,
+            MockSubstrateTxStatus::InBestBlock {
+                hash: BlockRef::from_hash(MockHash::repeat_byte(1)),
+            },
+            MockSubstrateTxStatus::InFinalizedBlock {
+                hash: BlockRef::from_hash(MockHash::repeat_byte(1)),
+            },
+        ]);
+        let mut progress = ResilientTxProgress::new(tx_progress, MockClient, vec![], None, 0);
+
+        assert!(matches!(
+            progress.next().await,
+            Some(Ok(ResilientTxStatus::Included(_)))
+        ));
+        assert!(matches!(
+            progress.next().await,
+            Some(Ok(ResilientTxStatus::Finalized(b))) if b.block_hash() == MockHash::repeat_byte(1)
+        ));
+        assert!(progress.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn resilient_progress_returns_err_when_invalid() {
+        let tx_progress = mock_tx_progress(vec![MockSubstrateTxStatus::Invalid {
+            message: "err".into(),
+        }]);
+        let progress = ResilientTxProgress::new(tx_progress, MockClient, vec![], None, 0);
+
+        assert!(matches!(
+            progress.wait_for_finalized_or_expired().await,
+            Err(Error::Transaction(crate::error::TransactionError::Invalid(e))) if e == "err"
+        ));
+    }
+
+    #[tokio::test]
+    async fn next_with_timeout_gives_up_waiting_for_status() {
+        let statuses = futures::stream::iter([Ok(MockSubstrateTxStatus::Validated)])
+            .chain(futures::stream::pending());
+        let sub = StreamOfResults::new(Box::pin(statuses));
+        let mut tx_progress: MockTxProgress = TxProgress::new(sub, MockClient, Default::default());
+        let timeout = std::time::Duration::from_millis(50);
+
+        assert!(matches!(
+            tx_progress.next_with_timeout(timeout).await,
+            Some(Ok(TxStatus::Validated))
+        ));
+        assert!(
+            tx_progress
+                .next_with_timeout(timeout)
+                .await
+                .unwrap()
+                .is_err_and(|e| e.is_request_timeout())
+        );
+    }
+
+    #[tokio::test]
+    async fn finalized_block_scanner_finds_transactions_in_missed_blocks() {
+        let ext = mock_extrinsic(1);
+        let chain = MockChain::new(20, &[(12, ext.clone())]);
+        let client = chain.client(10, 10, 10);
+        let mut scanner = FinalizedBlockScanner::new(vec![chain.ext_hash(&ext)], None, 10);
+
+        // Blocks 11 to 13 were never seen, but are checked by walking back from block 14.
+        assert!(matches!(
+            scanner.check(&client, chain.block(&client, 14)).await,
+            Ok(Some(TxOutcome::Included(b))) if b.block_hash() == chain.hashes[12]
+        ));
+    }
+
+    #[tokio::test]
+    async fn finalized_block_scanner_skips_checked_blocks() {
+        let ext = mock_extrinsic(1);
+        let chain = MockChain::new(20, &[(10, ext.clone()), (12, ext.clone())]);
+        let client = chain.client(10, 10, 10);
+        let mut scanner = FinalizedBlockScanner::new(vec![chain.ext_hash(&ext)], None, 12);
+
+        assert!(matches!(
+            scanner.check(&client, chain.block(&client, 10)).await,
+            Ok(None)
+        ));
+        assert!(matches!(
+            scanner.check(&client, chain.block(&client, 13)).await,
+            Ok(None)
+        ));
+        assert_eq!(scanner.checked_up_to, 13);
+    }
+
+    #[tokio::test]
+    async fn finalized_block_scanner_reports_expiry() {
+        let ext = mock_extrinsic(1);
+        let chain = MockChain::new(20, &[]);
+        let client = chain.client(10, 10, 10);
+        let mut scanner = FinalizedBlockScanner::new(vec![chain.ext_hash(&ext)], Some(15), 10);
+
+        assert!(matches!(
+            scanner.check(&client, chain.block(&client, 13)).await,
+            Ok(None)
+        ));
+        assert!(matches!(
+            scanner.check(&client, chain.block(&client, 14)).await,
+            Ok(Some(TxOutcome::Expired))
+        ));
+    }
+
+    #[tokio::test]
+    async fn wait_until_included_or_expired_when_birth_unknown() {
+        // The transaction was constructed at the best block, 14, while the latest finalized
+        // block was 10, and so it can be included in blocks up to 21.
+        let era = Era::mortal(8, 14);
+        let ext = mock_extrinsic(1);
+        let chain = MockChain::new(30, &[(16, ext.clone())]);
+        let client = chain.client(10, 14, 20);
+        let tx_progress = TxProgress::new(
+            create_substrate_tx_status_subscription(vec![
+                MockSubstrateTxStatus::Broadcasted,
+                MockSubstrateTxStatus::Dropped {
+                    message: "err".into(),
+                },
+            ]),
+            client,
+            chain.ext_hash(&ext),
+        )
+        .with_mortality(Some(era), None);
+
+        assert!(matches!(
+            tx_progress.wait_until_included_or_expired().await,
+            Ok(TxOutcome::Included(b)) if b.block_hash() == chain.hashes[16]
+        ));
+    }
+
+    #[tokio::test]
+    async fn wait_until_included_or_expired_when_expired() {
+        let era = Era::mortal(8, 14);
+        let ext = mock_extrinsic(1);
+        let chain = MockChain::new(30, &[(22, ext.clone())]);
+        let client = chain.client(10, 14, 25);
+        let tx_progress = TxProgress::new(
+            create_substrate_tx_status_subscription(vec![MockSubstrateTxStatus::Broadcasted]),
+            client,
+            chain.ext_hash(&ext),
+        )
+        .with_mortality(Some(era), Some(14));
+
+        assert!(matches!(
+            tx_progress.wait_until_included_or_expired().await,
+            Ok(TxOutcome::Expired)
+        ));
+    }
+
+    type MockHeader = <SubstrateConfig as Config>::Header;
+
+    /// A chain of blocks numbered from 0, each of which is the parent of the next.
+    #[derive(Clone)]
+    struct MockChain {
+        hasher: <SubstrateConfig as Config>::Hasher,
+        headers: Vec<MockHeader>,
+        hashes: Vec<MockHash>,
+        bodies: Vec<Vec<Vec<u8>>>,
+    }
+
+    impl MockChain {
+        /// Build a chain of `len` blocks, which contain the given `(block number, extrinsic)` pairs.
+        fn new(len: u32, extrinsics: &[(u32, Vec<u8>)]) -> Self {
+            let mut chain = MockChain {
+                hasher: Hasher::new(&metadata()),
+                headers: vec![],
+                hashes: vec![],
+                bodies: vec![],
+            };
+            for number in 0..len {
+                let header = SubstrateHeader {
+                    parent_hash: chain.hashes.last().copied().unwrap_or_default(),
+                    number,
+                    state_root: Default::default(),
+                    extrinsics_root: Default::default(),
+                    digest: Default::default(),
+                };
+                let body = extrinsics
+                    .iter()
+                    .filter(|(n, _)| *n == number)
+                    .map(|(_, ext)| ext.clone())
+                    .collect();
+                chain.hashes.push(header.hash_with(chain.hasher));
+                chain.headers.push(header);
+                chain.bodies.push(body);
+            }
+            chain
+        }
+
+        fn ext_hash(&self, ext: &[u8]) -> MockHash {
+            self.hasher.hash(ext)
+        }
+
+        fn block(
+            &self,
+            client: &OnlineClient<SubstrateConfig>,
+            number: usize,
+        ) -> Block<SubstrateConfig, OnlineClient<SubstrateConfig>> {
+            let block_ref = BlockRef::from_hash(self.hashes[number]);
+            Block::new(self.headers[number].clone(), block_ref, client.clone())
+        }
+
+        fn index_of(&self, params: Option<Box<RawValue>>) -> Option<usize> {
+            let (hash,): (MockHash,) = parse_params(params);
+            self.hashes.iter().position(|h| *h == hash)
+        }
+
+        /// A client connected to this chain, where block `finalized` is the latest finalized
+        /// block and `best` is the best block. Subscribing to finalized blocks then returns
+        /// the blocks after `finalized` up to `newly_finalized`.
+        fn client(
+            &self,
+            finalized: usize,
+            best: usize,
+            newly_finalized: usize,
+        ) -> OnlineClient<SubstrateConfig> {
+            let chain = Arc::new(self.clone());
+            let finalized_hash = self.hashes[finalized];
+            let best_header = self.headers[best].clone();
+            let newly_finalized_header = self.headers[newly_finalized].clone();
+
+            let rpc_client = MockRpcClient::builder()
+                .method_handler("chain_getFinalizedHead", move |_params| async move {
+                    Json(finalized_hash)
+                })
+                .method_handler("chain_getBlockHash", {
+                    let chain = chain.clone();
+                    move |params| {
+                        let (number,): (usize,) = parse_params(params);
+                        let hash = chain.hashes.get(number).copied();
+                        async move { Json(hash) }
+                    }
+                })
+                .method_handler("chain_getHeader", {
+                    let chain = chain.clone();
+                    move |params| {
+                        let header = chain.index_of(params).map(|n| chain.headers[n].clone());
+                        async move { Json(header) }
+                    }
+                })
+                .method_handler("chain_getBlock", {
+                    let chain = chain.clone();
+                    move |params| {
+                        let block = chain.index_of(params).map(|n| {
+                            serde_json::json!({
+                                "block": {
+                                    "header": chain.headers[n],
+                                    "extrinsics": chain.bodies[n].iter().map(to_hex).collect::<Vec<_>>(),
+                                },
+                                "justifications": null,
+                            })
+                        });
+                        async move { Json(block) }
+                    }
+                })
+                .subscription_handler("chain_subscribeNewHeads", move |_params, _unsub| {
+                    let header = best_header.clone();
+                    async move { vec![Json(header)] }
+                })
+                .subscription_handler("chain_subscribeFinalizedHeads", move |_params, _unsub| {
+                    let header = newly_finalized_header.clone();
+                    async move { vec![Json(header)] }
+                })
+                .build();
+
+            let runtime_version = RuntimeVersion {
+                spec_version: 0,
+                transaction_version: 0,
+            };
+            OnlineClient::from_rpc_client_with(
+                Default::default(),
+                runtime_version,
+                metadata(),
+                rpc_client,
+            )
+            .unwrap()
+        }
+    }
+
+    fn mock_extrinsic(n: u8) -> Vec<u8> {
+        vec![n; 8].encode()
+    }
+
+    fn parse_params<P: serde::de::DeserializeOwned>(params: Option<Box<RawValue>>) -> P {
+        serde_json::from_str(params.expect("params should be given").get())
+            .expect("params should be valid")
+    }
+
     fn mock_tx_progress(statuses: Vec<MockSubstrateTxStatus>) -> MockTxProgress {
         let sub = create_substrate_tx_status_subscription(statuses);
         TxProgress::new(sub, MockClient, Default::default())
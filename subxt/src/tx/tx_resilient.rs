@@ -0,0 +1,239 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Watch a transaction in a way that survives the connection to the node dropping.
+
+use super::tx_progress::{FinalizedBlockScanner, TxInBlock, TxOutcome, TxProgress, TxStatus};
+use crate::{
+    backend::StreamOfResults,
+    blocks::Block,
+    client::OnlineClientT,
+    config::{Config, HashFor},
+    error::{Error, TransactionError},
+};
+use derive_where::derive_where;
+
+/// This struct tracks the progress of some transaction that was submitted using
+/// [`crate::tx::SubmittableTransaction::submit_and_watch_resilient()`].
+///
+/// Unlike [`TxProgress`], the connection to the node dropping does not end the progress.
+/// Instead, the transaction is submitted again once the client has reconnected. If that
+/// isn't possible, or the node no longer recognises the transaction (for instance because
+/// it was included while we were disconnected), then the extrinsics in each new finalized
+/// block are checked for the transaction until it is found or its mortality period ends.
+pub struct ResilientTxProgress<T: Config, C> {
+    client: C,
+    tx_bytes: Vec<u8>,
+    ext_hash: HashFor<T>,
+    scanner: FinalizedBlockScanner<T>,
+    state: State<T, C>,
+}
+
+enum State<T: Config, C> {
+    Watching {
+        progress: TxProgress<T, C>,
+        resubmitted: bool,
+    },
+    Scanning {
+        blocks: Option<StreamOfResults<Block<T, C>>>,
+    },
+    Done,
+}
+
+impl<T: Config, C> std::fmt::Debug for ResilientTxProgress<T, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = match &self.state {
+            State::Watching { .. } => "watching",
+            State::Scanning { .. } => "scanning",
+            State::Done => "done",
+        };
+        f.debug_struct("ResilientTxProgress")
+            .field("ext_hash", &self.ext_hash)
+            .field("state", &state)
+            .field("client", &"<client>")
+            .finish()
+    }
+}
+
+/// Possible statuses returned from [`ResilientTxProgress::next()`].
+#[derive_where(Debug; C)]
+pub enum ResilientTxStatus<T: Config, C> {
+    /// The transaction has been included in a best block. It may yet be retracted.
+    Included(TxInBlock<T, C>),
+    /// The transaction has been included in a finalized block. Nothing further will be emitted.
+    Finalized(TxInBlock<T, C>),
+    /// The mortality period of the transaction has ended without it being included in a
+    /// finalized block, and so it can never be included. Nothing further will be emitted.
+    Expired,
+}
+
+impl<T: Config, C> ResilientTxStatus<T, C> {
+    /// Is this a final status, after which no further statuses will be emitted?
+    pub fn is_final(&self) -> bool {
+        !matches!(self, Self::Included(_))
+    }
+}
+
+impl<T, C> ResilientTxProgress<T, C>
+where
+    T: Config,
+    C: OnlineClientT<T>,
+{
+    pub(crate) fn new(
+        progress: TxProgress<T, C>,
+        client: C,
+        tx_bytes: Vec<u8>,
+        death: Option<u64>,
+        checked_up_to: u64,
+    ) -> Self {
+        let ext_hash = progress.extrinsic_hash();
+        Self {
+            ext_hash,
+            client,
+            tx_bytes,
+            scanner: FinalizedBlockScanner::new(vec![ext_hash], death, checked_up_to),
+            state: State::Watching {
+                progress,
+                resubmitted: false,
+            },
+        }
+    }
+
+    /// Return the hash of the extrinsic.
+    pub fn extrinsic_hash(&self) -> HashFor<T> {
+        self.ext_hash
+    }
+
+    /// Return the next status of the transaction, or `None` once a final status has
+    /// been returned.
+    pub async fn next(&mut self) -> Option<Result<ResilientTxStatus<T, C>, Error>> {
+        let res = self.next_inner().await;
+        if matches!(
+            res,
+            Some(Err(_))
+                | Some(Ok(
+                    ResilientTxStatus::Finalized(_) | ResilientTxStatus::Expired
+                ))
+        ) {
+            self.state = State::Done;
+        }
+        res
+    }
+
+    /// Wait until the transaction is either finalized or has expired, returning
+    /// [`ResilientTxStatus::Finalized`] or [`ResilientTxStatus::Expired`] respectively.
+    pub async fn wait_for_finalized_or_expired(mut self) -> Result<ResilientTxStatus<T, C>, Error> {
+        while let Some(status) = self.next().await {
+            let status = status?;
+            if status.is_final() {
+                return Ok(status);
+            }
+        }
+        Err(crate::error::RpcError::SubscriptionDropped.into())
+    }
+
+    async fn next_inner(&mut self) -> Option<Result<ResilientTxStatus<T, C>, Error>> {
+        loop {
+            match &mut self.state {
+                State::Done => return None,
+                State::Watching {
+                    progress,
+                    resubmitted,
+                } => {
+                    let resubmitted = *resubmitted;
+                    match progress.next().await {
+                        Some(Ok(TxStatus::InBestBlock(b))) => {
+                            return Some(Ok(ResilientTxStatus::Included(b)));
+                        }
+                        Some(Ok(TxStatus::InFinalizedBlock(b))) => {
+                            return Some(Ok(ResilientTxStatus::Finalized(b)));
+                        }
+                        Some(Ok(
+                            TxStatus::Validated
+                            | TxStatus::Broadcasted
+                            | TxStatus::NoLongerInBestBlock,
+                        )) => continue,
+                        // The first time that we submit the transaction, errors from the node are
+                        // conclusive. After resubmitting, the node may complain because the transaction
+                        // is already in the pool or has already been included, so we check the blocks.
+                        Some(Ok(TxStatus::Invalid { message })) if !resubmitted => {
+                            return Some(Err(TransactionError::Invalid(message).into()));
+                        }
+                        Some(Ok(TxStatus::Error { message })) if !resubmitted => {
+                            return Some(Err(TransactionError::Error(message).into()));
+                        }
+                        Some(Ok(
+                            TxStatus::Invalid { .. }
+                            | TxStatus::Error { .. }
+                            | TxStatus::Dropped { .. },
+                        )) => self.start_scanning(),
+                        Some(Err(e)) if !is_connection_error(&e) => return Some(Err(e)),
+                        // The connection dropped or the subscription ended early; submit again
+                        // if we haven't already, else just look for the transaction in blocks.
+                        Some(Err(_)) | None if !resubmitted => self.resubmit().await,
+                        Some(Err(_)) | None => self.start_scanning(),
+                    }
+                }
+                State::Scanning { blocks } => {
+                    let Some(blocks) = blocks else {
+                        match self.client.blocks().subscribe_finalized().await {
+                            Ok(sub) => *blocks = Some(sub),
+                            Err(e) => return Some(Err(e)),
+                        }
+                        continue;
+                    };
+
+                    // The block subscription resubscribes itself after a reconnect.
+                    let block = match blocks.next().await {
+                        Some(Ok(block)) => block,
+                        Some(Err(e)) if e.is_disconnected_will_reconnect() => continue,
+                        Some(Err(e)) => return Some(Err(e)),
+                        // Subscribe again if the stream ends.
+                        None => {
+                            self.state = State::Scanning { blocks: None };
+                            continue;
+                        }
+                    };
+
+                    match self.scanner.check(&self.client, block).await {
+                        Ok(Some(TxOutcome::Included(b))) => {
+                            return Some(Ok(ResilientTxStatus::Finalized(b)));
+                        }
+                        Ok(Some(TxOutcome::Expired)) => {
+                            return Some(Ok(ResilientTxStatus::Expired));
+                        }
+                        Ok(None) => continue,
+                        Err(e) => return Some(Err(e)),
+                    }
+                }
+            }
+        }
+    }
+
+    fn start_scanning(&mut self) {
+        self.state = State::Scanning { blocks: None };
+    }
+
+    async fn resubmit(&mut self) {
+        match self
+            .client
+            .backend()
+            .submit_transaction(&self.tx_bytes)
+            .await
+        {
+            Ok(sub) => {
+                self.state = State::Watching {
+                    progress: TxProgress::new(sub, self.client.clone(), self.ext_hash),
+                    resubmitted: true,
+                }
+            }
+            Err(_) => self.start_scanning(),
+        }
+    }
+}
+
+fn is_connection_error(e: &Error) -> bool {
+    e.is_disconnected_will_reconnect()
+        || matches!(e, Error::Rpc(crate::error::RpcError::SubscriptionDropped))
+}
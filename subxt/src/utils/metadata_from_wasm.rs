@@ -0,0 +1,65 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use crate::error::Error;
+use crate::metadata::Metadata;
+use codec::Decode;
+use subxt_utils_fetchmetadata::MetadataVersion;
+
+/// Obtain the [`Metadata`] from a (possibly compressed) Wasm runtime blob by executing it
+/// locally. This allows the metadata of some proposed runtime to be inspected, for instance
+/// to compare it against the metadata of the live chain before a runtime upgrade is enacted.
+///
+/// The latest stable metadata version exposed by the runtime is used.
+///
+/// # Example
+///
+/// ```rust,no_run,standalone_crate
+/// use subxt::{OnlineClient, PolkadotConfig};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let wasm = std::fs::read("runtime.compact.compressed.wasm")?;
+/// let new_metadata = subxt::utils::metadata_from_wasm(&wasm)?;
+///
+/// let api = OnlineClient::<PolkadotConfig>::new().await?;
+/// let current_metadata = api.metadata();
+///
+/// for pallet in new_metadata.pallets() {
+///     if current_metadata.pallet_by_name(pallet.name()).is_none() {
+///         println!("New pallet: {}", pallet.name());
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn metadata_from_wasm(wasm: &[u8]) -> Result<Metadata, Error> {
+    let bytes = subxt_utils_fetchmetadata::from_wasm(wasm, MetadataVersion::Latest)
+        .map_err(|e| Error::Other(e.to_string()))?;
+    let metadata = Metadata::decode(&mut &bytes[..])?;
+    Ok(metadata)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decodes_metadata_from_wasm() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../artifacts/westend_runtime.compact.compressed.wasm"
+        );
+        let wasm = std::fs::read(path).unwrap();
+
+        let metadata = metadata_from_wasm(&wasm).unwrap();
+        assert!(metadata.pallet_by_name("System").is_some());
+        assert!(metadata.pallet_by_name("Balances").is_some());
+    }
+
+    #[test]
+    fn rejects_invalid_wasm() {
+        assert!(metadata_from_wasm(b"not a wasm runtime").is_err());
+    }
+}
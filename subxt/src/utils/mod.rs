@@ -18,3 +18,8 @@ cfg_jsonrpsee! {
     mod fetch_chain_spec;
     pub use fetch_chain_spec::{fetch_chainspec_from_rpc_node, FetchChainspecError};
 }
+
+crate::macros::cfg_runtime_wasm_path! {
+    mod metadata_from_wasm;
+    pub use metadata_from_wasm::metadata_from_wasm;
+}
@@ -442,3 +442,23 @@ async fn decode_block_mortality() {
         ));
     }
 }
+
+#[cfg(fullclient)]
+#[subxt_test]
+async fn verify_extrinsic_signatures() {
+    let ctx = test_context().await;
+    let api = ctx.client();
+    let genesis_hash = api.genesis_hash();
+
+    for params in [
+        DefaultExtrinsicParamsBuilder::new().immortal(),
+        DefaultExtrinsicParamsBuilder::new().mortal(16),
+    ] {
+        let tx = submit_extrinsic_and_get_it_back(&api, params).await;
+        assert!(tx.verify_signature(genesis_hash).await.unwrap());
+
+        // The wrong genesis hash leads to a different signer payload:
+        let wrong_genesis_hash = Default::default();
+        assert!(!tx.verify_signature(wrong_genesis_hash).await.unwrap());
+    }
+}
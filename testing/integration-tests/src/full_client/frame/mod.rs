@@ -5,9 +5,12 @@
 //! Test interactions with some built-in FRAME pallets.
 
 mod balances;
+mod multisig;
+mod proxy;
 mod staking;
 mod system;
 mod timestamp;
+mod utility;
 
 #[cfg(fullclient)]
 mod contracts;
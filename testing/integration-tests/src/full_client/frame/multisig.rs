@@ -0,0 +1,62 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use crate::{node_runtime, subxt_test, test_context};
+use subxt::tx::Multisig;
+use subxt::{PolkadotConfig, utils::AccountId32};
+use subxt_signer::sr25519::dev;
+
+#[subxt_test]
+async fn multisig_approval_then_execution() -> Result<(), subxt::Error> {
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    let alice: AccountId32 = dev::alice().public_key().into();
+    let bob: AccountId32 = dev::bob().public_key().into();
+    let charlie: AccountId32 = dev::charlie().public_key().into();
+    let multisig = Multisig::<PolkadotConfig>::new(2, [alice.clone(), bob.clone(), charlie]);
+
+    // Fund the multisig account so that it can pay for the call:
+    let fund = node_runtime::tx()
+        .balances()
+        .transfer_allow_death(multisig.account_id().into(), 1_000_000_000_000);
+    api.tx()
+        .sign_and_submit_then_watch_default(&fund, &dev::alice())
+        .await?
+        .wait_for_finalized_success()
+        .await?;
+
+    let remark = || {
+        node_runtime::tx()
+            .system()
+            .remark_with_event(b"multisig".to_vec())
+    };
+
+    // Alice approves first; nothing is dispatched yet:
+    let approval = multisig.approve(&api, &alice, remark()).await?;
+    assert!(!approval.is_final());
+    assert!(approval.timepoint().is_none());
+    api.tx()
+        .sign_and_submit_then_watch_default(&approval.into_payload(), &dev::alice())
+        .await?
+        .wait_for_finalized_success()
+        .await?;
+
+    // Alice can't approve twice:
+    assert!(multisig.approve(&api, &alice, remark()).await.is_err());
+
+    // Bob's approval refers to Alice's, and dispatches the call:
+    let approval = multisig.approve(&api, &bob, remark()).await?;
+    assert!(approval.is_final());
+    assert!(approval.timepoint().is_some());
+    let events = api
+        .tx()
+        .sign_and_submit_then_watch_default(&approval.into_payload(), &dev::bob())
+        .await?
+        .wait_for_finalized_success()
+        .await?;
+
+    assert!(events.has::<node_runtime::system::events::Remarked>()?);
+    Ok(())
+}
@@ -0,0 +1,44 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use crate::{node_runtime, subxt_test, test_context};
+use subxt::{PolkadotConfig, utils::AccountId32};
+use subxt_signer::sr25519::dev;
+
+#[subxt_test]
+async fn proxy_dispatches_on_behalf_of_real_account() -> Result<(), subxt::Error> {
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    let bob: AccountId32 = dev::bob().public_key().into();
+    let add_proxy = node_runtime::tx().proxy().add_proxy(
+        dev::dave().public_key().into(),
+        node_runtime::runtime_types::kitchensink_runtime::ProxyType::Any,
+        0,
+    );
+    api.tx()
+        .sign_and_submit_then_watch_default(&add_proxy, &dev::bob())
+        .await?
+        .wait_for_finalized_success()
+        .await?;
+
+    let remark = node_runtime::tx()
+        .system()
+        .remark_with_event(b"proxied".to_vec());
+    let events = api
+        .tx()
+        .sign_and_submit_then_watch_default(
+            &subxt::tx::proxy::<PolkadotConfig>(bob.clone(), remark),
+            &dev::dave(),
+        )
+        .await?
+        .wait_for_finalized_success()
+        .await?;
+
+    let remarked = events
+        .find_first::<node_runtime::system::events::Remarked>()?
+        .expect("remark event");
+    assert_eq!(remarked.sender, bob);
+    Ok(())
+}
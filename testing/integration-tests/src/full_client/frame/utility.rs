@@ -0,0 +1,67 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use crate::{node_runtime, subxt_test, test_context};
+use assert_matches::assert_matches;
+use subxt::error::DispatchError;
+use subxt::tx::{Batch, BatchItemResult, BatchMode};
+use subxt_signer::sr25519::dev;
+
+fn batch_with_failing_call(mode: BatchMode) -> Batch {
+    Batch::new(mode)
+        .call(node_runtime::tx().system().remark(b"first".to_vec()))
+        // Needs root, so this will fail with BadOrigin:
+        .call(node_runtime::tx().system().set_heap_pages(1))
+        .call(node_runtime::tx().system().remark(b"third".to_vec()))
+}
+
+#[subxt_test]
+async fn batch_is_interrupted() -> Result<(), subxt::Error> {
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    let batch = batch_with_failing_call(BatchMode::Batch);
+    let events = api
+        .tx()
+        .sign_and_submit_then_watch_default(&batch, &dev::alice())
+        .await?
+        .wait_for_finalized_success()
+        .await?;
+
+    let results = events.batch_results(batch.len())?;
+    assert_matches!(
+        &results[..],
+        [
+            BatchItemResult::Completed,
+            BatchItemResult::Failed(DispatchError::BadOrigin),
+            BatchItemResult::NotExecuted,
+        ]
+    );
+    Ok(())
+}
+
+#[subxt_test]
+async fn force_batch_continues() -> Result<(), subxt::Error> {
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    let batch = batch_with_failing_call(BatchMode::ForceBatch);
+    let events = api
+        .tx()
+        .sign_and_submit_then_watch_default(&batch, &dev::alice())
+        .await?
+        .wait_for_finalized_success()
+        .await?;
+
+    let results = events.batch_results(batch.len())?;
+    assert_matches!(
+        &results[..],
+        [
+            BatchItemResult::Completed,
+            BatchItemResult::Failed(DispatchError::BadOrigin),
+            BatchItemResult::Completed,
+        ]
+    );
+    Ok(())
+}
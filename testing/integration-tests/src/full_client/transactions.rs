@@ -147,3 +147,95 @@ async fn v5_general_encode_decode() -> Result<(), subxt::Error> {
 
     Ok(())
 }
+
+#[subxt_test]
+async fn pending_transactions_can_be_found_and_removed() -> Result<(), subxt::Error> {
+    use subxt::backend::legacy::rpc_methods::ExtrinsicOrHash;
+    use subxt::config::DefaultExtrinsicParamsBuilder as Params;
+    use subxt::tx::PendingFilter;
+
+    let ctx = test_context().await;
+    let api = ctx.client();
+    let rpc = ctx.legacy_rpc_methods().await;
+
+    let alice = dev::alice().public_key().to_account_id();
+    let nonce = api.tx().account_nonce(&alice).await?;
+
+    // A nonce in the future means that the transaction will sit in the pool:
+    let future_nonce = nonce + 10;
+    let call = node_runtime::tx().system().remark(b"stuck".to_vec());
+    let tx = api
+        .tx()
+        .create_partial_offline(&call, Params::new().nonce(future_nonce).build())?
+        .sign(&dev::alice());
+    let tx_hash = tx.submit().await?;
+
+    let pending = api
+        .tx()
+        .pending(
+            PendingFilter::new()
+                .signer(alice.clone())
+                .nonce(future_nonce),
+        )
+        .await?;
+    assert_eq!(pending.len(), 1);
+    let pending_tx = pending[0]
+        .as_ref()
+        .expect("pending transaction should decode");
+    assert_eq!(pending_tx.hash(), tx_hash);
+    assert!(
+        pending_tx
+            .as_extrinsic::<node_runtime::system::calls::types::Remark>()?
+            .is_some()
+    );
+
+    // Nothing with a different nonce:
+    let pending = api
+        .tx()
+        .pending(PendingFilter::new().signer(alice).nonce(future_nonce + 1))
+        .await?;
+    assert!(pending.is_empty());
+
+    // Now, remove it from the pool:
+    let removed = rpc
+        .author_remove_extrinsic(vec![ExtrinsicOrHash::Hash(tx_hash)])
+        .await?;
+    assert_eq!(removed, vec![tx_hash]);
+    assert!(rpc.author_pending_extrinsics().await?.is_empty());
+
+    Ok(())
+}
+
+#[subxt_test]
+async fn bump_tip_replaces_pending_transaction() -> Result<(), subxt::Error> {
+    use subxt::config::DefaultExtrinsicParamsBuilder as Params;
+    use subxt::tx::TipBumpOutcome;
+
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    let alice = dev::alice();
+    let call = node_runtime::tx().system().remark(b"bump me".to_vec());
+    let tx = api
+        .tx()
+        .create_partial(
+            &call,
+            &alice.public_key().to_account_id(),
+            Params::new().tip(1).build(),
+        )
+        .await?
+        .sign(&alice);
+    tx.submit().await?;
+
+    // The new tip must be higher:
+    assert!(tx.bump_tip(1, &alice).await.is_err());
+
+    let outcome = tx.bump_tip(1_000_000, &alice).await?;
+    let tx_in_block = match outcome {
+        TipBumpOutcome::Original(b) | TipBumpOutcome::Replacement(b) => b,
+        TipBumpOutcome::Expired => panic!("one of the transactions should be included"),
+    };
+    tx_in_block.wait_for_success().await?;
+
+    Ok(())
+}
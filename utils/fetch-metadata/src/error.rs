@@ -16,6 +16,10 @@ pub enum Error {
     #[cfg(feature = "url")]
     #[error("Request error: {0}")]
     RequestError(#[from] jsonrpsee::core::ClientError),
+    /// Error executing a Wasm runtime blob.
+    #[cfg(feature = "wasm")]
+    #[error("Wasm error: {0}")]
+    Wasm(String),
     /// Failed IO when fetching from a file.
     #[error(
         "Failed IO for {0}, make sure that you are providing the correct file path for metadata: {1}"
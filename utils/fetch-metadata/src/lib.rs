@@ -13,7 +13,12 @@ mod error;
 
 cfg_fetch_from_url! {
     mod url;
-    pub use url::{from_url, from_url_blocking, MetadataVersion, Url};
+    pub use url::{from_url, from_url_blocking, Url};
+}
+
+cfg_fetch_from_wasm! {
+    mod wasm;
+    pub use wasm::{from_wasm, from_wasm_file_blocking};
 }
 
 pub use error::Error;
@@ -28,3 +33,116 @@ pub fn from_file_blocking(path: &std::path::Path) -> Result<Vec<u8>, error::Erro
     file.read_to_end(&mut bytes).map_err(to_err)?;
     Ok(bytes)
 }
+
+/// The metadata version to fetch.
+#[derive(Default, Debug, Clone, Copy)]
+pub enum MetadataVersion {
+    /// Latest stable version of the metadata which Subxt supports.
+    #[default]
+    Latest,
+    /// Fetch a specified version of the metadata.
+    Version(u32),
+    /// Latest unstable version of the metadata.
+    Unstable,
+}
+
+#[cfg(any(feature = "url", feature = "wasm"))]
+impl MetadataVersion {
+    /// The version number used to ask for unstable metadata.
+    const UNSTABLE: u32 = u32::MAX;
+
+    /// Pick the metadata version to fetch, given the versions that a node or runtime
+    /// (named by `source` in any error) has available.
+    pub(crate) fn select(self, available: &[u32], source: &str) -> Result<u32, Error> {
+        match self {
+            MetadataVersion::Latest => available
+                .iter()
+                .copied()
+                .filter(|v| subxt_metadata::SUPPORTED_METADATA_VERSIONS.contains(v))
+                .max()
+                .ok_or_else(|| {
+                    Error::Other(format!(
+                        "The {source} does not have a metadata version that Subxt supports available"
+                    ))
+                }),
+            MetadataVersion::Unstable => {
+                if available.contains(&Self::UNSTABLE) {
+                    Ok(Self::UNSTABLE)
+                } else {
+                    Err(Error::Other(format!(
+                        "The {source} does not have an unstable metadata version available"
+                    )))
+                }
+            }
+            MetadataVersion::Version(version) => {
+                if available.contains(&version) {
+                    Ok(version)
+                } else {
+                    Err(Error::Other(format!(
+                        "The {source} does not have metadata version {version} available"
+                    )))
+                }
+            }
+        }
+    }
+}
+
+// Note: Implementation needed for the CLI tool.
+impl std::str::FromStr for MetadataVersion {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "unstable" => Ok(MetadataVersion::Unstable),
+            "latest" => Ok(MetadataVersion::Latest),
+            version => {
+                let num: u32 = version
+                    .parse()
+                    .map_err(|_| format!("Invalid metadata version specified {version:?}"))?;
+
+                Ok(MetadataVersion::Version(num))
+            }
+        }
+    }
+}
+
+#[cfg(all(test, any(feature = "url", feature = "wasm")))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn latest_ignores_unstable_and_unsupported_versions() {
+        let available = [14, 15, 16, 17, u32::MAX];
+        let version = MetadataVersion::Latest.select(&available, "node").unwrap();
+        assert_eq!(version, 16);
+    }
+
+    #[test]
+    fn latest_fails_without_supported_versions() {
+        let available = [17, u32::MAX];
+        assert!(MetadataVersion::Latest.select(&available, "node").is_err());
+    }
+
+    #[test]
+    fn unstable_and_specific_versions_must_be_available() {
+        let available = [14, 15, u32::MAX];
+        assert_eq!(
+            MetadataVersion::Unstable
+                .select(&available, "node")
+                .unwrap(),
+            u32::MAX
+        );
+        assert_eq!(
+            MetadataVersion::Version(14)
+                .select(&available, "node")
+                .unwrap(),
+            14
+        );
+        assert!(
+            MetadataVersion::Version(16)
+                .select(&available, "node")
+                .is_err()
+        );
+        assert!(MetadataVersion::Unstable.select(&[14], "node").is_err());
+    }
+}
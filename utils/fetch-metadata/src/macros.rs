@@ -18,5 +18,11 @@ macro_rules! cfg_fetch_from_url {
 	};
 }
 
+macro_rules! cfg_fetch_from_wasm {
+	($($item:item)*) => {
+		crate::macros::cfg_feature!("wasm", $($item)*);
+	};
+}
+
 #[allow(unused)]
-pub(crate) use {cfg_feature, cfg_fetch_from_url};
+pub(crate) use {cfg_feature, cfg_fetch_from_url, cfg_fetch_from_wasm};
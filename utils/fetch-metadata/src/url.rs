@@ -4,7 +4,7 @@
 
 //! Fetch metadata from a URL.
 
-use crate::Error;
+use crate::{Error, MetadataVersion};
 use codec::{Decode, Encode};
 use jsonrpsee::{
     core::client::ClientT, http_client::HttpClientBuilder, rpc_params, ws_client::WsClientBuilder,
@@ -12,37 +12,6 @@ use jsonrpsee::{
 
 pub use url::Url;
 
-/// The metadata version that is fetched from the node.
-#[derive(Default, Debug, Clone, Copy)]
-pub enum MetadataVersion {
-    /// Latest stable version of the metadata.
-    #[default]
-    Latest,
-    /// Fetch a specified version of the metadata.
-    Version(u32),
-    /// Latest unstable version of the metadata.
-    Unstable,
-}
-
-// Note: Implementation needed for the CLI tool.
-impl std::str::FromStr for MetadataVersion {
-    type Err = String;
-
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        match input {
-            "unstable" => Ok(MetadataVersion::Unstable),
-            "latest" => Ok(MetadataVersion::Latest),
-            version => {
-                let num: u32 = version
-                    .parse()
-                    .map_err(|_| format!("Invalid metadata version specified {version:?}"))?;
-
-                Ok(MetadataVersion::Version(num))
-            }
-        }
-    }
-}
-
 /// Returns the metadata bytes from the provided URL.
 pub async fn from_url(url: Url, version: MetadataVersion) -> Result<Vec<u8>, Error> {
     let bytes = match url.scheme() {
@@ -88,8 +57,6 @@ async fn fetch_metadata_http(url: Url, version: MetadataVersion) -> Result<Vec<u
 
 /// The innermost call to fetch metadata:
 async fn fetch_metadata(client: impl ClientT, version: MetadataVersion) -> Result<Vec<u8>, Error> {
-    const UNSTABLE_METADATA_VERSION: u32 = u32::MAX;
-
     // Fetch available metadata versions. If error, revert to legacy metadata code.
     async fn fetch_available_versions(
         client: &impl ClientT,
@@ -108,31 +75,7 @@ async fn fetch_metadata(client: impl ClientT, version: MetadataVersion) -> Resul
         supported_versions: Vec<u32>,
     ) -> Result<Vec<u8>, Error> {
         // Return the version the user wants if it's supported:
-        let version = match version {
-            MetadataVersion::Latest => *supported_versions
-                .iter()
-                .filter(|&&v| v != UNSTABLE_METADATA_VERSION)
-                .max()
-                .ok_or_else(|| Error::Other("No valid metadata versions returned".to_string()))?,
-            MetadataVersion::Unstable => {
-                if supported_versions.contains(&UNSTABLE_METADATA_VERSION) {
-                    UNSTABLE_METADATA_VERSION
-                } else {
-                    return Err(Error::Other(
-                        "The node does not have an unstable metadata version available".to_string(),
-                    ));
-                }
-            }
-            MetadataVersion::Version(version) => {
-                if supported_versions.contains(&version) {
-                    version
-                } else {
-                    return Err(Error::Other(format!(
-                        "The node does not have metadata version {version} available"
-                    )));
-                }
-            }
-        };
+        let version = version.select(&supported_versions, "node")?;
 
         let bytes = version.encode();
         let version: String = format!("0x{}", hex::encode(&bytes));
@@ -0,0 +1,152 @@
+// Copyright 2019-2025 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Fetch metadata from a Wasm runtime blob.
+
+use crate::{Error, MetadataVersion};
+use codec::{Decode, Encode};
+use sc_executor::{WasmExecutionMethod, WasmExecutor};
+use sc_executor_common::runtime_blob::RuntimeBlob;
+use sp_maybe_compressed_blob::CODE_BLOB_BOMB_LIMIT;
+
+/// Returns the metadata bytes from the provided Wasm runtime file, blocking the current thread.
+pub fn from_wasm_file_blocking(
+    path: &std::path::Path,
+    version: MetadataVersion,
+) -> Result<Vec<u8>, Error> {
+    let wasm = crate::from_file_blocking(path)?;
+    from_wasm(&wasm, version)
+}
+
+/// Returns the metadata bytes from the provided Wasm runtime blob, which may be compressed
+/// (as is the case for the `:code` stored on chain or a `*.compact.compressed.wasm` file).
+/// The runtime is executed locally in order to obtain the metadata.
+pub fn from_wasm(wasm: &[u8], version: MetadataVersion) -> Result<Vec<u8>, Error> {
+    let wasm = sp_maybe_compressed_blob::decompress(wasm, CODE_BLOB_BOMB_LIMIT)
+        .map_err(|e| Error::Wasm(e.to_string()))?;
+    let mut executor = Executor::new(&wasm)?;
+
+    match executor.versions() {
+        Ok(available_versions) => {
+            let version = version.select(&available_versions, "runtime")?;
+            executor.load_metadata_at_version(version)
+        }
+        Err(e) => {
+            // The "new" interface failed. if the user is asking for V14 or the "latest"
+            // metadata then try the legacy interface instead. Else, just return the
+            // reason for failure.
+            if matches!(
+                version,
+                MetadataVersion::Version(14) | MetadataVersion::Latest
+            ) {
+                executor.load_legacy_metadata()
+            } else {
+                Err(e)
+            }
+        }
+    }
+}
+
+struct Executor {
+    runtime_blob: RuntimeBlob,
+    executor: WasmExecutor<sp_io::SubstrateHostFunctions>,
+    externalities: sp_state_machine::BasicExternalities,
+}
+
+impl Executor {
+    fn new(wasm: &[u8]) -> Result<Self, Error> {
+        let externalities: sp_state_machine::BasicExternalities = Default::default();
+
+        let executor: WasmExecutor<sp_io::SubstrateHostFunctions> = WasmExecutor::builder()
+            .with_execution_method(WasmExecutionMethod::default())
+            .with_offchain_heap_alloc_strategy(sc_executor::HeapAllocStrategy::Dynamic {
+                maximum_pages: Some(64),
+            })
+            .with_max_runtime_instances(1)
+            .with_runtime_cache_size(1)
+            .build();
+
+        let runtime_blob = RuntimeBlob::new(wasm).map_err(|e| Error::Wasm(e.to_string()))?;
+
+        Ok(Self {
+            runtime_blob,
+            executor,
+            externalities,
+        })
+    }
+
+    fn call(&mut self, method: &str, data: &[u8]) -> Result<Vec<u8>, Error> {
+        self.executor
+            .uncached_call(
+                self.runtime_blob.clone(),
+                &mut self.externalities,
+                true,
+                method,
+                data,
+            )
+            .map_err(|e| {
+                Error::Wasm(format!(
+                    "Failed to call \"{method}\" on WASM runtime. Cause: {e}"
+                ))
+            })
+    }
+
+    fn versions(&mut self) -> Result<Vec<u32>, Error> {
+        let versions = self.call("Metadata_metadata_versions", &[])?;
+        Decode::decode(&mut &versions[..]).map_err(Into::into)
+    }
+
+    fn load_legacy_metadata(&mut self) -> Result<Vec<u8>, Error> {
+        let metadata = self.call("Metadata_metadata", &[])?;
+        let metadata: Vec<u8> = Decode::decode(&mut &metadata[..])?;
+        Ok(metadata)
+    }
+
+    fn load_metadata_at_version(&mut self, version: u32) -> Result<Vec<u8>, Error> {
+        let metadata = self.call("Metadata_metadata_at_version", &version.encode())?;
+        let Some(metadata) = <Option<Vec<u8>>>::decode(&mut &metadata[..])? else {
+            return Err(Error::Other(format!(
+                "The runtime does not have metadata version {version} available"
+            )));
+        };
+        Ok(metadata)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn westend_runtime() -> Vec<u8> {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../artifacts/westend_runtime.compact.compressed.wasm"
+        );
+        std::fs::read(path).expect("westend runtime artifact should exist")
+    }
+
+    // Encoded metadata starts with the magic number "meta" followed by the version.
+    fn metadata_version(bytes: &[u8]) -> u32 {
+        assert_eq!(&bytes[..4], b"meta");
+        bytes[4].into()
+    }
+
+    #[test]
+    fn fetches_latest_supported_metadata_from_compressed_wasm() {
+        let wasm = westend_runtime();
+        let bytes = from_wasm(&wasm, MetadataVersion::Latest).unwrap();
+        assert!(subxt_metadata::SUPPORTED_METADATA_VERSIONS.contains(&metadata_version(&bytes)));
+    }
+
+    #[test]
+    fn fails_if_version_is_unavailable() {
+        let wasm = westend_runtime();
+        assert!(from_wasm(&wasm, MetadataVersion::Version(1)).is_err());
+    }
+
+    #[test]
+    fn fails_if_wasm_is_invalid() {
+        assert!(from_wasm(b"not a wasm runtime", MetadataVersion::Latest).is_err());
+    }
+}